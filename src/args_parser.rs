@@ -1,17 +1,71 @@
 use std::{env, path::PathBuf};
 
+const LINT_COMMAND: &str = "lint";
+const JUNIT_OPTION: &str = "--junit";
+const SARIF_OPTION: &str = "--sarif";
+
 pub enum ArgsError {
     TooFew,
     NotDir,
+    /// An option that is not known, or one of the lint options without the path after it
+    BadOption(String),
+}
+
+pub enum Command {
+    /// Opens the project in the terminal UI
+    Open(PathBuf),
+    Lint(LintArgs),
 }
 
-pub fn parse() -> Result<PathBuf, ArgsError> {
+pub struct LintArgs {
+    pub path: PathBuf,
+    /// Where to write the JUnit XML report, if anywhere
+    pub junit: Option<PathBuf>,
+    /// Where to write the SARIF report, if anywhere
+    pub sarif: Option<PathBuf>,
+}
+
+pub fn parse() -> Result<Command, ArgsError> {
     let mut argv = env::args();
     let _program = argv.next();
     let path = match argv.next() {
         None => return Err(ArgsError::TooFew),
         Some(p) => p,
     };
+    if path == LINT_COMMAND {
+        return parse_lint(argv).map(Command::Lint);
+    }
+    project_dir(path).map(Command::Open)
+}
+
+fn parse_lint(mut argv: impl Iterator<Item = String>) -> Result<LintArgs, ArgsError> {
+    let mut path = None;
+    let mut junit = None;
+    let mut sarif = None;
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            JUNIT_OPTION => junit = Some(PathBuf::from(option_value(&mut argv, &arg)?)),
+            SARIF_OPTION => sarif = Some(PathBuf::from(option_value(&mut argv, &arg)?)),
+            _ if arg.starts_with("--") || path.is_some() => return Err(ArgsError::BadOption(arg)),
+            _ => path = Some(project_dir(arg)?),
+        }
+    }
+    Ok(LintArgs {
+        path: path.ok_or(ArgsError::TooFew)?,
+        junit,
+        sarif,
+    })
+}
+
+fn option_value(
+    argv: &mut impl Iterator<Item = String>,
+    option: &str,
+) -> Result<String, ArgsError> {
+    argv.next()
+        .ok_or_else(|| ArgsError::BadOption(option.to_owned()))
+}
+
+fn project_dir(path: String) -> Result<PathBuf, ArgsError> {
     let path = PathBuf::from(path);
     if !path.is_dir() {
         Err(ArgsError::NotDir)
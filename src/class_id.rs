@@ -5,6 +5,8 @@ use lazy_static::lazy_static;
 
 pub type ClassId = u32;
 
+pub const PREFAB_INSTANCE_CLASS_ID: ClassId = 1001;
+
 lazy_static! {
     pub static ref CLASS_IDS: HashMap<ClassId, &'static str> = hash_map![
         (0, "Object"),
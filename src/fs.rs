@@ -65,12 +65,15 @@ pub fn find_project_files(path: &Path) -> Result<ProjectFiles, Error> {
     Ok(file_paths)
 }
 
+/// Scenes, prefabs and assets are the Unity YAML files that can be opened
+pub fn is_unity_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| [SCENE_EXTENSION, ASSET_EXTENSION, PREFAB_EXTENSION].contains(&ext))
+}
+
 pub fn path_to_relative(full: &Path, base: &Path) -> Result<PathBuf, String> {
     full.strip_prefix(base)
         .map(|p| p.to_path_buf())
         .err_to_str()
 }
-
-pub fn path_to_absolute(relative: &Path, base: &Path) -> PathBuf {
-    base.join(relative)
-}
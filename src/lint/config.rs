@@ -0,0 +1,384 @@
+use super::{Rule, Severity, RULES};
+use crate::unity::error::{ParseError, ParseErrorKind};
+use glob::Pattern;
+use regex::Regex;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, io::ErrorKind, path::Path};
+
+/// The file at the root of the project the rules are read from
+pub const CONFIG_FILE_NAME: &str = "unity_tui.toml";
+
+/// The rules of the project and how the findings are reported. Without a config file only the
+/// built-in rules are checked, with their default severity
+#[derive(Debug, Default)]
+pub struct LintConfig {
+    /// The files that are not checked at all
+    ignore: PathFilter,
+    rules: HashMap<Rule, RuleSettings>,
+    pub forbidden_components: Vec<ForbiddenComponents>,
+    pub required_root_tags: Vec<RequiredRootTags>,
+    pub name_patterns: Vec<NamePattern>,
+    pub max_depths: Vec<MaxDepth>,
+}
+
+#[derive(Debug)]
+struct RuleSettings {
+    /// None turns the rule off
+    severity: Option<Severity>,
+    ignore: PathFilter,
+}
+
+/// Components that cannot be added to the objects of some files, by class name or script name
+#[derive(Debug)]
+pub struct ForbiddenComponents {
+    pub folders: PathFilter,
+    pub components: Vec<String>,
+}
+
+/// Tags one of which the root game objects of some files must have
+#[derive(Debug)]
+pub struct RequiredRootTags {
+    pub folders: PathFilter,
+    pub tags: Vec<String>,
+}
+
+/// A pattern the names of the game objects of some files must match
+#[derive(Debug)]
+pub struct NamePattern {
+    pub folders: PathFilter,
+    pub pattern: Regex,
+}
+
+/// How deep game objects can be nested in some files, root objects are at depth 1
+#[derive(Debug)]
+pub struct MaxDepth {
+    pub folders: PathFilter,
+    pub depth: usize,
+}
+
+/// Paths relative to the project given as glob patterns, like `Assets/UI/**/*.prefab`, or as
+/// folders, like `Assets/UI`
+#[derive(Debug, Default)]
+pub struct PathFilter {
+    patterns: Vec<Pattern>,
+}
+
+impl PathFilter {
+    fn new(patterns: &[String]) -> Result<Self, String> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).map_err(|e| format!("invalid pattern \"{}\": {}", pattern, e))
+            })
+            .collect::<Result<Vec<Pattern>, String>>()?;
+        Ok(PathFilter { patterns })
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        let text = path.to_string_lossy().replace('\\', "/");
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches(&text) || path.starts_with(pattern.as_str()))
+    }
+
+    /// An empty filter of folders means every file
+    pub fn matches_or_empty(&self, path: &Path) -> bool {
+        self.patterns.is_empty() || self.matches(path)
+    }
+}
+
+impl LintConfig {
+    /// The severity the findings of the rule are reported with, None if it is off for the file
+    pub fn get_severity(&self, rule: Rule, path: &Path) -> Option<Severity> {
+        if self.ignore.matches(path) {
+            return None;
+        }
+        match self.rules.get(&rule) {
+            Some(settings) if settings.ignore.matches(path) => None,
+            Some(settings) => settings.severity,
+            None => Some(rule.default_severity()),
+        }
+    }
+}
+
+/// Reads the config file of the project, the default config if there is none
+pub fn read_config(project_path: &Path) -> Result<LintConfig, ParseError> {
+    let path = project_path.join(CONFIG_FILE_NAME);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(LintConfig::default()),
+        Err(e) => {
+            return Err(ParseError::new(ParseErrorKind::Io, e.to_string())
+                .with_path(Path::new(CONFIG_FILE_NAME)))
+        }
+    };
+    parse_config(&text).map_err(|e| e.with_path(Path::new(CONFIG_FILE_NAME)))
+}
+
+fn parse_config(text: &str) -> Result<LintConfig, ParseError> {
+    let raw = toml::from_str::<RawConfig>(text).map_err(|e| {
+        let line = e.line_col().map(|(line, _)| line + 1);
+        let message = e.to_string();
+        // the line is kept in `line`, like the errors of the Unity files
+        let message = match message.rfind(" at line ") {
+            Some(idx) => message[..idx].to_owned(),
+            None => message,
+        };
+        ParseError::new(ParseErrorKind::Syntax, message).with_line(line)
+    })?;
+    raw.validate()
+        .map_err(|message| ParseError::new(ParseErrorKind::Document, message))
+}
+
+/// The config as it is written in the file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct RawConfig {
+    ignore: Vec<String>,
+    rules: HashMap<String, RawRuleSettings>,
+    forbidden_components: Vec<RawForbiddenComponents>,
+    required_root_tags: Vec<RawRequiredRootTags>,
+    name_patterns: Vec<RawNamePattern>,
+    max_depth: Vec<RawMaxDepth>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawRuleSettings {
+    /// `error`, `warning` or `off`
+    severity: Option<String>,
+    ignore: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawForbiddenComponents {
+    #[serde(default)]
+    folders: Vec<String>,
+    components: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRequiredRootTags {
+    #[serde(default)]
+    folders: Vec<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawNamePattern {
+    #[serde(default)]
+    folders: Vec<String>,
+    pattern: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawMaxDepth {
+    #[serde(default)]
+    folders: Vec<String>,
+    depth: usize,
+}
+
+impl RawConfig {
+    fn validate(self) -> Result<LintConfig, String> {
+        let mut rules = HashMap::new();
+        for (id, settings) in self.rules {
+            let rule = RULES
+                .iter()
+                .copied()
+                .find(|rule| rule.id() == id)
+                .ok_or_else(|| format!("unknown rule \"{}\"", id))?;
+            let severity = match settings.severity.as_deref() {
+                None => Some(rule.default_severity()),
+                Some("error") => Some(Severity::Error),
+                Some("warning") => Some(Severity::Warning),
+                Some("off") => None,
+                Some(other) => {
+                    return Err(format!(
+                        "unknown severity \"{}\" for {}, expected error, warning or off",
+                        other, id
+                    ))
+                }
+            };
+            let ignore = PathFilter::new(&settings.ignore)?;
+            rules.insert(rule, RuleSettings { severity, ignore });
+        }
+        Ok(LintConfig {
+            ignore: PathFilter::new(&self.ignore)?,
+            rules,
+            forbidden_components: self
+                .forbidden_components
+                .into_iter()
+                .map(|raw| {
+                    Ok(ForbiddenComponents {
+                        folders: PathFilter::new(&raw.folders)?,
+                        components: raw.components,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            required_root_tags: self
+                .required_root_tags
+                .into_iter()
+                .map(|raw| {
+                    Ok(RequiredRootTags {
+                        folders: PathFilter::new(&raw.folders)?,
+                        tags: raw.tags,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            name_patterns: self
+                .name_patterns
+                .into_iter()
+                .map(|raw| {
+                    let pattern = Regex::new(&raw.pattern)
+                        .map_err(|e| format!("invalid name pattern \"{}\": {}", raw.pattern, e))?;
+                    Ok(NamePattern {
+                        folders: PathFilter::new(&raw.folders)?,
+                        pattern,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            max_depths: self
+                .max_depth
+                .into_iter()
+                .map(|raw| {
+                    Ok(MaxDepth {
+                        folders: PathFilter::new(&raw.folders)?,
+                        depth: raw.depth,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_config, LintConfig, PathFilter};
+    use crate::{
+        lint::{Rule, Severity},
+        unity::error::ParseErrorKind,
+    };
+    use std::path::Path;
+
+    fn error(text: &str) -> (ParseErrorKind, String) {
+        let error = parse_config(text).unwrap_err();
+        (error.kind, error.message)
+    }
+
+    fn severity(config: &LintConfig, rule: Rule, path: &str) -> Option<Severity> {
+        config.get_severity(rule, Path::new(path))
+    }
+
+    #[test]
+    fn rules_keep_their_default_severity() {
+        let config = parse_config("").unwrap();
+        assert_eq!(
+            severity(&config, Rule::MissingScript, "Assets/Main.unity"),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            severity(&config, Rule::MaxDepth, "Assets/Main.unity"),
+            Some(Severity::Warning)
+        );
+    }
+
+    #[test]
+    fn rules_can_be_turned_off_or_ignored_for_some_files() {
+        let config = parse_config(
+            r#"
+ignore = ["Assets/ThirdParty"]
+
+[rules.missing-script]
+severity = "off"
+
+[rules.broken-reference]
+severity = "warning"
+ignore = ["Assets/**/*.prefab"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            severity(&config, Rule::MissingScript, "Assets/Main.unity"),
+            None
+        );
+        assert_eq!(
+            severity(&config, Rule::BrokenReference, "Assets/Main.unity"),
+            Some(Severity::Warning)
+        );
+        assert_eq!(
+            severity(&config, Rule::BrokenReference, "Assets/UI/Button.prefab"),
+            None
+        );
+        assert_eq!(
+            severity(
+                &config,
+                Rule::DuplicateFileId,
+                "Assets/ThirdParty/Demo.unity"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn folders_match_everything_below_them_and_globs_only_what_they_match() {
+        let filter =
+            PathFilter::new(&["Assets/UI".to_owned(), "Assets/*.unity".to_owned()]).unwrap();
+        assert!(filter.matches(Path::new("Assets/UI/Menus/Main.prefab")));
+        assert!(filter.matches(Path::new("Assets/Main.unity")));
+        // folders are compared by whole path components, not as the start of the text
+        assert!(!filter.matches(Path::new("Assets/UIKit/Main.prefab")));
+        assert!(!filter.matches(Path::new("Assets/Main.prefab")));
+        assert!(!PathFilter::default().matches(Path::new("Assets/Main.unity")));
+        assert!(PathFilter::default().matches_or_empty(Path::new("Assets/Main.unity")));
+    }
+
+    #[test]
+    fn mistakes_in_the_config_are_errors() {
+        assert_eq!(
+            error("[rules.missing-scripts]\nseverity = \"off\"\n"),
+            (
+                ParseErrorKind::Document,
+                "unknown rule \"missing-scripts\"".to_owned()
+            )
+        );
+        assert_eq!(
+            error("[rules.missing-script]\nseverity = \"fatal\"\n"),
+            (
+                ParseErrorKind::Document,
+                "unknown severity \"fatal\" for missing-script, expected error, warning or off"
+                    .to_owned()
+            )
+        );
+        let (kind, message) = error("[[name-patterns]]\npattern = \"[a-z\"\n");
+        assert_eq!(kind, ParseErrorKind::Document);
+        assert!(
+            message.starts_with("invalid name pattern \"[a-z\""),
+            "{}",
+            message
+        );
+        let (kind, message) = error("ignore = [\"Assets/[\"]\n");
+        assert_eq!(kind, ParseErrorKind::Document);
+        assert!(
+            message.starts_with("invalid pattern \"Assets/[\""),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_errors() {
+        let error =
+            parse_config("\n[[max-depth]]\ndepth = 4\nfolder = [\"Assets\"]\n").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::Syntax);
+        assert_eq!(error.line, Some(2));
+        assert!(
+            error.message.contains("unknown field `folder`"),
+            "{}",
+            error.message
+        );
+    }
+}
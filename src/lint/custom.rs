@@ -0,0 +1,223 @@
+use super::{config::LintConfig, Rule};
+use crate::unity::{self, object::GetId, repository::MetaFilesRepository, Id};
+use std::{collections::HashSet, path::Path};
+
+/// Checks the rules of the config file that apply to the file, the findings are returned with the
+/// id of the object they are about
+pub(super) fn check_config_rules(
+    path: &Path,
+    repo: &unity::Repository,
+    meta_files: &MetaFilesRepository,
+    config: &LintConfig,
+) -> Vec<(Rule, Id, String)> {
+    let mut out = vec![];
+    for forbidden in config
+        .forbidden_components
+        .iter()
+        .filter(|forbidden| forbidden.folders.matches_or_empty(path))
+    {
+        for component in repo.get_ids().filter_map(|id| repo.get_component(id)) {
+            let name = component.get_name(meta_files);
+            if !forbidden.components.contains(&name) {
+                continue;
+            }
+            // components added to a prefab instance belong to a game object of the source prefab
+            let owner = repo
+                .get_game_object(component.get_game_object_id())
+                .map_or_else(|| "a prefab instance".to_owned(), |go| go.name.clone());
+            out.push((
+                Rule::ForbiddenComponent,
+                component.get_id().clone(),
+                format!("{} has a {}, which is not allowed here", owner, name),
+            ));
+        }
+    }
+    for required in config
+        .required_root_tags
+        .iter()
+        .filter(|required| required.folders.matches_or_empty(path))
+    {
+        // the tags of prefab instances are in their source prefab, they are checked there
+        for go in repo
+            .get_unparented_transforms()
+            .into_iter()
+            .filter_map(|transform| repo.get_game_object(transform.get_game_object_id()))
+        {
+            if !required.tags.contains(&go.tag) {
+                out.push((
+                    Rule::RequiredRootTag,
+                    go.id.clone(),
+                    format!(
+                        "the root object {} has the tag {}, expected {}",
+                        go.name,
+                        go.tag,
+                        required.tags.join(" or ")
+                    ),
+                ));
+            }
+        }
+    }
+    for naming in config
+        .name_patterns
+        .iter()
+        .filter(|naming| naming.folders.matches_or_empty(path))
+    {
+        for go in repo.get_ids().filter_map(|id| repo.get_game_object(id)) {
+            if !naming.pattern.is_match(&go.name) {
+                out.push((
+                    Rule::NamePattern,
+                    go.id.clone(),
+                    format!(
+                        "the name \"{}\" does not match {}",
+                        go.name,
+                        naming.pattern.as_str()
+                    ),
+                ));
+            }
+        }
+    }
+    for max in config
+        .max_depths
+        .iter()
+        .filter(|max| max.folders.matches_or_empty(path))
+    {
+        // only the first level that is too deep is reported, not every object under it
+        for go in repo.get_ids().filter_map(|id| repo.get_game_object(id)) {
+            let Some(transform) = go
+                .component_ids
+                .iter()
+                .find_map(|id| repo.get_transform(id)) else {
+                continue;
+            };
+            let depth = get_depth(repo, transform);
+            if depth == max.depth + 1 {
+                out.push((
+                    Rule::MaxDepth,
+                    go.id.clone(),
+                    format!(
+                        "{} is nested {} levels deep, the limit is {}",
+                        go.name, depth, max.depth
+                    ),
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// Root objects are at depth 1. A parent that belongs to a prefab instance counts as one level,
+/// above it the hierarchy goes on from the transform the instance is parented to
+fn get_depth(repo: &unity::Repository, transform: &unity::Transform) -> usize {
+    let mut depth = 1;
+    // the id of the next transform up the hierarchy, "0" above the root
+    let mut parent_id = transform.get_father_id().clone();
+    let mut visited = HashSet::new();
+    while parent_id != "0" && visited.insert(parent_id.clone()) {
+        depth += 1;
+        parent_id = match repo.get_transform(&parent_id) {
+            Some(father) => father.get_father_id().clone(),
+            None => {
+                let Some(instance) = repo
+                    .get_stripped(&parent_id)
+                    .and_then(|stripped| repo.get_prefab_instance(&stripped.prefab_instance_id)) else {
+                    break;
+                };
+                instance.transform_parent_id.clone()
+            }
+        };
+    }
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_config_rules;
+    use crate::{
+        fs::ProjectFiles,
+        lint::{
+            config::{LintConfig, MaxDepth, PathFilter},
+            Rule,
+        },
+        unity::{
+            repository::construct_meta_repository,
+            test_util::{read_repository, SCENE},
+            Id,
+        },
+    };
+    use std::path::Path;
+
+    /// An instance parented to Player, with Sight added under the root of the instance
+    const INSTANCE: &str = "\
+--- !u!1001 &700
+PrefabInstance:
+  m_ObjectHideFlags: 0
+  serializedVersion: 2
+  m_Modification:
+    serializedVersion: 3
+    m_TransformParent: {fileID: 101}
+    m_Modifications: []
+    m_RemovedComponents: []
+  m_SourcePrefab: {fileID: 100100000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+--- !u!4 &800 stripped
+Transform:
+  m_CorrespondingSourceObject: {fileID: 400, guid: 0123456789abcdef0123456789abcdef, type: 3}
+  m_PrefabInstance: {fileID: 700}
+  m_PrefabAsset: {fileID: 0}
+--- !u!1 &130
+GameObject:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  serializedVersion: 6
+  m_Component:
+  - component: {fileID: 131}
+  m_Layer: 0
+  m_Name: Sight
+  m_TagString: Untagged
+  m_Icon: {fileID: 0}
+  m_NavMeshLayer: 0
+  m_StaticEditorFlags: 0
+  m_IsActive: 1
+--- !u!4 &131
+Transform:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  m_GameObject: {fileID: 130}
+  serializedVersion: 2
+  m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}
+  m_LocalPosition: {x: 0, y: 0, z: 0}
+  m_LocalScale: {x: 1, y: 1, z: 1}
+  m_ConstrainProportionsScale: 0
+  m_Children: []
+  m_Father: {fileID: 800}
+  m_RootOrder: 0
+  m_LocalEulerAnglesHint: {x: 0, y: 0, z: 0}
+";
+
+    fn too_deep(max_depth: usize) -> Vec<(Rule, Id, String)> {
+        let repo = read_repository(&format!("{}{}", SCENE, INSTANCE));
+        let (meta_files, _) = construct_meta_repository(&ProjectFiles::new(Path::new("")));
+        let mut config = LintConfig::default();
+        config.max_depths.push(MaxDepth {
+            folders: PathFilter::default(),
+            depth: max_depth,
+        });
+        check_config_rules(Path::new("Assets/Main.unity"), &repo, &meta_files, &config)
+    }
+
+    #[test]
+    fn objects_under_prefab_instances_count_the_parents_of_the_instance() {
+        assert_eq!(
+            too_deep(2),
+            vec![(
+                Rule::MaxDepth,
+                "130".to_owned(),
+                "Sight is nested 3 levels deep, the limit is 2".to_owned()
+            )]
+        );
+        assert_eq!(too_deep(3), vec![]);
+    }
+}
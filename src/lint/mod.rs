@@ -0,0 +1,569 @@
+mod config;
+mod custom;
+mod report;
+
+pub use config::{read_config, LintConfig};
+pub use report::{write_junit, write_sarif, write_text};
+
+use crate::{
+    fs::ProjectFiles,
+    unity::{
+        self,
+        error::ParseError,
+        object::GetId,
+        references::{self, BrokenReferenceKind},
+        repository::{construct_meta_repository, MetaFilesRepository},
+        yaml::{self, YamlUnityDocument, YamlUnityFile},
+        Id,
+    },
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Unity only needs meta files for what is in these folders
+const META_FILE_ROOTS: [&str; 2] = ["Assets", "Packages"];
+
+/// What a finding is about, the ids are the ones used in the reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rule {
+    InvalidFile,
+    InvalidMetaFile,
+    MissingScript,
+    BrokenReference,
+    DuplicateFileId,
+    InconsistentLink,
+    MissingMetaFile,
+    ForbiddenComponent,
+    RequiredRootTag,
+    NamePattern,
+    MaxDepth,
+}
+
+pub const RULES: [Rule; 11] = [
+    Rule::InvalidFile,
+    Rule::InvalidMetaFile,
+    Rule::MissingScript,
+    Rule::BrokenReference,
+    Rule::DuplicateFileId,
+    Rule::InconsistentLink,
+    Rule::MissingMetaFile,
+    Rule::ForbiddenComponent,
+    Rule::RequiredRootTag,
+    Rule::NamePattern,
+    Rule::MaxDepth,
+];
+
+impl Rule {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::InvalidFile => "invalid-file",
+            Rule::InvalidMetaFile => "invalid-meta-file",
+            Rule::MissingScript => "missing-script",
+            Rule::BrokenReference => "broken-reference",
+            Rule::DuplicateFileId => "duplicate-file-id",
+            Rule::InconsistentLink => "inconsistent-link",
+            Rule::MissingMetaFile => "missing-meta-file",
+            Rule::ForbiddenComponent => "forbidden-component",
+            Rule::RequiredRootTag => "required-root-tag",
+            Rule::NamePattern => "name-pattern",
+            Rule::MaxDepth => "max-depth",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Rule::InvalidFile => "A scene, prefab or asset or one of its documents cannot be read",
+            Rule::InvalidMetaFile => "A meta file cannot be read or has the guid of another one",
+            Rule::MissingScript => "The script of a MonoBehaviour is not in the project",
+            Rule::BrokenReference => {
+                "A reference points to an asset that is not in the project or to an object that is not in the file"
+            }
+            Rule::DuplicateFileId => "Two documents of a file have the same fileID",
+            Rule::InconsistentLink => {
+                "A parent and a child, or a game object and a component, do not list each other"
+            }
+            Rule::MissingMetaFile => "An asset has no meta file",
+            Rule::ForbiddenComponent => "A game object has a component the config does not allow",
+            Rule::RequiredRootTag => "A root game object does not have one of the tags of the config",
+            Rule::NamePattern => "The name of a game object does not match the pattern of the config",
+            Rule::MaxDepth => "A game object is nested deeper than the config allows",
+        }
+    }
+
+    /// The rules of the config file are warnings unless it says otherwise
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Rule::ForbiddenComponent
+            | Rule::RequiredRootTag
+            | Rule::NamePattern
+            | Rule::MaxDepth => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found in a file of the project
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub rule: Rule,
+    pub severity: Severity,
+    /// Relative to the project
+    pub path: PathBuf,
+    /// The line of the file, counted from 1
+    pub line: Option<usize>,
+    /// The fileID of the document the problem is in
+    pub file_id: Option<Id>,
+    pub class_name: Option<String>,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(rule: Rule, path: &Path, message: impl Into<String>) -> Self {
+        Finding {
+            rule,
+            severity: Severity::Error,
+            path: path.to_path_buf(),
+            line: None,
+            file_id: None,
+            class_name: None,
+            message: message.into(),
+        }
+    }
+
+    fn from_parse_error(rule: Rule, error: ParseError, path: &Path) -> Self {
+        Finding {
+            line: error.line,
+            file_id: error.file_id,
+            class_name: error.class_name,
+            ..Finding::new(rule, path, error.message)
+        }
+    }
+
+    fn with_document(mut self, doc: &YamlUnityDocument, class_name: &str) -> Self {
+        self.line = doc.line;
+        self.file_id = Some(doc.id.clone());
+        self.class_name = Some(class_name.to_owned());
+        self
+    }
+}
+
+/// Written like `Assets/Main.unity:12: error[missing-script]: MonoBehaviour &4: message`
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        write!(f, " {}[{}]: ", self.severity, self.rule.id())?;
+        match (&self.class_name, &self.file_id) {
+            (Some(class_name), Some(file_id)) => write!(f, "{} &{}: ", class_name, file_id)?,
+            (None, Some(file_id)) => write!(f, "&{}: ", file_id)?,
+            _ => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The findings of a whole project
+pub struct LintReport {
+    /// The files that were read, relative to the project
+    pub checked_files: Vec<PathBuf>,
+    /// In the order of the files, then of the lines
+    pub findings: Vec<Finding>,
+}
+
+impl LintReport {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    pub fn get_file_findings<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a Finding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.path == path)
+    }
+}
+
+/// Checks every scene, prefab and text asset of the project and the meta files, binary assets are
+/// left out
+pub fn lint_project(project: &ProjectFiles, config: &LintConfig) -> LintReport {
+    let (meta_files, meta_errors) = construct_meta_repository(project);
+    let mut findings = meta_errors
+        .into_iter()
+        .map(|error| {
+            let path = error.path.clone().unwrap_or_default();
+            Finding::from_parse_error(Rule::InvalidMetaFile, error, &path)
+        })
+        .collect::<Vec<Finding>>();
+    let mut checked_files = vec![];
+    let mut paths = project
+        .scenes
+        .iter()
+        .chain(project.prefabs.iter())
+        .chain(project.assets.iter())
+        .collect::<Vec<&PathBuf>>();
+    paths.sort();
+    for path in paths {
+        let relative = path.strip_prefix(&project.base_path).unwrap_or(path);
+        if project.assets.contains(path) && yaml::is_binary_file(path) {
+            continue;
+        }
+        checked_files.push(relative.to_path_buf());
+        findings.extend(check_meta_file(path, relative));
+        findings.extend(lint_file(path, relative, &meta_files, config));
+    }
+    let mut findings = apply_config(findings, config);
+    findings.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+    LintReport {
+        checked_files,
+        findings,
+    }
+}
+
+fn check_meta_file(path: &Path, relative: &Path) -> Option<Finding> {
+    let needs_meta = relative
+        .components()
+        .next()
+        .is_some_and(|root| META_FILE_ROOTS.iter().any(|name| root.as_os_str() == *name));
+    let mut meta_path = path.as_os_str().to_owned();
+    meta_path.push(".meta");
+    (needs_meta && !Path::new(&meta_path).exists()).then(|| {
+        Finding::new(
+            Rule::MissingMetaFile,
+            relative,
+            "the file has no meta file, Unity gives it a new guid on every machine",
+        )
+    })
+}
+
+/// Sets the severity the config gives to the findings, the ones of rules that are off for their
+/// file are left out
+fn apply_config(findings: Vec<Finding>, config: &LintConfig) -> Vec<Finding> {
+    findings
+        .into_iter()
+        .filter_map(|finding| {
+            let severity = config.get_severity(finding.rule, &finding.path)?;
+            Some(Finding {
+                severity,
+                ..finding
+            })
+        })
+        .collect()
+}
+
+/// Reads and checks a single Unity file, `relative` is the path shown in the findings. The config
+/// is not applied to them
+fn lint_file(
+    path: &Path,
+    relative: &Path,
+    meta_files: &MetaFilesRepository,
+    config: &LintConfig,
+) -> Vec<Finding> {
+    match yaml::parse_unity_file(path) {
+        Ok(file) => check_unity_file(&file, relative, meta_files, config),
+        Err(error) => vec![Finding::from_parse_error(
+            Rule::InvalidFile,
+            error,
+            relative,
+        )],
+    }
+}
+
+/// Checks a Unity file that is already read, like the one open in the hierarchy view, with the
+/// config applied to the findings
+pub fn lint_unity_file(
+    file: &YamlUnityFile,
+    relative: &Path,
+    meta_files: &MetaFilesRepository,
+    config: &LintConfig,
+) -> Vec<Finding> {
+    apply_config(check_unity_file(file, relative, meta_files, config), config)
+}
+
+fn check_unity_file(
+    file: &YamlUnityFile,
+    relative: &Path,
+    meta_files: &MetaFilesRepository,
+    config: &LintConfig,
+) -> Vec<Finding> {
+    let mut findings = file
+        .errors
+        .iter()
+        .cloned()
+        .map(|error| Finding::from_parse_error(Rule::InvalidFile, error, relative))
+        .collect::<Vec<Finding>>();
+    let (repo, errors) = unity::construct_repository(file.documents.clone());
+    findings.extend(
+        errors
+            .into_iter()
+            .map(|error| Finding::from_parse_error(Rule::InvalidFile, error, relative)),
+    );
+
+    let mut docs_by_id = HashMap::<&Id, &YamlUnityDocument>::new();
+    for doc in file.documents.iter().filter(|doc| !doc.is_unreadable()) {
+        if let Some(first) = docs_by_id.get(&doc.id) {
+            let class_name = document_class_name(doc);
+            findings.push(
+                Finding::new(
+                    Rule::DuplicateFileId,
+                    relative,
+                    format!(
+                        "the fileID is also used by the {} at line {}",
+                        document_class_name(first),
+                        first.line.unwrap_or_default()
+                    ),
+                )
+                .with_document(doc, &class_name),
+            );
+        } else {
+            docs_by_id.insert(&doc.id, doc);
+        }
+    }
+
+    for broken in references::find_broken_references(&file.documents, meta_files) {
+        let rule = match broken.kind {
+            BrokenReferenceKind::Script => Rule::MissingScript,
+            BrokenReferenceKind::Asset | BrokenReferenceKind::Object => Rule::BrokenReference,
+        };
+        findings.push(Finding {
+            line: broken.line,
+            file_id: Some(broken.file_id.clone()),
+            class_name: Some(broken.class_name.clone()),
+            ..Finding::new(
+                rule,
+                relative,
+                format!(
+                    "{}: {}",
+                    broken.property_path,
+                    broken.kind.describe(&broken.reference)
+                ),
+            )
+        });
+    }
+
+    let links = find_inconsistent_links(&repo)
+        .into_iter()
+        .map(|(id, message)| (Rule::InconsistentLink, id, message));
+    let config_rules = custom::check_config_rules(relative, &repo, meta_files, config);
+    for (rule, id, message) in links.chain(config_rules) {
+        let mut finding = Finding::new(rule, relative, message);
+        if let Some(doc) = docs_by_id.get(&id) {
+            finding = finding.with_document(doc, &document_class_name(doc));
+        }
+        findings.push(finding);
+    }
+    findings
+}
+
+fn document_class_name(doc: &YamlUnityDocument) -> String {
+    crate::class_id::CLASS_IDS
+        .get(&doc.class_id)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| doc.class_id.to_string())
+}
+
+/// Finds the transforms and the parents, and the game objects and the components, that do not
+/// list each other. Links to objects that are not in the file are broken references, and the
+/// objects of prefab instances only store the link to their source, so both are left out
+fn find_inconsistent_links(repo: &unity::Repository) -> Vec<(Id, String)> {
+    let mut out = vec![];
+    for id in repo.get_ids() {
+        if let Some(transform) = repo.get_transform(id) {
+            let father_id = transform.get_father_id();
+            if let Some(father) = repo.get_transform(father_id) {
+                if !father.get_children_ids().contains(id) {
+                    out.push((
+                        id.clone(),
+                        format!(
+                            "the parent &{} does not list the transform in m_Children",
+                            father_id
+                        ),
+                    ));
+                }
+            }
+            for child_id in transform.get_children_ids() {
+                let Some(child) = repo.get_transform(child_id) else { continue; };
+                if child.get_father_id() != id {
+                    out.push((
+                        id.clone(),
+                        format!(
+                            "the child &{} has &{} as its m_Father",
+                            child_id,
+                            child.get_father_id()
+                        ),
+                    ));
+                }
+            }
+        }
+        if let Some(go) = repo.get_game_object(id) {
+            for component_id in &go.component_ids {
+                let Some(component) = repo.get_component(component_id) else { continue; };
+                if component.get_game_object_id() != id {
+                    out.push((
+                        id.clone(),
+                        format!(
+                            "the component &{} belongs to the game object &{}",
+                            component_id,
+                            component.get_game_object_id()
+                        ),
+                    ));
+                }
+            }
+        }
+        if let Some(component) = repo.get_component(id) {
+            let go_id = component.get_game_object_id();
+            if let Some(go) = repo.get_game_object(go_id) {
+                if !go.component_ids.contains(component.get_id()) {
+                    out.push((
+                        id.clone(),
+                        format!(
+                            "the game object &{} does not list the component in m_Component",
+                            go_id
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_unity_file, lint_project, LintConfig, Rule};
+    use crate::{
+        fs::ProjectFiles,
+        unity::{repository::construct_meta_repository, test_util::SCENE, yaml::parse_unity_str},
+    };
+    use std::{fs, path::Path};
+
+    /// Replaces the only occurrence of `from` in the test scene
+    fn edit_scene(from: &str, to: &str) -> String {
+        assert_eq!(SCENE.matches(from).count(), 1, "{}", from);
+        SCENE.replacen(from, to, 1)
+    }
+
+    /// Checks the file without any meta files, so every script is missing
+    fn check(content: &str) -> Vec<(Rule, Option<String>)> {
+        let (meta_files, _) = construct_meta_repository(&ProjectFiles::new(Path::new("")));
+        check_unity_file(
+            &parse_unity_str(content),
+            Path::new("Assets/Main.unity"),
+            &meta_files,
+            &LintConfig::default(),
+        )
+        .into_iter()
+        .map(|finding| (finding.rule, finding.file_id))
+        .collect()
+    }
+
+    fn missing_script() -> (Rule, Option<String>) {
+        (Rule::MissingScript, Some("102".to_owned()))
+    }
+
+    #[test]
+    fn scripts_that_are_not_in_the_project_are_missing() {
+        assert_eq!(check(SCENE), vec![missing_script()]);
+    }
+
+    #[test]
+    fn references_to_objects_that_are_not_in_the_file_are_broken() {
+        let content = edit_scene("  speed: 1.5\n", "  speed: 1.5\n  target: {fileID: 999}\n");
+        assert_eq!(
+            check(&content),
+            vec![
+                missing_script(),
+                (Rule::BrokenReference, Some("102".to_owned()))
+            ]
+        );
+    }
+
+    #[test]
+    fn documents_with_the_same_file_id_are_duplicates() {
+        let content = format!(
+            "{}--- !u!114 &102\nMonoBehaviour:\n  m_GameObject: {{fileID: 120}}\n",
+            SCENE
+        );
+        let findings = check(&content);
+        assert!(findings.contains(&(Rule::DuplicateFileId, Some("102".to_owned()))));
+    }
+
+    #[test]
+    fn parents_that_do_not_list_their_children_are_inconsistent() {
+        let content = edit_scene("  m_Children:\n  - {fileID: 111}\n", "  m_Children: []\n");
+        assert_eq!(
+            check(&content),
+            vec![
+                missing_script(),
+                (Rule::InconsistentLink, Some("111".to_owned()))
+            ]
+        );
+    }
+
+    #[test]
+    fn unreadable_documents_make_the_file_invalid() {
+        let content = format!("{}--- !u!1 &130\nGameObject: [\n", SCENE);
+        let findings = check(&content);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].0, Rule::InvalidFile);
+        assert!(findings.contains(&missing_script()));
+    }
+
+    #[test]
+    fn assets_need_a_readable_meta_file() {
+        let dir = std::env::temp_dir().join(format!("unity_tui_lint_{}", std::process::id()));
+        fs::create_dir_all(dir.join("Assets")).unwrap();
+        let scene = dir.join("Assets/Main.unity");
+        fs::write(&scene, "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n").unwrap();
+        fs::write(
+            dir.join("Assets/Other.prefab.meta"),
+            "fileFormatVersion: 2\n",
+        )
+        .unwrap();
+        let mut project = ProjectFiles::new(&dir);
+        project.scenes.push(scene);
+        project
+            .meta_files
+            .push(dir.join("Assets/Other.prefab.meta"));
+
+        let report = lint_project(&project, &LintConfig::default());
+        fs::remove_dir_all(&dir).unwrap();
+        let findings = report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule, finding.path.to_string_lossy().into_owned()))
+            .collect::<Vec<(Rule, String)>>();
+        assert_eq!(
+            findings,
+            vec![
+                (Rule::MissingMetaFile, "Assets/Main.unity".to_owned()),
+                (Rule::InvalidMetaFile, "Assets/Other.prefab.meta".to_owned())
+            ]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use super::{Finding, LintReport, Severity, RULES};
+use std::{
+    io::{Result, Write},
+    path::{Path, PathBuf},
+};
+
+const TOOL_NAME: &str = "unity_tui";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// One finding per line and a summary, like compilers write them
+pub fn write_text(out: &mut impl Write, report: &LintReport) -> Result<()> {
+    for finding in &report.findings {
+        writeln!(out, "{}", finding)?;
+    }
+    writeln!(
+        out,
+        "{} errors, {} warnings in {} files",
+        report.count(Severity::Error),
+        report.count(Severity::Warning),
+        report.checked_files.len()
+    )
+}
+
+/// A test case per file, failed if it has errors. Warnings do not fail it and are written to its
+/// output
+pub fn write_junit(out: &mut impl Write, report: &LintReport) -> Result<()> {
+    // meta files are only listed when they have findings
+    let mut paths = report
+        .checked_files
+        .iter()
+        .chain(report.findings.iter().map(|finding| &finding.path))
+        .collect::<Vec<&PathBuf>>();
+    paths.sort();
+    paths.dedup();
+    let failures = paths
+        .iter()
+        .filter(|path| {
+            report
+                .get_file_findings(path)
+                .any(|finding| finding.severity == Severity::Error)
+        })
+        .count();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="{0}" tests="{1}" failures="{2}">"#,
+        TOOL_NAME,
+        paths.len(),
+        failures
+    )?;
+    writeln!(
+        out,
+        r#"  <testsuite name="{0} lint" tests="{1}" failures="{2}" errors="0" skipped="0">"#,
+        TOOL_NAME,
+        paths.len(),
+        failures
+    )?;
+    for path in paths {
+        let name = escape_xml(&path.to_string_lossy());
+        let (errors, warnings): (Vec<&Finding>, Vec<&Finding>) = report
+            .get_file_findings(path)
+            .partition(|finding| finding.severity == Severity::Error);
+        if errors.is_empty() && warnings.is_empty() {
+            writeln!(out, r#"    <testcase classname="{0}" name="{0}"/>"#, name)?;
+            continue;
+        }
+        writeln!(out, r#"    <testcase classname="{0}" name="{0}">"#, name)?;
+        if let Some(first) = errors.first() {
+            writeln!(
+                out,
+                r#"      <failure message="{}" type="{}">{}</failure>"#,
+                escape_xml(&format!(
+                    "{} errors, the first: {}",
+                    errors.len(),
+                    first.message
+                )),
+                first.rule.id(),
+                escape_xml(&findings_to_lines(&errors))
+            )?;
+        }
+        if !warnings.is_empty() {
+            writeln!(
+                out,
+                "      <system-out>{}</system-out>",
+                escape_xml(&findings_to_lines(&warnings))
+            )?;
+        }
+        writeln!(out, "    </testcase>")?;
+    }
+    writeln!(out, "  </testsuite>")?;
+    writeln!(out, "</testsuites>")
+}
+
+fn findings_to_lines(findings: &[&Finding]) -> String {
+    findings
+        .iter()
+        .map(|finding| finding.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// A SARIF 2.1.0 log with a run listing every rule and a result per finding
+pub fn write_sarif(out: &mut impl Write, report: &LintReport) -> Result<()> {
+    let rules = RULES
+        .iter()
+        .map(|rule| {
+            format!(
+                r#"{{"id":"{}","shortDescription":{{"text":"{}"}}}}"#,
+                rule.id(),
+                escape_json(rule.description())
+            )
+        })
+        .collect::<Vec<String>>();
+    let results = report
+        .findings
+        .iter()
+        .map(|finding| {
+            let region = finding
+                .line
+                .map(|line| format!(r#","region":{{"startLine":{}}}"#, line))
+                .unwrap_or_default();
+            let message = match (&finding.class_name, &finding.file_id) {
+                (Some(class_name), Some(file_id)) => {
+                    format!("{} &{}: {}", class_name, file_id, finding.message)
+                }
+                _ => finding.message.clone(),
+            };
+            format!(
+                r#"{{"ruleId":"{}","level":"{}","message":{{"text":"{}"}},"locations":[{{"physicalLocation":{{"artifactLocation":{{"uri":"{}"}}{}}}}}]}}"#,
+                finding.rule.id(),
+                finding.severity,
+                escape_json(&message),
+                escape_json(&path_to_uri(&finding.path)),
+                region
+            )
+        })
+        .collect::<Vec<String>>();
+    writeln!(
+        out,
+        r#"{{"$schema":"{}","version":"{}","runs":[{{"tool":{{"driver":{{"name":"{}","version":"{}","rules":[{}]}}}},"results":[{}]}}]}}"#,
+        SARIF_SCHEMA,
+        SARIF_VERSION,
+        TOOL_NAME,
+        env!("CARGO_PKG_VERSION"),
+        rules.join(","),
+        results.join(",")
+    )
+}
+
+/// SARIF locations are URIs relative to the project, always with forward slashes
+fn path_to_uri(path: &Path) -> String {
+    path.components()
+        .map(|part| {
+            part.as_os_str()
+                .to_string_lossy()
+                .replace('%', "%25")
+                .replace(' ', "%20")
+                .replace('#', "%23")
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+fn escape_json(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_json, escape_xml, path_to_uri, write_junit, write_sarif};
+    use crate::lint::{Finding, LintReport, Rule, Severity};
+    use std::path::{Path, PathBuf};
+
+    fn finding(rule: Rule, severity: Severity, path: &str, line: Option<usize>) -> Finding {
+        Finding {
+            severity,
+            line,
+            file_id: line.map(|_| "4".to_owned()),
+            class_name: line.map(|_| "MonoBehaviour".to_owned()),
+            ..Finding::new(rule, Path::new(path), "the <script> & \"guid\"")
+        }
+    }
+
+    /// Errors in A, a warning in B and nothing in C
+    fn report() -> LintReport {
+        LintReport {
+            checked_files: ["Assets/A.unity", "Assets/B.unity", "Assets/C.unity"]
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            findings: vec![
+                finding(
+                    Rule::MissingScript,
+                    Severity::Error,
+                    "Assets/A.unity",
+                    Some(12),
+                ),
+                finding(
+                    Rule::BrokenReference,
+                    Severity::Error,
+                    "Assets/A.unity",
+                    None,
+                ),
+                finding(
+                    Rule::NamePattern,
+                    Severity::Warning,
+                    "Assets/B.unity",
+                    Some(3),
+                ),
+            ],
+        }
+    }
+
+    fn to_string(write: impl Fn(&mut Vec<u8>, &LintReport) -> std::io::Result<()>) -> String {
+        let mut out = vec![];
+        write(&mut out, &report()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Returns the lines from the test case of the file to its end
+    fn test_case<'a>(junit: &'a str, path: &str) -> Vec<&'a str> {
+        let start = format!(r#"<testcase classname="{0}" name="{0}""#, path);
+        junit
+            .lines()
+            .skip_while(|line| !line.trim_start().starts_with(&start))
+            .take_while(|line| !line.trim_start().starts_with("</testcase"))
+            .collect()
+    }
+
+    #[test]
+    fn files_with_errors_fail_in_junit() {
+        let junit = to_string(write_junit);
+
+        assert!(junit.contains(r#"<testsuites name="unity_tui" tests="3" failures="1">"#));
+        let a = test_case(&junit, "Assets/A.unity");
+        assert_eq!(
+            a[1],
+            r#"      <failure message="2 errors, the first: the &lt;script&gt; &amp; &quot;guid&quot;" type="missing-script">Assets/A.unity:12: error[missing-script]: MonoBehaviour &amp;4: the &lt;script&gt; &amp; &quot;guid&quot;"#
+        );
+
+        let b = test_case(&junit, "Assets/B.unity");
+        assert!(b.iter().all(|line| !line.contains("<failure")));
+        assert!(b[1].starts_with("      <system-out>Assets/B.unity:3: warning[name-pattern]"));
+
+        assert!(
+            junit.contains(r#"    <testcase classname="Assets/C.unity" name="Assets/C.unity"/>"#)
+        );
+    }
+
+    #[test]
+    fn findings_are_sarif_results() {
+        let sarif = to_string(write_sarif);
+
+        assert!(sarif.contains(
+            r#"{"ruleId":"missing-script","level":"error","message":{"text":"MonoBehaviour &4: the <script> & \"guid\""},"locations":[{"physicalLocation":{"artifactLocation":{"uri":"Assets/A.unity"},"region":{"startLine":12}}}]}"#
+        ));
+        // findings without a line have no region
+        assert!(sarif.contains(
+            r#"{"ruleId":"broken-reference","level":"error","message":{"text":"the <script> & \"guid\""},"locations":[{"physicalLocation":{"artifactLocation":{"uri":"Assets/A.unity"}}}]}"#
+        ));
+        assert!(sarif.contains(r#""ruleId":"name-pattern","level":"warning""#));
+        assert!(sarif.contains(r#"{"id":"max-depth","shortDescription":"#));
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            escape_xml(r#"a & <b> "c" 'd'"#),
+            "a &amp; &lt;b&gt; &quot;c&quot; &apos;d&apos;"
+        );
+        assert_eq!(
+            escape_json("say \"hi\"\n\\ \t\u{1}"),
+            r#"say \"hi\"\n\\ \t\u0001"#
+        );
+        assert_eq!(
+            path_to_uri(Path::new("Assets/My Scenes/#1 100%.unity")),
+            "Assets/My%20Scenes/%231%20100%25.unity"
+        );
+    }
+}
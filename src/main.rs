@@ -1,15 +1,16 @@
 mod args_parser;
 mod class_id;
 mod fs;
+mod lint;
 mod ui;
 mod unity;
 mod util;
 
 fn main() -> std::io::Result<()> {
-    use std::{io::stdout, panic, process::exit};
+    use std::process::exit;
 
-    let path = match args_parser::parse() {
-        Ok(path) => path,
+    let command = match args_parser::parse() {
+        Ok(command) => command,
         Err(e) => match e {
             args_parser::ArgsError::TooFew => {
                 print_usage();
@@ -19,9 +20,29 @@ fn main() -> std::io::Result<()> {
                 print_usage();
                 exit(1);
             }
+            args_parser::ArgsError::BadOption(option) => {
+                eprintln!("Unknown option or missing value: {}", option);
+                print_usage();
+                exit(1);
+            }
         },
     };
 
+    match command {
+        args_parser::Command::Open(path) => open(path),
+        args_parser::Command::Lint(args) => {
+            // the findings are written either way, the exit code is what fails a CI job
+            if !lint(args)? {
+                exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn open(path: std::path::PathBuf) -> std::io::Result<()> {
+    use std::{io::stdout, panic};
+
     let project = fs::find_project_files(&path)?;
 
     panic::set_hook(Box::new({
@@ -36,6 +57,41 @@ fn main() -> std::io::Result<()> {
     ui::cleanup_terminal(&mut stdout())
 }
 
+/// Writes the findings and the reports asked for, returns false if there are errors
+fn lint(args: args_parser::LintArgs) -> std::io::Result<bool> {
+    use std::{
+        fs::File,
+        io::{stdout, BufWriter, Write},
+    };
+
+    let config = match lint::read_config(&args.path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return Ok(false);
+        }
+    };
+    let project = fs::find_project_files(&args.path)?;
+    let report = lint::lint_project(&project, &config);
+    lint::write_text(&mut stdout().lock(), &report)?;
+    if let Some(path) = &args.junit {
+        let mut out = BufWriter::new(File::create(path)?);
+        lint::write_junit(&mut out, &report)?;
+        out.flush()?;
+    }
+    if let Some(path) = &args.sarif {
+        let mut out = BufWriter::new(File::create(path)?);
+        lint::write_sarif(&mut out, &report)?;
+        out.flush()?;
+    }
+    Ok(!report.has_errors())
+}
+
 fn print_usage() {
-    todo!("the \"print_usage\" functionality")
+    eprintln!("Usage: unity_tui <project>");
+    eprintln!("       unity_tui lint <project> [--junit <file>] [--sarif <file>]");
+    eprintln!();
+    eprintln!("lint checks every scene, prefab and asset of the project, it exits with 1 if it");
+    eprintln!("finds errors. --junit and --sarif also write the findings as a JUnit XML or a");
+    eprintln!("SARIF report");
 }
@@ -1,14 +1,22 @@
 use crate::{
     fs::ProjectFiles,
-    ui::screen::{self, Screen},
-    unity::repository::{construct_meta_repository, MetaFilesRepository},
+    lint::{self, LintConfig},
+    ui::{
+        history::History,
+        screen::{self, Screen},
+    },
+    unity::{
+        repository::{construct_meta_repository, MetaFilesRepository},
+        usages::UsagesIndex,
+        version::{read_project_version, UnityVersion},
+    },
 };
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::{
-    io::{stdout, Error, ErrorKind, Stdout},
+    io::{stdout, Error, Stdout},
     time::Duration,
 };
 use tui::{
@@ -20,16 +28,44 @@ pub struct AppState {
     pub project: ProjectFiles,
     pub active_screen: Screen,
     pub meta_files_repository: MetaFilesRepository,
+    /// The version of Unity the project was last opened with, None without a ProjectVersion.txt
+    pub unity_version: Option<UnityVersion>,
+    /// The rules of the project config file, the open file is checked with them
+    pub lint_config: LintConfig,
+    /// The references to the assets from every file, read the first time usages are looked for
+    pub usages_index: UsagesIndex,
+    /// The changes made to the open file
+    pub history: History,
 }
 
 impl AppState {
     pub fn new(project: ProjectFiles) -> Self {
-        let meta_files_repository = construct_meta_repository(&project).unwrap();
-        let active_screen = Screen::new_file_select(&project);
+        println!("Loading project, please wait...");
+        let (meta_files_repository, meta_file_errors) = construct_meta_repository(&project);
+        let unity_version = read_project_version(&project.base_path);
+        // the built-in rules are still checked when the config cannot be read
+        let (lint_config, config_error) = match lint::read_config(&project.base_path) {
+            Ok(config) => (config, None),
+            Err(e) => (LintConfig::default(), Some(format!("Error: {}", e))),
+        };
+        let mut active_screen = Screen::new_file_select(&project);
+        if let Screen::FileSelect(file_select) = &mut active_screen {
+            // the meta files are reported by the lint subcommand too, here only when the project opens
+            let messages = [
+                config_error,
+                screen::parse_errors_message("meta files", &meta_file_errors),
+            ];
+            let message = messages.into_iter().flatten().collect::<Vec<String>>();
+            file_select.message = (!message.is_empty()).then(|| message.join("  "));
+        }
         Self {
             project,
             active_screen,
             meta_files_repository,
+            unity_version,
+            lint_config,
+            usages_index: UsagesIndex::default(),
+            history: History::new(),
         }
     }
 
@@ -81,16 +117,12 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut state: AppState) -> cross
                         kind: KeyEventKind::Press,
                         ..
                     } => {
-                        state
-                            .handle_event(&event)
-                            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                        state.handle_event(&event).map_err(Error::other)?;
                     }
                     _ => {}
                 },
                 Event::Paste(_) => {
-                    state
-                        .handle_event(&event)
-                        .map_err(|e| Error::new(ErrorKind::Other, e))?;
+                    state.handle_event(&event).map_err(Error::other)?;
                 }
                 _ => {}
             }
@@ -0,0 +1,184 @@
+use crate::unity::edit::Operation;
+
+/// Operations done together by one action, they are undone together
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub description: String,
+    pub operations: Vec<Operation>,
+}
+
+impl HistoryEntry {
+    pub fn new(description: String, operations: Vec<Operation>) -> Self {
+        Self {
+            description,
+            operations,
+        }
+    }
+
+    /// Returns the entry that undoes this one, the operations are undone in reverse order
+    pub fn reversed(&self) -> HistoryEntry {
+        HistoryEntry {
+            description: self.description.clone(),
+            operations: self
+                .operations
+                .iter()
+                .rev()
+                .map(|op| op.reversed())
+                .collect(),
+        }
+    }
+}
+
+/// The undo and redo stacks of the open file
+#[derive(Default)]
+pub struct History {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    /// The length of the undo stack when the file was saved, None if that state cannot be reached
+    saved_at: Option<usize>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            saved_at: Some(0),
+            ..Default::default()
+        }
+    }
+
+    /// Records an entry that was applied, the entries that were undone cannot be redone anymore
+    pub fn push(&mut self, entry: HistoryEntry) {
+        if self
+            .saved_at
+            .is_some_and(|saved_at| saved_at > self.undo.len())
+        {
+            self.saved_at = None;
+        }
+        self.undo.push(entry);
+        self.redo.clear();
+    }
+
+    /// Returns the entry to apply to undo the last change
+    pub fn undo(&mut self) -> Option<HistoryEntry> {
+        let entry = self.undo.pop()?;
+        let reversed = entry.reversed();
+        self.redo.push(entry);
+        Some(reversed)
+    }
+
+    /// Returns the entry to apply to redo the last undone change
+    pub fn redo(&mut self) -> Option<HistoryEntry> {
+        let entry = self.redo.pop()?;
+        self.undo.push(entry.clone());
+        Some(entry)
+    }
+
+    pub fn clear(&mut self) {
+        *self = History::new();
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved_at = Some(self.undo.len());
+    }
+
+    /// Returns true if the changes since the file was saved were undone or redone back
+    pub fn is_saved(&self) -> bool {
+        self.saved_at == Some(self.undo.len())
+    }
+
+    /// Returns the applied entries, oldest first
+    pub fn get_done(&self) -> &[HistoryEntry] {
+        &self.undo
+    }
+
+    /// Returns the undone entries, the next to redo first
+    pub fn get_undone(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.redo.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unity::{
+        edit,
+        test_util::{Edited, SCENE},
+    };
+
+    fn entry(description: &str) -> HistoryEntry {
+        HistoryEntry::new(description.to_owned(), vec![])
+    }
+
+    fn descriptions<'a>(entries: impl Iterator<Item = &'a HistoryEntry>) -> Vec<&'a str> {
+        entries.map(|entry| entry.description.as_str()).collect()
+    }
+
+    #[test]
+    fn undone_entries_are_redone_until_a_new_one_is_pushed() {
+        let mut history = History::new();
+        history.push(entry("first"));
+        history.push(entry("second"));
+
+        assert_eq!(history.undo().unwrap().description, "second");
+        assert_eq!(descriptions(history.get_done().iter()), ["first"]);
+        assert_eq!(descriptions(history.get_undone()), ["second"]);
+        assert_eq!(history.redo().unwrap().description, "second");
+        assert!(history.redo().is_none());
+
+        history.undo();
+        history.push(entry("third"));
+        assert_eq!(descriptions(history.get_done().iter()), ["first", "third"]);
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn saved_state_is_reached_again_by_undoing_and_redoing() {
+        let mut history = History::new();
+        assert!(history.is_saved());
+        history.push(entry("first"));
+        assert!(!history.is_saved());
+        history.undo();
+        assert!(history.is_saved());
+        history.redo();
+        history.mark_saved();
+        assert!(history.is_saved());
+
+        // the saved entry cannot be redone once another one replaces it
+        history.undo();
+        history.push(entry("second"));
+        assert!(!history.is_saved());
+        history.undo();
+        assert!(!history.is_saved());
+    }
+
+    #[test]
+    fn reversed_entry_restores_the_file() {
+        let mut edited = Edited::open(SCENE);
+        let mut history = History::new();
+
+        let operations =
+            edit::rename_game_object(&edited.repo, &"100".to_owned(), "Enemy").unwrap();
+        let entry = HistoryEntry::new("Rename Player".to_owned(), operations);
+        edited.apply(&entry.operations);
+        history.push(entry);
+        assert_eq!(
+            edited.text(),
+            SCENE.replace("m_Name: Player", "m_Name: Enemy")
+        );
+
+        let undo = history.undo().unwrap();
+        edited.apply(&undo.operations);
+        assert_eq!(edited.text(), SCENE);
+        assert_eq!(
+            edited.repo.get_game_object(&"100".to_owned()).unwrap().name,
+            "Player"
+        );
+
+        let redo = history.redo().unwrap();
+        edited.apply(&redo.operations);
+        assert_eq!(
+            edited.repo.get_game_object(&"100".to_owned()).unwrap().name,
+            "Enemy"
+        );
+    }
+}
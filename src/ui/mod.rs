@@ -1,4 +1,5 @@
 mod app;
+mod history;
 mod screen;
 
 pub use app::cleanup_terminal;
@@ -3,11 +3,13 @@ use crate::{
     fs::{self, ProjectFiles},
     ui::{
         app::AppState,
-        screen::{AvailableSize, FooterRenderer, Screen, SelectNextPrev},
+        screen::{
+            self, hierarchy_view, AvailableSize, FooterRenderer, Screen, SelectNextPrev, UsagesList,
+        },
     },
 };
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 use std::path::{Path, PathBuf};
 use tui::{
     backend::Backend,
@@ -21,6 +23,10 @@ pub struct FileSelectState {
     pub scenes_state: ListState,
     pub prefabs_state: ListState,
     pub assets_state: ListState,
+    /// Shown instead of the footer until the next key is pressed
+    pub message: Option<String>,
+    /// The usages of the selected file, listed while it is Some
+    pub usages_list: Option<UsagesList>,
 }
 
 impl Screen {
@@ -41,6 +47,8 @@ impl Screen {
             scenes_state,
             prefabs_state,
             assets_state,
+            message: None,
+            usages_list: None,
         })
     }
 }
@@ -50,6 +58,8 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, state: &mut AppState) {
         scenes_state,
         prefabs_state,
         assets_state,
+        message,
+        usages_list,
     }) = &mut state.active_screen else {
         unreachable!()
     };
@@ -77,7 +87,7 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, state: &mut AppState) {
                 non_empty += 1;
                 ratios[2].0 = 1;
             }
-            for mut ratio in ratios.iter_mut() {
+            for ratio in ratios.iter_mut() {
                 ratio.1 = non_empty;
             }
             ratios
@@ -94,8 +104,16 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, state: &mut AppState) {
             )
             .split(size);
 
-        let scenes_list =
-            project_files_item_list(&state.project.scenes, &state.project.base_path, "Scenes");
+        // the version is shown on the first list so it is visible without its own block
+        let scenes_title = match &state.unity_version {
+            Some(version) => format!("Scenes (Unity {})", version),
+            None => "Scenes".to_owned(),
+        };
+        let scenes_list = project_files_item_list(
+            &state.project.scenes,
+            &state.project.base_path,
+            &scenes_title,
+        );
         let prefabs_list =
             project_files_item_list(&state.project.prefabs, &state.project.base_path, "Prefabs");
         let assets_list =
@@ -104,10 +122,14 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, state: &mut AppState) {
         f.render_stateful_widget(scenes_list, layout[0], scenes_state);
         f.render_stateful_widget(prefabs_list, layout[1], prefabs_state);
         f.render_stateful_widget(assets_list, layout[2], assets_state);
-        footer_text = "shift+j/k/down/up: switch section  j/k/down/up: move  space/enter: select  ctrl+q: quit";
+        footer_text = "shift+j/k/down/up: switch section  j/k/down/up: move  space/enter: select  f: find usages  ctrl+q: quit";
+    }
+    if let Some(usages_list) = usages_list {
+        let usages = state.usages_index.get_usages(&usages_list.guid);
+        usages_list.render(f, &usages);
     }
 
-    f.render_footer(footer_text);
+    f.render_footer(message.as_deref().unwrap_or(footer_text));
 }
 
 pub fn handle_event(event: &Event, state: &mut AppState) -> Result<(), Error> {
@@ -115,11 +137,18 @@ pub fn handle_event(event: &Event, state: &mut AppState) -> Result<(), Error> {
         scenes_state,
         prefabs_state,
         assets_state,
+        message,
+        usages_list,
     }) = &mut state.active_screen else {
         unreachable!()
     };
 
     if let Event::Key(e) = event {
+        *message = None;
+        if usages_list.is_some() {
+            handle_usages_event(e.code, state);
+            return Ok(());
+        }
         match e {
             KeyEvent {
                 code: KeyCode::Char('J') | KeyCode::Down,
@@ -207,18 +236,31 @@ pub fn handle_event(event: &Event, state: &mut AppState) -> Result<(), Error> {
                 ..
             } => {
                 // TODO: create a different view for assets
-                let selected_file_path = {
-                    if let Some(idx) = scenes_state.selected() {
-                        state.project.scenes[idx].clone()
-                    } else if let Some(idx) = prefabs_state.selected() {
-                        state.project.prefabs[idx].clone()
-                    } else if let Some(idx) = assets_state.selected() {
-                        state.project.assets[idx].clone()
-                    } else {
-                        PathBuf::new()
+                let selected_file_path =
+                    get_selected_file(&state.project, scenes_state, prefabs_state, assets_state)
+                        .unwrap_or_default();
+                // a file that cannot be read is reported without leaving the list
+                match Screen::new_hierarchy_view(selected_file_path, &state.meta_files_repository) {
+                    Ok(screen) => {
+                        state.active_screen = screen;
+                        state.history.clear();
                     }
+                    Err(e) => *message = Some(format!("Error: {}", e)),
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('f'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                let Some(path) =
+                    get_selected_file(&state.project, scenes_state, prefabs_state, assets_state) else {
+                    return Ok(());
                 };
-                state.active_screen = Screen::new_hierarchy_view(selected_file_path)?;
+                if let Err(e) = find_selected_file_usages(state, &path) {
+                    let Screen::FileSelect(file_select) = &mut state.active_screen else { unreachable!() };
+                    file_select.message = Some(format!("Error: {}", e));
+                }
             }
             _ => {}
         }
@@ -226,6 +268,59 @@ pub fn handle_event(event: &Event, state: &mut AppState) -> Result<(), Error> {
     Ok(())
 }
 
+fn get_selected_file(
+    project: &ProjectFiles,
+    scenes_state: &ListState,
+    prefabs_state: &ListState,
+    assets_state: &ListState,
+) -> Option<PathBuf> {
+    if let Some(idx) = scenes_state.selected() {
+        project.scenes.get(idx).cloned()
+    } else if let Some(idx) = prefabs_state.selected() {
+        project.prefabs.get(idx).cloned()
+    } else if let Some(idx) = assets_state.selected() {
+        project.assets.get(idx).cloned()
+    } else {
+        None
+    }
+}
+
+/// Lists the files and objects that reference the selected file, by the guid of its meta file
+fn find_selected_file_usages(state: &mut AppState, path: &Path) -> Result<(), Error> {
+    let asset = state
+        .meta_files_repository
+        .get_by_path(path)
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, "the file has no meta file"))?;
+    let usages_list = UsagesList::new(asset.guid.clone(), &state.meta_files_repository);
+    state
+        .usages_index
+        .refresh(&state.project, &state.meta_files_repository);
+    let Screen::FileSelect(file_select) = &mut state.active_screen else { return Ok(()); };
+    file_select.message = screen::parse_errors_message("files", &state.usages_index.get_errors());
+    file_select.usages_list = Some(usages_list);
+    Ok(())
+}
+
+/// Moves in the list of usages, enter opens the file of the selected one
+fn handle_usages_event(code: KeyCode, state: &mut AppState) {
+    let Screen::FileSelect(file_select) = &mut state.active_screen else { return; };
+    let Some(usages_list) = &mut file_select.usages_list else { return; };
+    if let KeyCode::Esc | KeyCode::Char('f') = code {
+        file_select.usages_list = None;
+        return;
+    }
+    let usages = state.usages_index.get_usages(&usages_list.guid);
+    let Some(usage) = usages_list.handle_key(code, &usages).cloned() else { return; };
+    file_select.usages_list = None;
+    match hierarchy_view::open_usage(state, &usage) {
+        Ok(()) => {}
+        Err(e) => {
+            let Screen::FileSelect(file_select) = &mut state.active_screen else { unreachable!() };
+            file_select.message = Some(format!("Error: {}", e));
+        }
+    }
+}
+
 fn project_files_item_list<'a>(
     files: &'a [PathBuf],
     base_path: &'a Path,
@@ -0,0 +1,93 @@
+use crate::{
+    ui::screen::{bordered_list, SelectNextPrev},
+    unity::{
+        self,
+        references::{self, BrokenReference},
+        repository::MetaFilesRepository,
+    },
+};
+use crossterm::event::{Event, KeyCode};
+use std::io::{Error, ErrorKind};
+use tui::{
+    style::{Color, Style},
+    widgets::{List, ListItem},
+};
+
+use super::{fields::get_object_label, select_object, select_property, HierarchyViewState};
+
+/// Lists the broken references of the file by the object and the field they are in
+pub(super) fn create_broken_references_list(
+    broken: &[BrokenReference],
+    objects_repository: &unity::Repository,
+    meta_files_repository: &MetaFilesRepository,
+) -> List<'static> {
+    let mut items = broken
+        .iter()
+        .map(|broken| {
+            // the documents that are not in the hierarchy, like RenderSettings, are named by their class
+            let label = objects_repository
+                .get(&broken.file_id)
+                .and_then(|object| {
+                    get_object_label(meta_files_repository, objects_repository, object)
+                })
+                .unwrap_or_else(|| broken.class_name.clone());
+            ListItem::new(format!(
+                "{} {}: {}",
+                label,
+                broken.property_path,
+                broken.kind.describe(&broken.reference)
+            ))
+            .style(Style::reset())
+        })
+        .collect::<Vec<ListItem>>();
+    if items.is_empty() {
+        items.push(ListItem::new("No broken references").style(Style::reset().fg(Color::Gray)));
+    }
+    bordered_list(items, Some(format!("Broken references ({})", broken.len())))
+}
+
+/// Moves in the list of broken references, enter goes to the object the selected one is in
+pub(super) fn handle_broken_references_event(
+    event: &Event,
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) {
+    let Event::Key(e) = event else { return; };
+    let Some(list_state) = &mut view_state.broken_references_list_state else { return; };
+    let broken =
+        references::find_broken_references(&view_state.file.documents, meta_files_repository);
+    match e.code {
+        KeyCode::Esc | KeyCode::Char('b') => view_state.broken_references_list_state = None,
+        KeyCode::Char('j') | KeyCode::Down if !broken.is_empty() => {
+            list_state.next_if_some(broken.len())
+        }
+        KeyCode::Char('k') | KeyCode::Up if !broken.is_empty() => {
+            list_state.prev_if_some(broken.len())
+        }
+        KeyCode::Enter => {
+            let Some(broken) = list_state.selected().and_then(|idx| broken.get(idx)) else { return; };
+            view_state.broken_references_list_state = None;
+            if let Err(e) = select_broken_reference(view_state, meta_files_repository, broken) {
+                view_state.message = Some(format!("Error: {}", e));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Selects the object the broken reference is in, and the field when it is shown in the fields list
+fn select_broken_reference(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+    broken: &BrokenReference,
+) -> Result<(), Error> {
+    if view_state.objects_repository.get(&broken.file_id).is_none() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("{} is not in the hierarchy", broken.class_name),
+        ));
+    }
+    select_object(view_state, meta_files_repository, &broken.file_id)?;
+    select_property(view_state, meta_files_repository, &broken.property_path);
+    Ok(())
+}
@@ -0,0 +1,589 @@
+use crate::{
+    ui::history::{History, HistoryEntry},
+    unity::{
+        self,
+        asset::Asset,
+        edit::{self, FileIdGenerator, Operation},
+        object::{Field, GetId},
+        repository::MetaFilesRepository,
+        script, template,
+        vector::Quaternion,
+        version::UnityVersion,
+    },
+};
+use std::io::{Error, ErrorKind};
+
+use super::{
+    fields::{
+        field_to_string, get_component_label, get_components_fields, get_selected_field_row,
+        local_euler_angles,
+    },
+    get_entry_name, get_selected_component, get_selected_document_id, get_selected_game_object,
+    get_selected_name, has_document,
+    input::parse_field_input,
+    select_component, select_hierarchy_entry, HierarchyViewBlocksState, HierarchyViewState,
+    InputTarget, TextInput,
+};
+
+#[derive(Clone, Copy)]
+pub(super) enum MoveDirection {
+    Up,
+    Down,
+    /// Next to its parent
+    Out,
+    /// Under the sibling above it
+    In,
+}
+
+/// A component that can be added to a game object
+pub(super) enum ComponentChoice<'a> {
+    Script(&'a Asset),
+    BuiltIn(&'static str),
+}
+
+impl ComponentChoice<'_> {
+    fn get_name(&self) -> &str {
+        match self {
+            ComponentChoice::Script(script) => script.get_name(),
+            ComponentChoice::BuiltIn(name) => name,
+        }
+    }
+}
+
+pub(super) fn begin_field_edit(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some(component) = get_selected_component(view_state, meta_files_repository) else { return Ok(()); };
+    let Some(row) = get_selected_field_row(view_state, meta_files_repository, component) else { return Ok(()); };
+    if row.depth > 0 {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "nested fields cannot be edited yet",
+        ));
+    }
+    let field_idx = row.field_idx;
+    let field = get_editable_field(component, field_idx)
+        .ok_or_else(|| Error::new(ErrorKind::Unsupported, "this field cannot be edited"))?;
+    // objects coming from a prefab are not in the file, they are changed with overrides
+    if !view_state
+        .file
+        .documents
+        .iter()
+        .any(|doc| &doc.id == component.get_id() && !doc.stripped)
+    {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "objects from a prefab instance cannot be edited here, edit the prefab instead",
+        ));
+    }
+    view_state.input = Some(TextInput {
+        target: InputTarget::Field(field_idx),
+        text: field_to_string(&field),
+    });
+    Ok(())
+}
+
+/// Writes the edited value into the file, the file is only marked as changed until it is saved
+pub(super) fn commit_field_edit(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some(TextInput {
+        target: InputTarget::Field(field_idx),
+        text,
+    }) = &view_state.input else {
+        return Ok(());
+    };
+    let field_idx = *field_idx;
+    let Some(component) = get_selected_component(view_state, meta_files_repository) else { return Ok(()); };
+    let current = get_editable_field(component, field_idx)
+        .ok_or_else(|| Error::new(ErrorKind::Unsupported, "this field cannot be edited"))?;
+    let value =
+        parse_field_input(text, &current).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    let description = format!(
+        "{}: {}",
+        get_component_label(
+            meta_files_repository,
+            &view_state.objects_repository,
+            component
+        )
+        .unwrap_or_else(|| component.get_id().clone()),
+        get_components_fields(
+            meta_files_repository,
+            &view_state.objects_repository,
+            component,
+        )
+        .get(field_idx)
+        .and_then(|field| field.split_once(':'))
+        .map_or("", |(name, _)| name),
+    );
+    let before = component.clone();
+    let mut after = component.clone();
+    set_editable_field(&mut after, field_idx, value);
+
+    let entry = HistoryEntry::new(
+        format!("{} = {}", description, text.trim()),
+        vec![Operation::set(
+            unity::Object::Component(before),
+            unity::Object::Component(after),
+        )],
+    );
+    view_state.push(entry, history, meta_files_repository);
+    Ok(())
+}
+
+/// Returns the value of the field at the index of the fields list if it can be edited
+fn get_editable_field(component: &unity::Component, field_idx: usize) -> Option<Field> {
+    match component {
+        unity::Component::MonoBehaviour(mono) => match field_idx {
+            0 => Some(Field::Bool(mono.enabled)),
+            idx => mono
+                .fields
+                .values()
+                .nth(idx - 1)
+                .filter(|field| {
+                    !matches!(
+                        field,
+                        Field::Array(_) | Field::Reference(_) | Field::Yaml(_)
+                    )
+                })
+                .cloned(),
+        },
+        unity::Component::Transform(trans) => {
+            get_transform_fields(trans).into_iter().nth(field_idx)
+        }
+        _ => None,
+    }
+}
+
+/// The fields of a transform in the order they are listed
+fn get_transform_fields(transform: &unity::Transform) -> Vec<Field> {
+    match transform {
+        unity::Transform::Transform3D(trans) => vec![
+            Field::Vector3(trans.local_position),
+            Field::Vector3(local_euler_angles(&trans.local_rotation)),
+            Field::Vector3(trans.local_scale),
+        ],
+        unity::Transform::RectTransform(trans) => vec![
+            Field::Vector3(trans.local_position),
+            Field::Vector3(local_euler_angles(&trans.local_rotation)),
+            Field::Vector3(trans.local_scale),
+            Field::Vector2(trans.pivot),
+            Field::Vector2(trans.anchor_min),
+            Field::Vector2(trans.anchor_max),
+            Field::Vector2(trans.size_delta),
+            Field::Vector2(trans.anchored_position),
+        ],
+    }
+}
+
+/// Sets the field at the index of the fields list, the value must be checked with [`parse_field_input`]
+fn set_editable_field(component: &mut unity::Component, field_idx: usize, value: Field) {
+    match component {
+        unity::Component::MonoBehaviour(mono) => match (field_idx, value) {
+            (0, Field::Bool(enabled)) => mono.enabled = enabled,
+            (0, _) => {}
+            (idx, value) => {
+                if let Some((_, field)) = mono.fields.iter_mut().nth(idx - 1) {
+                    *field = value;
+                }
+            }
+        },
+        unity::Component::Transform(unity::Transform::Transform3D(trans)) => {
+            match (field_idx, value) {
+                (0, Field::Vector3(vec)) => trans.local_position = vec,
+                (1, Field::Vector3(angles)) => {
+                    trans.local_rotation = Quaternion::from_euler(angles).normalized()
+                }
+                (2, Field::Vector3(vec)) => trans.local_scale = vec,
+                _ => {}
+            }
+        }
+        unity::Component::Transform(unity::Transform::RectTransform(trans)) => {
+            match (field_idx, value) {
+                (0, Field::Vector3(vec)) => trans.local_position = vec,
+                (1, Field::Vector3(angles)) => {
+                    trans.local_rotation = Quaternion::from_euler(angles).normalized()
+                }
+                (2, Field::Vector3(vec)) => trans.local_scale = vec,
+                (3, Field::Vector2(vec)) => trans.pivot = vec,
+                (4, Field::Vector2(vec)) => trans.anchor_min = vec,
+                (5, Field::Vector2(vec)) => trans.anchor_max = vec,
+                (6, Field::Vector2(vec)) => trans.size_delta = vec,
+                (7, Field::Vector2(vec)) => trans.anchored_position = vec,
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Creates an empty game object under the selected one, or at the root of the file
+pub(super) fn create_game_object(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+    unity_version: Option<&UnityVersion>,
+    as_child: bool,
+) -> Result<(), Error> {
+    let parent = if as_child && view_state.game_objects_list_len > 0 {
+        let (_, transform) = get_selected_game_object(view_state, meta_files_repository)
+            .filter(|(_, transform)| has_document(view_state, transform.get_id()))
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unsupported,
+                    "game objects cannot be added inside a prefab instance here, use N to add one at the root",
+                )
+            })?;
+        Some(transform.get_id().clone())
+    } else {
+        None
+    };
+    let mut ids = FileIdGenerator::new(&view_state.file, &view_state.objects_repository);
+    let (id, operations) = edit::create_game_object(
+        &view_state.file,
+        &view_state.objects_repository,
+        &mut ids,
+        parent.as_ref(),
+        unity_version,
+    )
+    .ok_or_else(|| Error::other("the game object could not be created"))?;
+    view_state.push(
+        HistoryEntry::new("Create GameObject".to_owned(), operations),
+        history,
+        meta_files_repository,
+    );
+    select_hierarchy_entry(view_state, meta_files_repository, &id);
+    Ok(())
+}
+
+pub(super) fn delete_game_object(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some(id) = get_selected_document_id(view_state, meta_files_repository)? else { return Ok(()); };
+    let name = get_selected_name(view_state, meta_files_repository);
+    let operations =
+        edit::delete_game_object(&view_state.file, &view_state.objects_repository, &id);
+    view_state.push(
+        HistoryEntry::new(format!("Delete {}", name), operations),
+        history,
+        meta_files_repository,
+    );
+    Ok(())
+}
+
+pub(super) fn duplicate_game_object(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some(id) = get_selected_document_id(view_state, meta_files_repository)? else { return Ok(()); };
+    let name = get_selected_name(view_state, meta_files_repository);
+    let mut ids = FileIdGenerator::new(&view_state.file, &view_state.objects_repository);
+    let (copy_id, operations) = edit::duplicate_game_object(
+        &view_state.file,
+        &view_state.objects_repository,
+        &mut ids,
+        &id,
+    )
+    .ok_or_else(|| Error::other("the game object could not be copied"))?;
+    view_state.push(
+        HistoryEntry::new(format!("Duplicate {}", name), operations),
+        history,
+        meta_files_repository,
+    );
+    select_hierarchy_entry(view_state, meta_files_repository, &copy_id);
+    Ok(())
+}
+
+/// Moves the selected object among its siblings or to another parent, see [`MoveDirection`]
+pub(super) fn move_selected(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+    direction: MoveDirection,
+) {
+    if let HierarchyViewBlocksState::Hierarchy = view_state.blocks_state {
+        let result = try_move_selected(view_state, history, meta_files_repository, direction);
+        view_state.message = result.err().map(|e| format!("Error: {}", e));
+    }
+}
+
+fn try_move_selected(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+    direction: MoveDirection,
+) -> Result<(), Error> {
+    let Some(id) = get_selected_document_id(view_state, meta_files_repository)? else { return Ok(()); };
+    let file = &view_state.file;
+    let repo = &view_state.objects_repository;
+    let parent_id = edit::get_hierarchy_parent(repo, &id);
+    let siblings = edit::get_hierarchy_siblings(file, repo, parent_id.as_ref());
+    let Some(idx) = siblings.iter().position(|sibling| sibling == &id) else { return Ok(()); };
+    let name = get_entry_name(repo, meta_files_repository, &id);
+    let (new_parent_id, index, description) = match direction {
+        MoveDirection::Up if idx > 0 => (parent_id, idx - 1, format!("Move {} up", name)),
+        MoveDirection::Down if idx + 1 < siblings.len() => {
+            (parent_id, idx + 1, format!("Move {} down", name))
+        }
+        MoveDirection::Out => {
+            let Some(parent_id) = parent_id else { return Ok(()); };
+            let parent_entry = edit::get_hierarchy_entry(file, repo, &parent_id)
+                .ok_or_else(|| Error::other("the parent is not in the file"))?;
+            let grandparent_id = edit::get_hierarchy_parent(repo, &parent_entry);
+            let parent_idx = edit::get_hierarchy_siblings(file, repo, grandparent_id.as_ref())
+                .iter()
+                .position(|sibling| sibling == &parent_entry)
+                .map_or(usize::MAX, |idx| idx + 1);
+            let description = format!(
+                "Move {} out of {}",
+                name,
+                get_entry_name(repo, meta_files_repository, &parent_entry)
+            );
+            (grandparent_id, parent_idx, description)
+        }
+        MoveDirection::In if idx > 0 => {
+            let new_parent = &siblings[idx - 1];
+            let transform_id = edit::get_hierarchy_transform_id(file, repo, new_parent)
+                .ok_or_else(|| Error::other("the object above has no transform in the file"))?;
+            let description = format!(
+                "Move {} into {}",
+                name,
+                get_entry_name(repo, meta_files_repository, new_parent)
+            );
+            (Some(transform_id), usize::MAX, description)
+        }
+        _ => return Ok(()),
+    };
+    let operations = edit::move_game_object(file, repo, &id, new_parent_id.as_ref(), index)?;
+    view_state.push(
+        HistoryEntry::new(description, operations),
+        history,
+        meta_files_repository,
+    );
+    select_hierarchy_entry(view_state, meta_files_repository, &id);
+    Ok(())
+}
+
+/// Moves the object picked with x under the selected game object, or to the root of the file
+pub(super) fn move_picked(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+    under_selected: bool,
+) -> Result<(), Error> {
+    let Some(id) = view_state.moving.clone() else { return Ok(()); };
+    let repo = &view_state.objects_repository;
+    let name = get_entry_name(repo, meta_files_repository, &id);
+    let (parent_id, description) = if under_selected {
+        let Some((go, transform)) = get_selected_game_object(view_state, meta_files_repository) else {
+            return Err(Error::other(
+                "objects cannot be moved into a prefab instance here, edit the prefab instead",
+            ));
+        };
+        (
+            Some(transform.get_id().clone()),
+            format!("Move {} into {}", name, go.name),
+        )
+    } else {
+        (None, format!("Move {} to the root", name))
+    };
+    let operations =
+        edit::move_game_object(&view_state.file, repo, &id, parent_id.as_ref(), usize::MAX)?;
+    view_state.moving = None;
+    view_state.push(
+        HistoryEntry::new(description, operations),
+        history,
+        meta_files_repository,
+    );
+    select_hierarchy_entry(view_state, meta_files_repository, &id);
+    Ok(())
+}
+
+pub(super) fn begin_rename(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some((go, _)) = get_selected_game_object(view_state, meta_files_repository) else {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "prefab instances are renamed with an m_Name override, open the game object instead",
+        ));
+    };
+    if !has_document(view_state, &go.id) {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "objects from a prefab instance cannot be renamed here, edit the prefab instead",
+        ));
+    }
+    view_state.input = Some(TextInput {
+        target: InputTarget::Name,
+        text: go.name.clone(),
+    });
+    Ok(())
+}
+
+pub(super) fn commit_rename(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some(TextInput {
+        target: InputTarget::Name,
+        text,
+    }) = &view_state.input else {
+        return Ok(());
+    };
+    let Some((go, _)) = get_selected_game_object(view_state, meta_files_repository) else { return Ok(()); };
+    if go.name == *text {
+        return Ok(());
+    }
+    let description = format!("Rename {} to {}", go.name, text);
+    let operations = edit::rename_game_object(&view_state.objects_repository, &go.id, text)
+        .ok_or_else(|| Error::other("the game object could not be renamed"))?;
+    view_state.push(
+        HistoryEntry::new(description, operations),
+        history,
+        meta_files_repository,
+    );
+    Ok(())
+}
+
+/// Returns the scripts and then the built-in components whose name contains the filter
+pub(super) fn get_component_choices<'a>(
+    meta_files_repository: &'a MetaFilesRepository,
+    filter: &str,
+) -> Vec<ComponentChoice<'a>> {
+    let filter = filter.to_lowercase();
+    meta_files_repository
+        .get_scripts()
+        .into_iter()
+        .map(ComponentChoice::Script)
+        .chain(
+            template::get_built_in_component_names()
+                .into_iter()
+                .map(ComponentChoice::BuiltIn),
+        )
+        .filter(|choice| choice.get_name().to_lowercase().contains(&filter))
+        .collect()
+}
+
+pub(super) fn begin_add_component(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some((go, _)) = get_selected_game_object(view_state, meta_files_repository) else {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "components cannot be added to a prefab instance here, edit the prefab instead",
+        ));
+    };
+    if !has_document(view_state, &go.id) {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "components cannot be added to objects from a prefab instance here, edit the prefab instead",
+        ));
+    }
+    view_state.input = Some(TextInput {
+        target: InputTarget::AddComponent,
+        text: String::new(),
+    });
+    view_state.fields_list_state.select(Some(0));
+    Ok(())
+}
+
+/// Adds the component selected in the choices to the selected game object, a MonoBehaviour gets
+/// the fields its script declares
+pub(super) fn commit_add_component(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some(TextInput {
+        target: InputTarget::AddComponent,
+        text,
+    }) = &view_state.input else {
+        return Ok(());
+    };
+    let choices = get_component_choices(meta_files_repository, text);
+    let choice = view_state
+        .fields_list_state
+        .selected()
+        .and_then(|idx| choices.get(idx))
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, "no component matches"))?;
+    let Some((go, _)) = get_selected_game_object(view_state, meta_files_repository) else { return Ok(()); };
+
+    let mut ids = FileIdGenerator::new(&view_state.file, &view_state.objects_repository);
+    let id = ids.next_id();
+    let document = match choice {
+        ComponentChoice::Script(script) => {
+            // scripts that cannot be read are added without fields, Unity adds them when it saves
+            let fields = meta_files_repository
+                .get_path(&script.guid)
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .map(|source| script::get_serialized_fields(&source, script.get_name()))
+                .unwrap_or_default();
+            template::new_mono_behaviour(&script.guid, fields, &id, &go.id)
+        }
+        ComponentChoice::BuiltIn(name) => template::new_built_in_component(name, &id, &go.id),
+    }
+    .ok_or_else(|| Error::other("the component could not be created"))?;
+    let description = format!("Add {} to {}", choice.get_name(), go.name);
+    let (id, operations) = edit::add_component(
+        &view_state.file,
+        &view_state.objects_repository,
+        &go.id,
+        document,
+    )
+    .ok_or_else(|| Error::other("the component could not be added"))?;
+    let go_id = go.id.clone();
+    view_state.push(
+        HistoryEntry::new(description, operations),
+        history,
+        meta_files_repository,
+    );
+    select_component(view_state, &go_id, &id);
+    Ok(())
+}
+
+pub(super) fn remove_component(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<(), Error> {
+    let Some(component) = get_selected_component(view_state, meta_files_repository) else { return Ok(()); };
+    if let unity::Component::Transform(_) = component {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "transforms cannot be removed, delete the game object instead",
+        ));
+    }
+    let name = component.get_name(meta_files_repository);
+    let go_name = view_state
+        .objects_repository
+        .get_game_object(component.get_game_object_id())
+        .map(|go| go.name.clone())
+        .unwrap_or_default();
+    let operations = edit::remove_component(
+        &view_state.file,
+        &view_state.objects_repository,
+        component.get_id(),
+    )
+    .ok_or_else(|| {
+        Error::new(
+            ErrorKind::Unsupported,
+            "components from a prefab instance cannot be removed here, edit the prefab instead",
+        )
+    })?;
+    view_state.push(
+        HistoryEntry::new(format!("Remove {}/{}", go_name, name), operations),
+        history,
+        meta_files_repository,
+    );
+    Ok(())
+}
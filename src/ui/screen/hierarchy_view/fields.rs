@@ -0,0 +1,882 @@
+use crate::unity::{
+    self,
+    object::{Field, GetId},
+    references::{self, BrokenReferenceKind},
+    repository::MetaFilesRepository,
+    vector::{Quaternion, Vector3, EULER_DECIMALS},
+    writer,
+};
+use std::collections::HashSet;
+use tui::{
+    style::{Color, Style},
+    text::Span,
+};
+use unity_yaml_rust::Yaml;
+
+use super::{get_selected_component, HierarchyViewState};
+
+/// The rotation in degrees the way the inspector shows it
+pub(super) fn local_euler_angles(rotation: &Quaternion) -> Vector3 {
+    rotation.to_euler().rounded(EULER_DECIMALS)
+}
+
+/// Returns the displayed names of the fields overridden by the prefab instance of the component
+pub(super) fn get_overridden_field_names(
+    objects_repository: &unity::Repository,
+    component: &unity::Component,
+) -> HashSet<String> {
+    objects_repository
+        .get_overrides(component.get_id())
+        .into_iter()
+        .filter_map(|(_, m)| m.property_path.split('.').next())
+        .map(unity::field_name_to_readable)
+        .collect()
+}
+
+pub(super) fn field_to_string(field: &unity::object::Field) -> String {
+    match field {
+        unity::object::Field::Vector2(vec) => format!("x:{} y:{}", vec.x, vec.y),
+        unity::object::Field::Vector3(vec) => format!("x:{} y:{} z:{}", vec.x, vec.y, vec.z),
+        unity::object::Field::Vector4(vec) => {
+            format!("x:{} y:{} z:{} w:{}", vec.x, vec.y, vec.z, vec.w)
+        }
+        unity::object::Field::F64(f) => f.to_string(),
+        unity::object::Field::I64(i) => i.to_string(),
+        unity::object::Field::Str(s) => s.clone(),
+        unity::object::Field::Bool(b) => b.to_string(),
+        unity::object::Field::Color(color) => color_to_string(color),
+        unity::object::Field::Array(items) => format!("[{}]", items.len()),
+        unity::object::Field::Reference(reference) => match &reference.guid {
+            Some(guid) => format!("fileID:{} guid:{}", reference.file_id, guid),
+            None => format!("fileID:{}", reference.file_id),
+        },
+        unity::object::Field::Yaml(y) => yaml_to_summary(y),
+    }
+}
+
+/// Describes nested values in one line, the values inside are shown when the field is expanded
+fn yaml_to_summary(yaml: &Yaml) -> String {
+    match yaml {
+        Yaml::Hash(hash) if is_inline_hash(hash) => hash
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}:{}",
+                    yaml_scalar_to_string(key),
+                    yaml_scalar_to_string(value)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+        Yaml::Hash(hash) => match hash.map.len() {
+            1 => "1 field".to_owned(),
+            len => format!("{} fields", len),
+        },
+        Yaml::Array(items) => format!("[{}]", items.len()),
+        _ => yaml_scalar_to_string(yaml),
+    }
+}
+
+/// Small values written on one line, like vectors, are shown on the line of their field
+fn is_inline_hash(hash: &unity_yaml_rust::yaml::Hash) -> bool {
+    !hash.block
+        && hash
+            .iter()
+            .all(|(_, value)| !matches!(value, Yaml::Hash(_) | Yaml::Array(_)))
+}
+
+fn yaml_scalar_to_string(yaml: &Yaml) -> String {
+    match yaml {
+        Yaml::Real(s) | Yaml::String(s) | Yaml::Original(s) => s.clone(),
+        Yaml::Integer(i) => i.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        Yaml::Hash(_) | Yaml::Array(_) => yaml_to_summary(yaml),
+        Yaml::Null | Yaml::BadValue | Yaml::Alias(_) => "".to_owned(),
+    }
+}
+
+/// A line of the fields list. Nested values of a field are listed under it when it is expanded
+pub(super) struct FieldRow {
+    pub(super) name: String,
+    pub(super) value: String,
+    pub(super) depth: usize,
+    /// The index of the field the row belongs to, in the list returned by [`get_components_fields`]
+    pub(super) field_idx: usize,
+    /// The key of the row in [`HierarchyViewState::expanded_fields`] if it has values inside
+    pub(super) path: Option<Vec<String>>,
+    pub(super) expanded: bool,
+    /// The object the value refers to, it can be jumped to
+    pub(super) reference: Option<unity::object::ObjectReference>,
+    /// Shown next to the value
+    pub(super) color: Option<unity::vector::Color>,
+}
+
+impl std::fmt::Display for FieldRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let marker = match (&self.path, self.expanded) {
+            (Some(_), true) => "▾ ",
+            (Some(_), false) => "▸ ",
+            (None, _) if self.depth > 0 => "  ",
+            (None, _) => "",
+        };
+        write!(
+            f,
+            "{}{}{}: {}",
+            "  ".repeat(self.depth),
+            marker,
+            self.name,
+            self.value
+        )
+    }
+}
+
+/// Returns the fields of the component with the nested values of the expanded ones
+pub(super) fn get_field_rows(
+    meta_files_repository: &MetaFilesRepository,
+    objects_repository: &unity::Repository,
+    component: &unity::Component,
+    expanded_fields: &HashSet<Vec<String>>,
+) -> Vec<FieldRow> {
+    let mut rows = vec![];
+    let fields = get_components_fields(meta_files_repository, objects_repository, component);
+    for (field_idx, field) in fields.into_iter().enumerate() {
+        let (name, value) = field.split_once(": ").unwrap_or((&field, ""));
+        let mut row = FieldRow {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            depth: 0,
+            field_idx,
+            path: None,
+            expanded: false,
+            reference: None,
+            color: None,
+        };
+        let field = get_serialized_field(component, field_idx);
+        // lists are shown like the nested values they are written as
+        let yaml = match field {
+            Some((_, Field::Yaml(yaml))) => Some(yaml.clone()),
+            Some((_, field @ Field::Array(_))) => Some(writer::field_to_yaml(field)),
+            _ => None,
+        };
+        match (field, yaml) {
+            (Some((key, _)), Some(yaml)) if is_expandable(&yaml) => {
+                let path = vec![component.get_id().clone(), key.clone()];
+                row.expanded = expanded_fields.contains(&path);
+                row.path = Some(path.clone());
+                let expanded = row.expanded;
+                rows.push(row);
+                if expanded {
+                    push_nested_rows(&mut rows, &yaml, &path, 1, field_idx, expanded_fields);
+                }
+            }
+            (Some((_, Field::Reference(reference))), _) => {
+                row.reference = Some(reference.clone());
+                rows.push(row);
+            }
+            (Some((_, Field::Color(color))), _) => {
+                row.color = Some(*color);
+                rows.push(row);
+            }
+            _ => rows.push(row),
+        }
+    }
+    // the nested references are shown like the ones of the fields
+    for row in rows.iter_mut().filter(|row| row.depth > 0) {
+        if let Some(reference) = &row.reference {
+            row.value =
+                field_reference_to_string(reference, objects_repository, meta_files_repository);
+        }
+    }
+    rows
+}
+
+fn push_nested_rows(
+    rows: &mut Vec<FieldRow>,
+    yaml: &Yaml,
+    path: &[String],
+    depth: usize,
+    field_idx: usize,
+    expanded_fields: &HashSet<Vec<String>>,
+) {
+    let children: Vec<(String, String, &Yaml)> = match yaml {
+        Yaml::Hash(hash) => hash
+            .iter()
+            .map(|(key, value)| {
+                let key = yaml_scalar_to_string(key);
+                (unity::field_name_to_readable(&key), key, value)
+            })
+            .collect(),
+        Yaml::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (format!("Element {}", idx), idx.to_string(), item))
+            .collect(),
+        _ => vec![],
+    };
+    for (name, key, value) in children {
+        let mut child_path = path.to_vec();
+        child_path.push(key);
+        let expandable = is_expandable(value);
+        let expanded = expandable && expanded_fields.contains(&child_path);
+        let (reference, color) = match unity::repository::field_from_yaml(value) {
+            Some(Field::Reference(reference)) => (Some(reference), None),
+            Some(Field::Color(color)) => (None, Some(color)),
+            _ => (None, None),
+        };
+        rows.push(FieldRow {
+            name,
+            value: yaml_to_summary(value),
+            depth,
+            field_idx,
+            path: expandable.then(|| child_path.clone()),
+            expanded,
+            reference,
+            color,
+        });
+        if expanded {
+            push_nested_rows(
+                rows,
+                value,
+                &child_path,
+                depth + 1,
+                field_idx,
+                expanded_fields,
+            );
+        }
+    }
+}
+
+fn is_expandable(yaml: &Yaml) -> bool {
+    match yaml {
+        Yaml::Hash(hash) => !hash.map.is_empty() && !is_inline_hash(hash),
+        Yaml::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Returns the name and the value of the serialized field at the index of the fields list
+fn get_serialized_field(
+    component: &unity::Component,
+    field_idx: usize,
+) -> Option<(&String, &Field)> {
+    let (fields, first_field_idx) = match component {
+        unity::Component::MonoBehaviour(mono) => (&mono.fields, 1),
+        unity::Component::Generic(generic) => {
+            (&generic.fields, usize::from(generic.enabled.is_some()))
+        }
+        _ => return None,
+    };
+    fields.iter().nth(field_idx.checked_sub(first_field_idx)?)
+}
+
+pub(super) fn get_selected_field_row(
+    view_state: &HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+    component: &unity::Component,
+) -> Option<FieldRow> {
+    let idx = view_state.fields_list_state.selected()?;
+    get_field_rows(
+        meta_files_repository,
+        &view_state.objects_repository,
+        component,
+        &view_state.expanded_fields,
+    )
+    .into_iter()
+    .nth(idx)
+}
+
+/// Expands or collapses the selected field, or switches it when `expand` is None. Collapsing a
+/// field without nested values selects the field it is in. Returns false if nothing changed
+pub(super) fn toggle_selected_field(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+    expand: Option<bool>,
+) -> bool {
+    let Some(component) = get_selected_component(view_state, meta_files_repository) else { return false; };
+    let Some(row) = get_selected_field_row(view_state, meta_files_repository, component) else { return false; };
+    let expand_to = expand.unwrap_or(!row.expanded);
+    match (row.path, expand) {
+        (Some(path), _) if expand_to != row.expanded => {
+            if expand_to {
+                view_state.expanded_fields.insert(path);
+            } else {
+                view_state.expanded_fields.remove(&path);
+            }
+            true
+        }
+        (_, Some(false)) if row.depth > 0 => {
+            let rows = get_field_rows(
+                meta_files_repository,
+                &view_state.objects_repository,
+                component,
+                &view_state.expanded_fields,
+            );
+            let selected = view_state.fields_list_state.selected().unwrap_or(0);
+            let parent_idx = rows[..selected]
+                .iter()
+                .rposition(|parent| parent.depth < row.depth);
+            view_state.fields_list_state.select(parent_idx);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn field_value_to_string(
+    field: &Field,
+    objects_repository: &unity::Repository,
+    meta_files_repository: &MetaFilesRepository,
+) -> String {
+    match field {
+        Field::Reference(reference) => {
+            field_reference_to_string(reference, objects_repository, meta_files_repository)
+        }
+        field => field_to_string(field),
+    }
+}
+
+/// Names the object of the same file a field refers to, or the path of the asset
+fn field_reference_to_string(
+    reference: &unity::object::ObjectReference,
+    objects_repository: &unity::Repository,
+    meta_files_repository: &MetaFilesRepository,
+) -> String {
+    if reference.is_null() {
+        return "None".to_owned();
+    }
+    if let Some(guid) = &reference.guid {
+        return match meta_files_repository.get_relative_path(guid) {
+            Some(path) => path.to_string_lossy().into_owned(),
+            None => reference_to_string(reference, meta_files_repository),
+        };
+    }
+    match objects_repository.get(&reference.file_id) {
+        Some(unity::Object::GameObject(go)) => go.name.clone(),
+        Some(unity::Object::Component(component)) => {
+            let go_name = objects_repository
+                .get_game_object(component.get_game_object_id())
+                .map_or("<Missing>", |go| go.name.as_str());
+            let name = component.get_name(meta_files_repository);
+            format!("{} ({})", go_name, name)
+        }
+        Some(unity::Object::PrefabInstance(instance)) => instance.get_name(meta_files_repository),
+        Some(unity::Object::Stripped(stripped)) => {
+            match objects_repository.get(&stripped.prefab_instance_id) {
+                Some(unity::Object::PrefabInstance(instance)) => format!(
+                    "{} ({})",
+                    instance.get_name(meta_files_repository),
+                    stripped.class_name
+                ),
+                _ => stripped.class_name.clone(),
+            }
+        }
+        Some(unity::Object::SceneRoots(_)) => "SceneRoots".to_owned(),
+        None => BrokenReferenceKind::Object.describe(reference),
+    }
+}
+
+fn reference_to_string(
+    reference: &unity::object::ObjectReference,
+    meta_files_repository: &MetaFilesRepository,
+) -> String {
+    if reference.is_null() {
+        return "None".to_owned();
+    }
+    match &reference.guid {
+        Some(guid) => match meta_files_repository.get_name(guid) {
+            Some(name) => name.to_owned(),
+            None if references::is_built_in_guid(guid) => {
+                format!("Built-in ({})", reference.file_id)
+            }
+            None => BrokenReferenceKind::Asset.describe(reference),
+        },
+        None => format!("fileID: {}", reference.file_id),
+    }
+}
+
+/// A block of the color, the alpha is ignored since the terminal cannot blend it
+pub(super) fn color_swatch(color: &unity::vector::Color) -> Span<'static> {
+    let to_u8 = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Span::styled(
+        "██",
+        Style::reset().fg(Color::Rgb(to_u8(color.r), to_u8(color.g), to_u8(color.b))),
+    )
+}
+
+fn color_to_string(color: &unity::vector::Color) -> String {
+    format!("r:{} g:{} b:{} a:{}", color.r, color.g, color.b, color.a)
+}
+
+/// Returns the name of an enum value stored as an integer, or the integer itself if it is out of range
+fn enum_to_string(value: i64, names: &[&str]) -> String {
+    usize::try_from(value)
+        .ok()
+        .and_then(|idx| names.get(idx))
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn layer_mask_to_string(mask: i64) -> String {
+    match mask {
+        0 => "Nothing".to_owned(),
+        -1 | 0xFFFFFFFF => "Everything".to_owned(),
+        mask => format!("{:#010b}", mask),
+    }
+}
+
+const CAST_SHADOWS: [&str; 4] = ["Off", "On", "Two Sided", "Shadows Only"];
+const INTERPOLATE: [&str; 3] = ["None", "Interpolate", "Extrapolate"];
+
+/// The decimals shown of values computed from several transforms, hides their float error
+const COMPUTED_DECIMALS: i32 = 4;
+
+/// Adds the lines of the world position, rotation and scale after the local fields of a transform
+fn push_world_transform(
+    out: &mut Vec<String>,
+    objects_repository: &unity::Repository,
+    id: &unity::Id,
+) {
+    let Some(world) = objects_repository.get_world_transform(id) else { return; };
+    out.push(format!(
+        "World Position: {}",
+        field_to_string(&Field::Vector3(world.position.rounded(COMPUTED_DECIMALS)))
+    ));
+    out.push(format!(
+        "World Rotation: {}",
+        field_to_string(&Field::Vector3(local_euler_angles(&world.rotation)))
+    ));
+    out.push(format!(
+        "World Scale: {}",
+        field_to_string(&Field::Vector3(
+            world.lossy_scale.rounded(COMPUTED_DECIMALS)
+        ))
+    ));
+}
+
+pub(super) fn get_components_fields(
+    meta_files_repository: &MetaFilesRepository,
+    objects_repository: &unity::Repository,
+    selected_component: &unity::Component,
+) -> Vec<String> {
+    let mut out = vec![];
+    match selected_component {
+        unity::Component::MonoBehaviour(mono) => {
+            out.push(format!("Enabled: {}", mono.enabled));
+            for (name, field) in &mono.fields {
+                out.push(format!(
+                    "{}: {}",
+                    unity::field_name_to_readable(name),
+                    field_value_to_string(field, objects_repository, meta_files_repository)
+                ));
+            }
+        }
+        unity::Component::Transform(trans) => match trans {
+            unity::Transform::Transform3D(trans) => {
+                out.push(format!(
+                    "Local Position: {}",
+                    field_to_string(&unity::object::Field::Vector3(trans.local_position))
+                ));
+                out.push(format!(
+                    "Local Rotation: {}",
+                    field_to_string(&unity::object::Field::Vector3(local_euler_angles(
+                        &trans.local_rotation
+                    )))
+                ));
+                out.push(format!(
+                    "Local Scale: {}",
+                    field_to_string(&unity::object::Field::Vector3(trans.local_scale))
+                ));
+                push_world_transform(&mut out, objects_repository, &trans.id);
+            }
+            unity::Transform::RectTransform(trans) => {
+                out.push(format!(
+                    "Local Position: {}",
+                    field_to_string(&unity::object::Field::Vector3(trans.local_position))
+                ));
+                out.push(format!(
+                    "Local Rotation: {}",
+                    field_to_string(&unity::object::Field::Vector3(local_euler_angles(
+                        &trans.local_rotation
+                    )))
+                ));
+                out.push(format!(
+                    "Local Scale: {}",
+                    field_to_string(&unity::object::Field::Vector3(trans.local_scale))
+                ));
+                out.push(format!(
+                    "Pivot: {}",
+                    field_to_string(&unity::object::Field::Vector2(trans.pivot))
+                ));
+                out.push(format!(
+                    "Anchor Min: {}",
+                    field_to_string(&unity::object::Field::Vector2(trans.anchor_min))
+                ));
+                out.push(format!(
+                    "Anchor Max: {}",
+                    field_to_string(&unity::object::Field::Vector2(trans.anchor_max))
+                ));
+                out.push(format!(
+                    "Size Delta: {}",
+                    field_to_string(&unity::object::Field::Vector2(trans.size_delta))
+                ));
+                out.push(format!(
+                    "Anchored Position: {}",
+                    field_to_string(&unity::object::Field::Vector2(trans.anchored_position))
+                ));
+                push_world_transform(&mut out, objects_repository, &trans.id);
+            }
+        },
+        unity::Component::Camera(camera) => {
+            out.push(format!("Enabled: {}", camera.enabled));
+            out.push(format!(
+                "Clear Flags: {}",
+                enum_to_string(
+                    camera.clear_flags,
+                    &["", "Skybox", "Solid Color", "Depth Only", "Don't Clear"]
+                )
+            ));
+            out.push(format!(
+                "Background: {}",
+                color_to_string(&camera.background_color)
+            ));
+            out.push(format!(
+                "Culling Mask: {}",
+                layer_mask_to_string(camera.culling_mask)
+            ));
+            out.push(format!(
+                "Projection: {}",
+                if camera.orthographic {
+                    "Orthographic"
+                } else {
+                    "Perspective"
+                }
+            ));
+            if camera.orthographic {
+                out.push(format!("Size: {}", camera.orthographic_size));
+            } else {
+                out.push(format!("Field Of View: {}", camera.field_of_view));
+            }
+            out.push(format!("Near Clip Plane: {}", camera.near_clip_plane));
+            out.push(format!("Far Clip Plane: {}", camera.far_clip_plane));
+            out.push(format!("Depth: {}", camera.depth));
+            out.push(format!(
+                "Target Display: Display {}",
+                camera.target_display + 1
+            ));
+        }
+        unity::Component::Light(light) => {
+            out.push(format!("Enabled: {}", light.enabled));
+            out.push(format!(
+                "Type: {}",
+                enum_to_string(
+                    light.light_type,
+                    &["Spot", "Directional", "Point", "Area", "Disc"]
+                )
+            ));
+            out.push(format!("Color: {}", color_to_string(&light.color)));
+            out.push(format!("Intensity: {}", light.intensity));
+            out.push(format!("Range: {}", light.range));
+            out.push(format!("Spot Angle: {}", light.spot_angle));
+            out.push(format!(
+                "Shadow Type: {}",
+                enum_to_string(
+                    light.shadows_type,
+                    &["No Shadows", "Hard Shadows", "Soft Shadows"]
+                )
+            ));
+        }
+        unity::Component::MeshFilter(filter) => {
+            out.push(format!(
+                "Mesh: {}",
+                reference_to_string(&filter.mesh, meta_files_repository)
+            ));
+        }
+        unity::Component::Renderer(renderer) => {
+            out.push(format!("Enabled: {}", renderer.is_enabled()));
+            match renderer {
+                unity::object::Renderer::Mesh(r) => {
+                    out.push(format!(
+                        "Cast Shadows: {}",
+                        enum_to_string(r.cast_shadows, &CAST_SHADOWS)
+                    ));
+                    out.push(format!("Receive Shadows: {}", r.receive_shadows));
+                }
+                unity::object::Renderer::SkinnedMesh(r) => {
+                    out.push(format!(
+                        "Mesh: {}",
+                        reference_to_string(&r.mesh, meta_files_repository)
+                    ));
+                    out.push(format!(
+                        "Root Bone: {}",
+                        reference_to_string(&r.root_bone, meta_files_repository)
+                    ));
+                    out.push(format!(
+                        "Update When Offscreen: {}",
+                        r.update_when_offscreen
+                    ));
+                    out.push(format!(
+                        "Cast Shadows: {}",
+                        enum_to_string(r.cast_shadows, &CAST_SHADOWS)
+                    ));
+                    out.push(format!("Receive Shadows: {}", r.receive_shadows));
+                }
+                unity::object::Renderer::Sprite(r) => {
+                    out.push(format!(
+                        "Sprite: {}",
+                        reference_to_string(&r.sprite, meta_files_repository)
+                    ));
+                    out.push(format!("Color: {}", color_to_string(&r.color)));
+                    out.push(format!("Flip: x:{} y:{}", r.flip_x, r.flip_y));
+                    out.push(format!("Order In Layer: {}", r.sorting_order));
+                }
+            }
+            for (idx, material) in renderer.get_materials().iter().enumerate() {
+                out.push(format!(
+                    "Material {}: {}",
+                    idx,
+                    reference_to_string(material, meta_files_repository)
+                ));
+            }
+        }
+        unity::Component::Collider(collider) => {
+            out.push(format!("Enabled: {}", collider.is_enabled()));
+            out.push(format!("Is Trigger: {}", collider.is_trigger()));
+            out.push(format!(
+                "Material: {}",
+                reference_to_string(collider.get_material(), meta_files_repository)
+            ));
+            match collider {
+                unity::object::Collider::BoxCollider(c) => {
+                    out.push(format!(
+                        "Center: {}",
+                        field_to_string(&unity::object::Field::Vector3(c.center))
+                    ));
+                    out.push(format!(
+                        "Size: {}",
+                        field_to_string(&unity::object::Field::Vector3(c.size))
+                    ));
+                }
+                unity::object::Collider::SphereCollider(c) => {
+                    out.push(format!(
+                        "Center: {}",
+                        field_to_string(&unity::object::Field::Vector3(c.center))
+                    ));
+                    out.push(format!("Radius: {}", c.radius));
+                }
+                unity::object::Collider::CapsuleCollider(c) => {
+                    out.push(format!(
+                        "Center: {}",
+                        field_to_string(&unity::object::Field::Vector3(c.center))
+                    ));
+                    out.push(format!("Radius: {}", c.radius));
+                    out.push(format!("Height: {}", c.height));
+                    out.push(format!(
+                        "Direction: {}",
+                        enum_to_string(c.direction, &["X-Axis", "Y-Axis", "Z-Axis"])
+                    ));
+                }
+                unity::object::Collider::MeshCollider(c) => {
+                    out.push(format!("Convex: {}", c.convex));
+                    out.push(format!(
+                        "Mesh: {}",
+                        reference_to_string(&c.mesh, meta_files_repository)
+                    ));
+                }
+                unity::object::Collider::BoxCollider2D(c) => {
+                    out.push(format!(
+                        "Offset: {}",
+                        field_to_string(&unity::object::Field::Vector2(c.offset))
+                    ));
+                    out.push(format!(
+                        "Size: {}",
+                        field_to_string(&unity::object::Field::Vector2(c.size))
+                    ));
+                }
+                unity::object::Collider::CircleCollider2D(c) => {
+                    out.push(format!(
+                        "Offset: {}",
+                        field_to_string(&unity::object::Field::Vector2(c.offset))
+                    ));
+                    out.push(format!("Radius: {}", c.radius));
+                }
+            }
+        }
+        unity::Component::Rigidbody(rigidbody) => match rigidbody {
+            unity::object::Rigidbody::Rigidbody3D(r) => {
+                out.push(format!("Mass: {}", r.mass));
+                out.push(format!("Drag: {}", r.drag));
+                out.push(format!("Angular Drag: {}", r.angular_drag));
+                out.push(format!("Use Gravity: {}", r.use_gravity));
+                out.push(format!("Is Kinematic: {}", r.is_kinematic));
+                out.push(format!(
+                    "Interpolate: {}",
+                    enum_to_string(r.interpolate, &INTERPOLATE)
+                ));
+                out.push(format!(
+                    "Collision Detection: {}",
+                    enum_to_string(
+                        r.collision_detection,
+                        &[
+                            "Discrete",
+                            "Continuous",
+                            "Continuous Dynamic",
+                            "Continuous Speculative"
+                        ]
+                    )
+                ));
+                out.push(format!("Constraints: {:#08b}", r.constraints));
+            }
+            unity::object::Rigidbody::Rigidbody2D(r) => {
+                out.push(format!(
+                    "Body Type: {}",
+                    enum_to_string(r.body_type, &["Dynamic", "Kinematic", "Static"])
+                ));
+                out.push(format!("Simulated: {}", r.simulated));
+                out.push(format!("Mass: {}", r.mass));
+                out.push(format!("Linear Drag: {}", r.linear_drag));
+                out.push(format!("Angular Drag: {}", r.angular_drag));
+                out.push(format!("Gravity Scale: {}", r.gravity_scale));
+                out.push(format!(
+                    "Interpolate: {}",
+                    enum_to_string(r.interpolate, &INTERPOLATE)
+                ));
+                out.push(format!(
+                    "Collision Detection: {}",
+                    enum_to_string(r.collision_detection, &["Discrete", "Continuous"])
+                ));
+                out.push(format!("Constraints: {:#05b}", r.constraints));
+            }
+        },
+        unity::Component::Generic(generic) => {
+            if let Some(enabled) = generic.enabled {
+                out.push(format!("Enabled: {}", enabled));
+            }
+            for (name, field) in &generic.fields {
+                out.push(format!(
+                    "{}: {}",
+                    unity::field_name_to_readable(name),
+                    field_value_to_string(field, objects_repository, meta_files_repository)
+                ));
+            }
+        }
+        unity::Component::Canvas(canvas) => {
+            out.push(format!("Enabled: {}", canvas.enabled));
+            out.push(format!(
+                "Render Mode: {}",
+                enum_to_string(
+                    canvas.render_mode,
+                    &[
+                        "Screen Space - Overlay",
+                        "Screen Space - Camera",
+                        "World Space"
+                    ]
+                )
+            ));
+            out.push(format!(
+                "Render Camera: {}",
+                reference_to_string(&canvas.camera, meta_files_repository)
+            ));
+            out.push(format!("Plane Distance: {}", canvas.plane_distance));
+            out.push(format!("Pixel Perfect: {}", canvas.pixel_perfect));
+            out.push(format!("Sort Order: {}", canvas.sorting_order));
+            out.push(format!(
+                "Target Display: Display {}",
+                canvas.target_display + 1
+            ));
+        }
+    }
+    out
+}
+
+/// Number of fields listed before the modifications of a prefab instance
+pub(super) const PREFAB_INSTANCE_FIELDS_HEADER_LEN: usize = 2;
+
+pub(super) fn get_prefab_instance_fields(
+    meta_files_repository: &MetaFilesRepository,
+    objects_repository: &unity::Repository,
+    instance: &unity::object::PrefabInstance,
+) -> Vec<String> {
+    // the fields above the modifications, see PREFAB_INSTANCE_FIELDS_HEADER_LEN
+    let mut out = vec![format!(
+        "Source Prefab: {}",
+        reference_to_string(&instance.source_prefab, meta_files_repository)
+    )];
+    out.push(format!("Overrides: {}", instance.modifications.len()));
+    for modification in &instance.modifications {
+        let value = if modification.object_reference.is_null() {
+            match &modification.value {
+                Yaml::Real(s) | Yaml::String(s) => s.clone(),
+                Yaml::Integer(i) => i.to_string(),
+                Yaml::Boolean(b) => b.to_string(),
+                _ => "".to_owned(),
+            }
+        } else {
+            reference_to_string(&modification.object_reference, meta_files_repository)
+        };
+        let target = objects_repository
+            .get_prefab_instance_object(&instance.id, &modification.target)
+            .and_then(|obj| get_object_label(meta_files_repository, objects_repository, obj))
+            .unwrap_or_else(|| modification.target.file_id.clone());
+        out.push(format!(
+            "  {}: {} = {}",
+            target, modification.property_path, value
+        ));
+    }
+
+    let added_game_objects = objects_repository
+        .get_transforms_added_to_prefab_instance(&instance.id)
+        .into_iter()
+        .filter_map(|t| objects_repository.get_game_object(t.get_game_object_id()))
+        .collect::<Vec<&unity::GameObject>>();
+    out.push(format!("Added Game Objects: {}", added_game_objects.len()));
+    out.extend(added_game_objects.iter().map(|go| format!("  {}", go.name)));
+
+    let added_components = objects_repository
+        .get_components_added_to_prefab_instance(&instance.id)
+        .into_iter()
+        .filter(|c| !matches!(c, unity::Component::Transform(_)))
+        .collect::<Vec<&unity::Component>>();
+    out.push(format!("Added Components: {}", added_components.len()));
+    out.extend(added_components.into_iter().map(|c| {
+        let label = get_component_label(meta_files_repository, objects_repository, c);
+        format!("  {}", label.unwrap_or_else(|| c.get_id().clone()))
+    }));
+
+    out.push(format!(
+        "Removed Components: {}",
+        instance.removed_components.len()
+    ));
+    for removed in &instance.removed_components {
+        let label = objects_repository
+            .get_prefab_instance_object(&instance.id, removed)
+            .and_then(|obj| get_object_label(meta_files_repository, objects_repository, obj))
+            .unwrap_or_else(|| removed.file_id.clone());
+        out.push(format!("  {}", label));
+    }
+    out
+}
+
+/// Names a game object by its name and a component by its game object and its own name
+pub(super) fn get_object_label(
+    meta_files_repository: &MetaFilesRepository,
+    objects_repository: &unity::Repository,
+    object: &unity::Object,
+) -> Option<String> {
+    match object {
+        unity::Object::GameObject(go) => Some(go.name.clone()),
+        unity::Object::Component(component) => {
+            get_component_label(meta_files_repository, objects_repository, component)
+        }
+        _ => None,
+    }
+}
+
+pub(super) fn get_component_label(
+    meta_files_repository: &MetaFilesRepository,
+    objects_repository: &unity::Repository,
+    component: &unity::Component,
+) -> Option<String> {
+    let go = objects_repository.get_game_object(component.get_game_object_id())?;
+    let name = component.get_name(meta_files_repository);
+    Some(format!("{}/{}", go.name, name))
+}
@@ -0,0 +1,286 @@
+use crate::util::hash_set;
+use crate::{
+    lint::{Finding, Severity},
+    ui::screen::bordered_list,
+    unity::{self, repository::MetaFilesRepository},
+};
+use tui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{List, ListItem},
+};
+
+#[derive(Clone, Copy)]
+pub(super) enum HierarchyEntry<'a> {
+    GameObject(&'a unity::GameObject, &'a unity::Transform),
+    PrefabInstance(&'a unity::object::PrefabInstance),
+}
+
+impl<'a> HierarchyEntry<'a> {
+    pub(super) fn get_name(&self, meta_files_repository: &MetaFilesRepository) -> String {
+        match self {
+            HierarchyEntry::GameObject(go, _) => go.name.clone(),
+            HierarchyEntry::PrefabInstance(instance) => instance.get_name(meta_files_repository),
+        }
+    }
+
+    /// Returns true for the game object with the id, or the root of the prefab instance with it
+    pub(super) fn has_id(&self, objects_repository: &unity::Repository, id: &unity::Id) -> bool {
+        match self {
+            HierarchyEntry::GameObject(go, _) => {
+                &go.id == id
+                    || objects_repository
+                        .get_prefab_instance_of_root(go)
+                        .is_some_and(|instance| &instance.id == id)
+            }
+            HierarchyEntry::PrefabInstance(instance) => &instance.id == id,
+        }
+    }
+
+    fn get_root_order(&self, objects_repository: &unity::Repository) -> i64 {
+        match self {
+            HierarchyEntry::GameObject(_, transform) => transform.get_root_order(),
+            HierarchyEntry::PrefabInstance(instance) => {
+                objects_repository.get_prefab_instance_root_order(instance)
+            }
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        match self {
+            HierarchyEntry::GameObject(go, _) => go.active,
+            HierarchyEntry::PrefabInstance(instance) => instance
+                .get_modification("m_IsActive")
+                .and_then(|m| m.value.as_i64())
+                .is_none_or(|active| active > 0),
+        }
+    }
+}
+
+pub(super) fn get_named_list<'a>(
+    objects_repository: &'a unity::Repository,
+    meta_files_repository: &MetaFilesRepository,
+) -> Vec<(String, HierarchyEntry<'a>)> {
+    let mut named_list = vec![];
+    for entry in get_unparented(objects_repository) {
+        named_list.append(
+            &mut generate_game_object_named_list(
+                entry,
+                0,
+                objects_repository,
+                meta_files_repository,
+            )
+            .unwrap(),
+        );
+    }
+    named_list
+}
+
+fn generate_game_object_named_list<'a>(
+    entry: HierarchyEntry<'a>,
+    indent: usize,
+    objects_repository: &'a unity::Repository,
+    meta_files_repository: &MetaFilesRepository,
+) -> Option<Vec<(String, HierarchyEntry<'a>)>> {
+    let mut out = vec![];
+    let name = entry.get_name(meta_files_repository);
+    out.push(if indent == 0 {
+        (name, entry)
+    } else {
+        (format!("{}└{}", " ".repeat(indent - 1), &name), entry)
+    });
+
+    let mut children = get_children(entry, objects_repository)?;
+    children.sort_by_key(|child| child.get_root_order(objects_repository));
+
+    for child in children {
+        out.append(&mut generate_game_object_named_list(
+            child,
+            indent + 1,
+            objects_repository,
+            meta_files_repository,
+        )?);
+    }
+
+    Some(out)
+}
+
+fn get_children<'a>(
+    entry: HierarchyEntry<'a>,
+    objects_repository: &'a unity::Repository,
+) -> Option<Vec<HierarchyEntry<'a>>> {
+    match entry {
+        // children that could not be read are left out, they are reported when the file is opened
+        HierarchyEntry::GameObject(_, transform) => Some(
+            transform
+                .get_children_ids()
+                .iter()
+                .filter_map(|id| {
+                    if let Some(stripped) = objects_repository.get_stripped(id) {
+                        let instance =
+                            objects_repository.get_prefab_instance(&stripped.prefab_instance_id)?;
+                        Some(HierarchyEntry::PrefabInstance(instance))
+                    } else {
+                        let child = objects_repository.get_transform(id)?;
+                        let go = objects_repository.get_game_object(child.get_game_object_id())?;
+                        Some(HierarchyEntry::GameObject(go, child))
+                    }
+                })
+                .collect(),
+        ),
+        HierarchyEntry::PrefabInstance(instance) => {
+            let mut children = objects_repository
+                .get_transforms_added_to_prefab_instance(&instance.id)
+                .into_iter()
+                .map(|child| {
+                    let go = objects_repository.get_game_object(child.get_game_object_id())?;
+                    Some(HierarchyEntry::GameObject(go, child))
+                })
+                .collect::<Option<Vec<HierarchyEntry>>>()?;
+            children.extend(
+                objects_repository
+                    .get_prefab_instances_nested_in(&instance.id)
+                    .into_iter()
+                    .map(HierarchyEntry::PrefabInstance),
+            );
+            Some(children)
+        }
+    }
+}
+
+fn get_unparented(objects_repository: &unity::Repository) -> Vec<HierarchyEntry<'_>> {
+    let mut sorted: Vec<HierarchyEntry> = objects_repository
+        .get_unparented_transforms()
+        .into_iter()
+        .filter_map(|trans| {
+            let go = objects_repository.get_game_object(trans.get_game_object_id())?;
+            Some(HierarchyEntry::GameObject(go, trans))
+        })
+        .chain(
+            objects_repository
+                .get_prefab_instances()
+                .into_iter()
+                .filter(|instance| {
+                    !instance.has_parent()
+                        && !objects_repository.is_prefab_instance_resolved(&instance.id)
+                })
+                .map(HierarchyEntry::PrefabInstance),
+        )
+        .collect();
+    sorted.sort_by_key(|entry| entry.get_root_order(objects_repository));
+    sorted
+}
+
+/// `rename` is the index of the game object being renamed with the name typed so far, `moving` the
+/// id of the object picked to be moved
+pub(super) fn create_hierarchy_view<'a>(
+    game_object_named_list: &[(String, HierarchyEntry)],
+    objects_repository: &unity::Repository,
+    title: &'a str,
+    rename: Option<(usize, &str)>,
+    moving: Option<&unity::Id>,
+    findings: &[Finding],
+) -> List<'a> {
+    let mut names = vec![];
+    let mut disabled_indices = hash_set![];
+    let mut prefab_instance_indices = hash_set![];
+    let mut moving_idx = None;
+    for (idx, (name, entry)) in game_object_named_list.iter().enumerate() {
+        names.push(name);
+        if moving.is_some_and(|id| entry.has_id(objects_repository, id)) {
+            moving_idx = Some(idx);
+        }
+        if !entry.is_active() {
+            disabled_indices.insert(idx);
+        }
+        let is_from_prefab = match entry {
+            HierarchyEntry::GameObject(go, _) => {
+                objects_repository.get_prefab_link(&go.id).is_some()
+            }
+            HierarchyEntry::PrefabInstance(_) => true,
+        };
+        if is_from_prefab {
+            prefab_instance_indices.insert(idx);
+        }
+    }
+    let list_items: Vec<ListItem> = names
+        .into_iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            if let Some((_, text)) = rename.filter(|(rename_idx, _)| *rename_idx == idx) {
+                let indent = &name[..name.len() - name.trim_start_matches([' ', '└']).len()];
+                return ListItem::new(format!("{}{}_", indent, text))
+                    .style(Style::reset().fg(Color::Yellow));
+            }
+            let style = if prefab_instance_indices.contains(&idx) {
+                Style::reset().fg(Color::LightBlue)
+            } else {
+                Style::reset()
+            };
+            let style = if moving_idx == Some(idx) {
+                style.add_modifier(Modifier::UNDERLINED)
+            } else {
+                style
+            };
+            let style = if disabled_indices.contains(&idx) {
+                style.fg(Color::Gray)
+            } else {
+                style
+            };
+            let (_, entry) = &game_object_named_list[idx];
+            let entry_findings = get_entry_findings(entry, objects_repository, findings);
+            match entry_findings.first() {
+                Some(first) => ListItem::new(Spans::from(vec![
+                    Span::raw(name.clone()),
+                    finding_marker(first, entry_findings.len()),
+                ]))
+                .style(style),
+                None => ListItem::new(name.clone()).style(style),
+            }
+        })
+        .collect();
+
+    bordered_list(list_items, Some(title))
+}
+
+/// The findings about a game object and its components, or about a prefab instance, errors first
+fn get_entry_findings<'a>(
+    entry: &HierarchyEntry,
+    objects_repository: &unity::Repository,
+    findings: &'a [Finding],
+) -> Vec<&'a Finding> {
+    let mut out = findings
+        .iter()
+        .filter(|finding| {
+            let Some(id) = &finding.file_id else { return false; };
+            match entry {
+                HierarchyEntry::GameObject(go, _) => {
+                    id == &go.id
+                        || objects_repository
+                            .get_component(id)
+                            .is_some_and(|component| component.get_game_object_id() == &go.id)
+                }
+                HierarchyEntry::PrefabInstance(instance) => id == &instance.id,
+            }
+        })
+        .collect::<Vec<&Finding>>();
+    out.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+    out
+}
+
+/// Shown after the name of an object with findings, the first one and how many others there are
+fn finding_marker(first: &Finding, count: usize) -> Span<'static> {
+    let color = match first.severity {
+        Severity::Error => Color::Red,
+        Severity::Warning => Color::Yellow,
+    };
+    let more = if count > 1 {
+        format!(" (+{})", count - 1)
+    } else {
+        "".to_owned()
+    };
+    Span::styled(
+        format!(" ⚠ {}{}", first.message, more),
+        Style::reset().fg(color),
+    )
+}
@@ -0,0 +1,160 @@
+use crate::{
+    ui::{history::History, screen::SelectNextPrev},
+    unity::{
+        self,
+        object::Field,
+        repository::MetaFilesRepository,
+        vector::{Vector2, Vector3, Vector4},
+    },
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+use super::{
+    edit::{commit_add_component, commit_field_edit, commit_rename, get_component_choices},
+    HierarchyViewState, InputTarget,
+};
+
+pub(super) fn handle_input_event(
+    event: &Event,
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) {
+    let Some(input) = view_state.input.as_mut() else { return; };
+    match event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Esc, ..
+        }) => view_state.input = None,
+        Event::Key(KeyEvent {
+            code: KeyCode::Enter,
+            ..
+        }) => {
+            let result = match input.target {
+                InputTarget::Field(_) => {
+                    commit_field_edit(view_state, history, meta_files_repository)
+                }
+                InputTarget::Name => commit_rename(view_state, history, meta_files_repository),
+                InputTarget::AddComponent => {
+                    commit_add_component(view_state, history, meta_files_repository)
+                }
+            };
+            // the input stays open when the value is invalid so it can be fixed
+            match result {
+                Ok(()) => view_state.input = None,
+                Err(e) => view_state.message = Some(format!("Error: {}", e)),
+            }
+        }
+        Event::Key(KeyEvent {
+            code: code @ (KeyCode::Down | KeyCode::Up),
+            ..
+        }) if input.target == InputTarget::AddComponent => {
+            let len = get_component_choices(meta_files_repository, &input.text).len();
+            if len > 0 {
+                if *code == KeyCode::Down {
+                    view_state.fields_list_state.next_if_some(len);
+                } else {
+                    view_state.fields_list_state.prev_if_some(len);
+                }
+            }
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Backspace,
+            ..
+        }) => {
+            input.text.pop();
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            ..
+        }) => input.text.push(*ch),
+        Event::Paste(text) => input.text.push_str(text.trim_end_matches(['\r', '\n'])),
+        _ => {}
+    }
+    // the choices change with the filter, the first one is selected again
+    if matches!(
+        event,
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(_) | KeyCode::Backspace,
+            ..
+        }) | Event::Paste(_)
+    ) && view_state
+        .input
+        .as_ref()
+        .is_some_and(|input| input.target == InputTarget::AddComponent)
+    {
+        view_state.fields_list_state.select(Some(0));
+    }
+}
+
+/// Reads the typed value as the same kind of field as the current value
+pub(super) fn parse_field_input(input: &str, current: &Field) -> Result<Field, String> {
+    let trimmed = input.trim();
+    match current {
+        Field::F64(_) => trimmed
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(Field::F64)
+            .ok_or_else(|| format!("\"{}\" is not a number", trimmed)),
+        // Unity writes floats without a fraction like integers, the field might be a float
+        Field::I64(_) => match trimmed.parse::<i64>() {
+            Ok(i) => Ok(Field::I64(i)),
+            Err(_) => trimmed
+                .parse::<f64>()
+                .ok()
+                .filter(|f| f.is_finite())
+                .map(Field::F64)
+                .ok_or_else(|| format!("\"{}\" is not a number", trimmed)),
+        },
+        Field::Str(_) => Ok(Field::Str(input.to_owned())),
+        Field::Bool(_) => match trimmed {
+            "true" | "1" => Ok(Field::Bool(true)),
+            "false" | "0" => Ok(Field::Bool(false)),
+            _ => Err(format!("\"{}\" is not true or false", trimmed)),
+        },
+        Field::Vector2(_) => {
+            let [x, y] = parse_vector_input(trimmed, ["x", "y"])?;
+            Ok(Field::Vector2(Vector2 { x, y }))
+        }
+        Field::Vector3(_) => {
+            let [x, y, z] = parse_vector_input(trimmed, ["x", "y", "z"])?;
+            Ok(Field::Vector3(Vector3 { x, y, z }))
+        }
+        Field::Vector4(_) => {
+            let [x, y, z, w] = parse_vector_input(trimmed, ["x", "y", "z", "w"])?;
+            Ok(Field::Vector4(Vector4 { x, y, z, w }))
+        }
+        Field::Color(_) => {
+            let [r, g, b, a] = parse_vector_input(trimmed, ["r", "g", "b", "a"])?;
+            Ok(Field::Color(unity::vector::Color { r, g, b, a }))
+        }
+        Field::Array(_) | Field::Reference(_) | Field::Yaml(_) => {
+            Err("this field cannot be edited".to_owned())
+        }
+    }
+}
+
+/// Reads vectors written like they are listed, `x:1 y:2`, or only the numbers, `1 2` or `1, 2`
+fn parse_vector_input<const N: usize>(input: &str, names: [&str; N]) -> Result<[f32; N], String> {
+    let parts = input
+        .split(|ch: char| ch.is_whitespace() || ch == ',')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>();
+    if parts.len() != N {
+        return Err(format!("expected {} values for {}", N, names.join(", ")));
+    }
+    let mut out = [0.0; N];
+    for (idx, (part, name)) in parts.into_iter().zip(names).enumerate() {
+        let value = part
+            .strip_prefix(name)
+            .and_then(|part| part.strip_prefix(':'))
+            .unwrap_or(part);
+        out[idx] = value
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| format!("\"{}\" is not a number", value))?;
+    }
+    Ok(out)
+}
@@ -0,0 +1,1231 @@
+mod broken_references;
+mod edit;
+mod fields;
+mod hierarchy;
+mod input;
+mod overrides;
+mod usages;
+
+pub use usages::open_usage;
+
+use crate::{
+    fs,
+    lint::{self, Finding},
+    ui::{
+        app::AppState,
+        history::{History, HistoryEntry},
+        screen::{self, bordered_list, FooterRenderer, Screen, SelectNextPrev, UsagesList},
+    },
+    unity::{
+        self,
+        error::ParseError,
+        object::GetId,
+        prefab, references,
+        repository::MetaFilesRepository,
+        writer,
+        yaml::{self, YamlUnityFile},
+    },
+};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use std::{
+    collections::HashSet,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::AvailableSize;
+
+use broken_references::{create_broken_references_list, handle_broken_references_event};
+use edit::{
+    begin_add_component, begin_field_edit, begin_rename, create_game_object, delete_game_object,
+    duplicate_game_object, get_component_choices, move_picked, move_selected, remove_component,
+    ComponentChoice, MoveDirection,
+};
+use fields::{
+    color_swatch, get_field_rows, get_overridden_field_names, get_prefab_instance_fields,
+    get_selected_field_row, toggle_selected_field,
+};
+use hierarchy::{create_hierarchy_view, get_named_list, HierarchyEntry};
+use input::handle_input_event;
+use overrides::{
+    apply_selected_overrides, result_to_message, revert_all_overrides, revert_selected_overrides,
+};
+use usages::{find_selected_script_usages, handle_usages_event};
+
+pub enum HierarchyViewBlocksState {
+    Hierarchy,
+    GameObject,
+    Component,
+}
+
+pub struct HierarchyViewState {
+    pub selected_file_path: PathBuf,
+    /// The file as it was read, before prefab instances were resolved. Edits are made to it
+    pub file: YamlUnityFile,
+    /// The file has edits that were not saved yet
+    pub dirty: bool,
+    pub input: Option<TextInput>,
+    /// The game object or prefab instance picked with x, it is moved to the next selected parent
+    pub moving: Option<unity::Id>,
+    /// The nested fields shown expanded, by component id and the keys and indices leading to them
+    pub expanded_fields: HashSet<Vec<String>>,
+    pub show_history: bool,
+    /// The selection in the list of the broken references of the file, the list is shown while it
+    /// is Some
+    pub broken_references_list_state: Option<ListState>,
+    /// The lint findings of the file, None until they are checked again after a change
+    pub findings: Option<Vec<Finding>>,
+    /// The usages of the script of the selected component, listed while it is Some
+    pub usages_list: Option<UsagesList>,
+    pub objects_repository: unity::Repository,
+    pub game_objects_list_state: ListState,
+    pub game_objects_list_len: usize,
+    pub components_list_state: ListState,
+    pub components_list_len: usize,
+    pub fields_list_state: ListState,
+    pub fields_list_len: usize,
+    pub blocks_state: HierarchyViewBlocksState,
+    /// Shown in the footer instead of the key help until the next key press
+    pub message: Option<String>,
+}
+
+/// Text typed in one of the lists, it is checked when it is confirmed
+pub struct TextInput {
+    pub target: InputTarget,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InputTarget {
+    /// The value of the field at the index of the fields list
+    Field(usize),
+    /// The name of the selected game object
+    Name,
+    /// The filter of the components that can be added to the selected game object, the choice is
+    /// selected in the fields list
+    AddComponent,
+}
+
+impl Screen {
+    pub fn new_hierarchy_view(
+        path: PathBuf,
+        meta_files_repository: &MetaFilesRepository,
+    ) -> Result<Self, Error> {
+        let file = yaml::parse_unity_file(&path).map_err(|e| {
+            let relative = path
+                .strip_prefix(meta_files_repository.get_base_path())
+                .unwrap_or(&path);
+            e.with_path(relative)
+        })?;
+        let (repo, errors) = build_repository(&path, &file, meta_files_repository);
+        let message = screen::parse_errors_message("documents", &errors).or_else(|| {
+            get_named_list(&repo, meta_files_repository)
+                .is_empty()
+                .then(|| "The file has no game objects".to_owned())
+        });
+        Ok(Screen::HierarchyView(Box::new(HierarchyViewState {
+            selected_file_path: path,
+            file,
+            dirty: false,
+            input: None,
+            moving: None,
+            expanded_fields: HashSet::new(),
+            show_history: false,
+            broken_references_list_state: None,
+            findings: None,
+            usages_list: None,
+            objects_repository: repo,
+            game_objects_list_state: ListState::default(),
+            game_objects_list_len: 0,
+            components_list_state: ListState::default(),
+            components_list_len: 0,
+            fields_list_state: ListState::default(),
+            fields_list_len: 0,
+            blocks_state: HierarchyViewBlocksState::Hierarchy,
+            message,
+        })))
+    }
+}
+
+impl HierarchyViewState {
+    fn save(&mut self, history: &mut History) -> Result<(), Error> {
+        writer::write_file(&self.selected_file_path, &self.file)?;
+        self.dirty = false;
+        history.mark_saved();
+        Ok(())
+    }
+
+    /// Applies the operations to the repository and the file
+    fn apply(&mut self, entry: &HistoryEntry, meta_files_repository: &MetaFilesRepository) {
+        unity::edit::apply_operations(
+            &entry.operations,
+            &mut self.file,
+            &mut self.objects_repository,
+            meta_files_repository,
+        );
+        self.findings = None;
+    }
+
+    /// Applies a new change and records it in the history
+    fn push(
+        &mut self,
+        entry: HistoryEntry,
+        history: &mut History,
+        meta_files_repository: &MetaFilesRepository,
+    ) {
+        self.apply(&entry, meta_files_repository);
+        history.push(entry);
+        self.dirty = !history.is_saved();
+    }
+}
+
+/// Builds the repository of the file with its prefab instances resolved. Returns the documents of
+/// the file that could not be read with it, in the order of the file
+fn build_repository(
+    path: &Path,
+    file: &YamlUnityFile,
+    meta_files_repository: &MetaFilesRepository,
+) -> (unity::Repository, Vec<ParseError>) {
+    let docs = prefab::resolve_prefab_instances(file.documents.clone(), meta_files_repository);
+    let (repo, errors) = unity::construct_repository(docs);
+    let path = path
+        .strip_prefix(meta_files_repository.get_base_path())
+        .unwrap_or(path);
+    // the documents merged in from prefabs are not reported, they belong to other files
+    let mut errors = errors
+        .into_iter()
+        .filter(|error| {
+            file.documents
+                .iter()
+                .any(|doc| Some(&doc.id) == error.file_id.as_ref())
+        })
+        .chain(file.errors.iter().cloned())
+        .map(|error| error.with_path(path))
+        .collect::<Vec<ParseError>>();
+    errors.sort_by_key(|error| error.line);
+    (repo, errors)
+}
+
+const UNSAVED_CHANGES_WARNING: &str =
+    "Unsaved changes, ctrl+s: save  esc: discard them and go back";
+
+pub fn ui<B: Backend>(f: &mut Frame<B>, state: &mut AppState) {
+    let Screen::HierarchyView(view_state) = &mut state.active_screen else { unreachable!() };
+
+    let size = f.get_available_size();
+
+    let named_list = get_named_list(&view_state.objects_repository, &state.meta_files_repository);
+
+    let t = fs::path_to_relative(&view_state.selected_file_path, &state.project.base_path).unwrap();
+    let findings = view_state.findings.get_or_insert_with(|| {
+        lint::lint_unity_file(
+            &view_state.file,
+            &t,
+            &state.meta_files_repository,
+            &state.lint_config,
+        )
+    });
+    let title = if view_state.dirty {
+        format!("{}*", t.to_str().unwrap())
+    } else {
+        t.to_str().unwrap().to_owned()
+    };
+    let rename = view_state
+        .input
+        .as_ref()
+        .filter(|input| input.target == InputTarget::Name)
+        .zip(view_state.game_objects_list_state.selected())
+        .map(|(input, idx)| (idx, input.text.as_str()));
+    let hierarchy_list = create_hierarchy_view(
+        &named_list,
+        &view_state.objects_repository,
+        &title,
+        rename,
+        view_state.moving.as_ref(),
+        findings,
+    );
+    if !named_list.is_empty() && view_state.game_objects_list_state.selected().is_none() {
+        view_state.game_objects_list_state.select(Some(0));
+    }
+    view_state.game_objects_list_len = named_list.len();
+    // the lists can get shorter when the file is reloaded after writing it
+    clamp_selection(&mut view_state.game_objects_list_state, named_list.len());
+
+    // files without game objects, like ScriptableObject assets, show empty panes
+    let selected = view_state
+        .game_objects_list_state
+        .selected()
+        .and_then(|idx| named_list.get(idx));
+    let repo = &view_state.objects_repository;
+    let inspector_entries = match selected {
+        Some((_, HierarchyEntry::GameObject(go, _))) => get_inspector_entries(repo, go),
+        Some((_, HierarchyEntry::PrefabInstance(instance))) => {
+            vec![InspectorEntry::PrefabOverrides(instance)]
+        }
+        None => vec![],
+    };
+    let list_items =
+        get_components_list_items(&state.meta_files_repository, repo, &inspector_entries);
+    view_state.components_list_len = list_items.len();
+    clamp_selection(&mut view_state.components_list_state, list_items.len());
+    let components_list = bordered_list(
+        list_items,
+        selected.map(|(name, _)| name.trim_start_matches([' ', '└']).to_owned()),
+    );
+
+    let adding_filter = view_state
+        .input
+        .as_ref()
+        .filter(|input| input.target == InputTarget::AddComponent)
+        .map(|input| input.text.clone());
+    let list_items: Vec<ListItem> = if let Some(filter) = &adding_filter {
+        get_component_choices(&state.meta_files_repository, filter)
+            .iter()
+            .map(|choice| match choice {
+                // the path tells apart scripts with the same name
+                ComponentChoice::Script(script) => {
+                    ListItem::new(format!("{} ({})", script.get_name(), script.path.display()))
+                }
+                ComponentChoice::BuiltIn(name) => ListItem::new(*name),
+            })
+            .map(|item| item.style(Style::reset()))
+            .collect()
+    } else if let HierarchyViewBlocksState::Component = view_state.blocks_state {
+        if view_state.fields_list_state.selected().is_none() {
+            view_state.fields_list_state.select(Some(0));
+        }
+        if let Some(selected_component_idx) = view_state.components_list_state.selected() {
+            match inspector_entries[selected_component_idx] {
+                InspectorEntry::Component(component) => {
+                    let overridden = get_overridden_field_names(repo, component);
+                    get_field_rows(
+                        &state.meta_files_repository,
+                        repo,
+                        component,
+                        &view_state.expanded_fields,
+                    )
+                    .into_iter()
+                    .map(|row| {
+                        if let Some(input) = view_state
+                            .input
+                            .as_ref()
+                            .filter(|input| input.target == InputTarget::Field(row.field_idx))
+                            .filter(|_| row.depth == 0)
+                        {
+                            return ListItem::new(format!("{}: {}_", row.name, input.text))
+                                .style(Style::reset().fg(Color::Yellow));
+                        }
+                        let is_overridden = row.depth == 0 && overridden.contains(&row.name);
+                        let is_broken = row.reference.as_ref().is_some_and(|reference| {
+                            references::check_reference(
+                                reference,
+                                |id| repo.get(id).is_some(),
+                                &state.meta_files_repository,
+                            )
+                            .is_some()
+                        });
+                        let style = if is_broken {
+                            Style::reset().fg(Color::Red)
+                        } else if is_overridden {
+                            Style::reset()
+                                .fg(Color::LightBlue)
+                                .add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::reset()
+                        };
+                        let mut spans = vec![Span::raw(row.to_string())];
+                        if let Some(color) = row.color {
+                            spans.push(Span::raw(" "));
+                            spans.push(color_swatch(&color));
+                        }
+                        ListItem::new(Spans::from(spans)).style(style)
+                    })
+                    .collect()
+                }
+                InspectorEntry::PrefabOverrides(instance) => {
+                    get_prefab_instance_fields(&state.meta_files_repository, repo, instance)
+                        .into_iter()
+                        .map(|field| ListItem::new(field).style(Style::reset()))
+                        .collect()
+                }
+            }
+        } else {
+            vec![]
+        }
+    } else {
+        vec![]
+    };
+    view_state.fields_list_len = list_items.len();
+    clamp_selection(&mut view_state.fields_list_state, list_items.len());
+    let title = adding_filter.map(|filter| format!("Add component: {}_", filter));
+    let fields_list = bordered_list(list_items, title);
+
+    let is_prefab_object = match selected {
+        Some((_, HierarchyEntry::GameObject(go, _))) => repo.get_prefab_link(&go.id).is_some(),
+        Some((_, HierarchyEntry::PrefabInstance(_))) => true,
+        None => false,
+    };
+    if let Some(message) = &view_state.message {
+        f.render_footer(message);
+    } else if adding_filter_shown(view_state) {
+        f.render_footer("type to filter  down/up: move  enter: add  esc: cancel");
+    } else if view_state.input.is_some() {
+        f.render_footer("enter: confirm  esc: cancel");
+    } else if is_prefab_object {
+        match view_state.blocks_state {
+            HierarchyViewBlocksState::Component => f.render_footer(
+                "j/k/down/up: move  right/left: expand/collapse  enter: edit  g: go to reference  r: revert override  a: apply override  R: revert all  esc: components  ctrl+q: quit",
+            ),
+            _ => f.render_footer("j/k/down/up: move  space/enter: select  R: revert all overrides  d: delete  ctrl+d: duplicate  J/K/H/L: reorder  x: move to  esc: back  ctrl+q: quit"),
+        }
+    } else {
+        match view_state.blocks_state {
+            HierarchyViewBlocksState::Hierarchy => f.render_footer(
+                "j/k/down/up: move  space/enter: select  n/N: new child/root  d: delete  ctrl+d: duplicate  F2: rename  J/K/H/L: reorder  x: move to  u/ctrl+r: undo/redo  h: history  b: broken references  ctrl+s: save  esc: select file  ctrl+q: quit",
+            ),
+            HierarchyViewBlocksState::GameObject => f.render_footer(
+                "j/k/down/up: move  space/enter: select  n: add component  d: remove component  f: find usages  u/ctrl+r: undo/redo  h: history  ctrl+s: save  esc: hierarchy  ctrl+q: quit",
+            ),
+            HierarchyViewBlocksState::Component => f.render_footer(
+                "j/k/down/up: move  right/left: expand/collapse  enter: edit  g: go to reference  u/ctrl+r: undo/redo  h: history  ctrl+s: save  esc: components  ctrl+q: quit",
+            ),
+        }
+    }
+
+    // the fields list also shows the components that can be added
+    let show_fields = adding_filter_shown(view_state)
+        || matches!(view_state.blocks_state, HierarchyViewBlocksState::Component);
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            if show_fields {
+                [
+                    Constraint::Ratio(1, 3),
+                    Constraint::Ratio(1, 3),
+                    Constraint::Ratio(1, 3),
+                ]
+            } else {
+                [
+                    Constraint::Ratio(1, 2),
+                    Constraint::Ratio(1, 2),
+                    Constraint::Ratio(0, 2),
+                ]
+            }
+            .as_ref(),
+        )
+        .split(size);
+
+    f.render_stateful_widget(
+        hierarchy_list,
+        layout[0],
+        &mut view_state.game_objects_list_state,
+    );
+    f.render_stateful_widget(
+        components_list,
+        layout[1],
+        &mut view_state.components_list_state,
+    );
+    if show_fields {
+        f.render_stateful_widget(fields_list, layout[2], &mut view_state.fields_list_state);
+    }
+    if view_state.show_history {
+        let area = Rect {
+            x: size.width - size.width / 3,
+            width: size.width / 3,
+            ..size
+        };
+        let (history_list, mut history_list_state) = create_history_list(&state.history);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(history_list, area, &mut history_list_state);
+    }
+    if let Some(list_state) = &mut view_state.broken_references_list_state {
+        let area = Rect {
+            x: size.width - size.width / 2,
+            width: size.width / 2,
+            ..size
+        };
+        let broken = references::find_broken_references(
+            &view_state.file.documents,
+            &state.meta_files_repository,
+        );
+        clamp_selection(list_state, broken.len());
+        let broken_list = create_broken_references_list(
+            &broken,
+            &view_state.objects_repository,
+            &state.meta_files_repository,
+        );
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(broken_list, area, list_state);
+    }
+    if let Some(usages_list) = &mut view_state.usages_list {
+        let usages = state.usages_index.get_usages(&usages_list.guid);
+        usages_list.render(f, &usages);
+    }
+}
+
+/// Lists the changes oldest first, the last applied one is selected and the undone ones are grayed
+fn create_history_list(history: &History) -> (List<'static>, ListState) {
+    let mut list_state = ListState::default();
+    list_state.select(history.get_done().len().checked_sub(1));
+    let mut items = history
+        .get_done()
+        .iter()
+        .map(|entry| ListItem::new(entry.description.clone()).style(Style::reset()))
+        .chain(history.get_undone().map(|entry| {
+            ListItem::new(entry.description.clone()).style(Style::reset().fg(Color::Gray))
+        }))
+        .collect::<Vec<ListItem>>();
+    if items.is_empty() {
+        items.push(ListItem::new("No changes").style(Style::reset().fg(Color::Gray)));
+    }
+    (bordered_list(items, Some("History")), list_state)
+}
+
+pub fn handle_event(event: &Event, state: &mut AppState) -> Result<(), Error> {
+    let Screen::HierarchyView(view_state) = &mut state.active_screen else { unreachable!() };
+
+    if view_state.input.is_some() {
+        view_state.message = None;
+        handle_input_event(
+            event,
+            view_state,
+            &mut state.history,
+            &state.meta_files_repository,
+        );
+        return Ok(());
+    }
+
+    if view_state.usages_list.is_some() {
+        view_state.message = None;
+        handle_usages_event(event, state);
+        return Ok(());
+    }
+
+    if view_state.broken_references_list_state.is_some() {
+        view_state.message = None;
+        handle_broken_references_event(event, view_state, &state.meta_files_repository);
+        return Ok(());
+    }
+
+    if let Event::Key(e) = event {
+        let message = view_state.message.take();
+        match e {
+            KeyEvent {
+                code: KeyCode::Esc,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } if view_state.show_history => view_state.show_history = false,
+            KeyEvent {
+                code: KeyCode::Esc,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } if view_state.moving.is_some() => view_state.moving = None,
+            KeyEvent {
+                code: KeyCode::Esc,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => match view_state.blocks_state {
+                HierarchyViewBlocksState::Hierarchy => {
+                    // the second esc discards the changes
+                    if view_state.dirty && message.as_deref() != Some(UNSAVED_CHANGES_WARNING) {
+                        view_state.message = Some(UNSAVED_CHANGES_WARNING.to_owned());
+                    } else {
+                        state.active_screen = Screen::new_file_select(&state.project);
+                    }
+                }
+                HierarchyViewBlocksState::GameObject => {
+                    view_state.blocks_state = HierarchyViewBlocksState::Hierarchy;
+                    view_state.components_list_state.select(None);
+                }
+                HierarchyViewBlocksState::Component => {
+                    view_state.blocks_state = HierarchyViewBlocksState::GameObject;
+                    view_state.fields_list_state.select(None);
+                }
+            },
+            KeyEvent {
+                code: KeyCode::Enter | KeyCode::Char(' '),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => match view_state.blocks_state {
+                HierarchyViewBlocksState::Hierarchy => {
+                    view_state.blocks_state = HierarchyViewBlocksState::GameObject;
+                    view_state.components_list_state.select(Some(0));
+                }
+                HierarchyViewBlocksState::GameObject => {
+                    view_state.blocks_state = HierarchyViewBlocksState::Component;
+                }
+                HierarchyViewBlocksState::Component => {
+                    if !toggle_selected_field(view_state, &state.meta_files_repository, None) {
+                        if let Err(e) = begin_field_edit(view_state, &state.meta_files_repository) {
+                            view_state.message = Some(format!("Error: {}", e));
+                        }
+                    }
+                }
+            },
+            KeyEvent {
+                code: KeyCode::Char('g'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Component = view_state.blocks_state {
+                    if let Err(e) = jump_to_selected_reference(state) {
+                        let Screen::HierarchyView(view_state) = &mut state.active_screen else { unreachable!() };
+                        view_state.message = Some(format!("Error: {}", e));
+                    }
+                }
+            }
+            KeyEvent {
+                code: code @ (KeyCode::Right | KeyCode::Left),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Component = view_state.blocks_state {
+                    let expand = *code == KeyCode::Right;
+                    toggle_selected_field(view_state, &state.meta_files_repository, Some(expand));
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('s'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                view_state.message = Some(match view_state.save(&mut state.history) {
+                    Ok(()) => "Saved".to_owned(),
+                    Err(e) => format!("Error: {}", e),
+                });
+            }
+            KeyEvent {
+                code: KeyCode::Char('u'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                view_state.message = Some(match state.history.undo() {
+                    Some(entry) => {
+                        view_state.apply(&entry, &state.meta_files_repository);
+                        view_state.dirty = !state.history.is_saved();
+                        format!("Undone: {}", entry.description)
+                    }
+                    None => "Nothing to undo".to_owned(),
+                });
+            }
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                view_state.message = Some(match state.history.redo() {
+                    Some(entry) => {
+                        view_state.apply(&entry, &state.meta_files_repository);
+                        view_state.dirty = !state.history.is_saved();
+                        format!("Redone: {}", entry.description)
+                    }
+                    None => "Nothing to redo".to_owned(),
+                });
+            }
+            KeyEvent {
+                code: KeyCode::Char('h'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => view_state.show_history = !view_state.show_history,
+            KeyEvent {
+                code: KeyCode::Char('b'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                let mut list_state = ListState::default();
+                list_state.select(Some(0));
+                view_state.broken_references_list_state = Some(list_state);
+            }
+            KeyEvent {
+                code: KeyCode::Char('f'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::GameObject = view_state.blocks_state {
+                    if let Err(e) = find_selected_script_usages(state) {
+                        let Screen::HierarchyView(view_state) = &mut state.active_screen else { unreachable!() };
+                        view_state.message = Some(format!("Error: {}", e));
+                    }
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('j') | KeyCode::Down,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => match view_state.blocks_state {
+                HierarchyViewBlocksState::Hierarchy => {
+                    view_state
+                        .game_objects_list_state
+                        .next_if_some(view_state.game_objects_list_len);
+                }
+                HierarchyViewBlocksState::GameObject => {
+                    view_state
+                        .components_list_state
+                        .next_if_some(view_state.components_list_len);
+                }
+                HierarchyViewBlocksState::Component => {
+                    view_state
+                        .fields_list_state
+                        .next_if_some(view_state.fields_list_len);
+                }
+            },
+            KeyEvent {
+                code: KeyCode::Char('k') | KeyCode::Up,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => match view_state.blocks_state {
+                HierarchyViewBlocksState::Hierarchy => {
+                    view_state
+                        .game_objects_list_state
+                        .prev_if_some(view_state.game_objects_list_len);
+                }
+                HierarchyViewBlocksState::GameObject => {
+                    view_state
+                        .components_list_state
+                        .prev_if_some(view_state.components_list_len);
+                }
+                HierarchyViewBlocksState::Component => {
+                    view_state
+                        .fields_list_state
+                        .prev_if_some(view_state.fields_list_len);
+                }
+            },
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Component = view_state.blocks_state {
+                    let result = revert_selected_overrides(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                    );
+                    view_state.message = Some(result_to_message(result, "Reverted"));
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('a'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Component = view_state.blocks_state {
+                    let result = apply_selected_overrides(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                    );
+                    view_state.message = Some(result_to_message(result, "Applied"));
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => match view_state.blocks_state {
+                HierarchyViewBlocksState::Hierarchy => {
+                    let result = create_game_object(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                        state.unity_version.as_ref(),
+                        true,
+                    );
+                    view_state.message = result.err().map(|e| format!("Error: {}", e));
+                }
+                HierarchyViewBlocksState::GameObject => {
+                    if let Err(e) = begin_add_component(view_state, &state.meta_files_repository) {
+                        view_state.message = Some(format!("Error: {}", e));
+                    }
+                }
+                HierarchyViewBlocksState::Component => {}
+            },
+            KeyEvent {
+                code: KeyCode::Char('N'),
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Hierarchy = view_state.blocks_state {
+                    let result = create_game_object(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                        state.unity_version.as_ref(),
+                        false,
+                    );
+                    view_state.message = result.err().map(|e| format!("Error: {}", e));
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('d'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                let result = match view_state.blocks_state {
+                    HierarchyViewBlocksState::Hierarchy => delete_game_object(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                    ),
+                    HierarchyViewBlocksState::GameObject => remove_component(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                    ),
+                    HierarchyViewBlocksState::Component => Ok(()),
+                };
+                view_state.message = result.err().map(|e| format!("Error: {}", e));
+            }
+            KeyEvent {
+                code: KeyCode::Char('d'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Hierarchy = view_state.blocks_state {
+                    let result = duplicate_game_object(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                    );
+                    view_state.message = result.err().map(|e| format!("Error: {}", e));
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('K'),
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Up,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            } => move_selected(
+                view_state,
+                &mut state.history,
+                &state.meta_files_repository,
+                MoveDirection::Up,
+            ),
+            KeyEvent {
+                code: KeyCode::Char('J'),
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Down,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            } => move_selected(
+                view_state,
+                &mut state.history,
+                &state.meta_files_repository,
+                MoveDirection::Down,
+            ),
+            KeyEvent {
+                code: KeyCode::Char('H'),
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Left,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            } => move_selected(
+                view_state,
+                &mut state.history,
+                &state.meta_files_repository,
+                MoveDirection::Out,
+            ),
+            KeyEvent {
+                code: KeyCode::Char('L'),
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Right,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            } => move_selected(
+                view_state,
+                &mut state.history,
+                &state.meta_files_repository,
+                MoveDirection::In,
+            ),
+            KeyEvent {
+                code: KeyCode::Char('x'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Hierarchy = view_state.blocks_state {
+                    view_state.message = match get_selected_document_id(
+                        view_state,
+                        &state.meta_files_repository,
+                    ) {
+                        Ok(Some(id)) => {
+                            view_state.moving = Some(id);
+                            Some("Select the new parent, p: move under it  P: move to the root  esc: cancel".to_owned())
+                        }
+                        Ok(None) => None,
+                        Err(e) => Some(format!("Error: {}", e)),
+                    };
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char(ch @ ('p' | 'P')),
+                ..
+            } => {
+                if let (HierarchyViewBlocksState::Hierarchy, Some(_)) =
+                    (&view_state.blocks_state, &view_state.moving)
+                {
+                    let result = move_picked(
+                        view_state,
+                        &mut state.history,
+                        &state.meta_files_repository,
+                        *ch == 'p',
+                    );
+                    view_state.message = result.err().map(|e| format!("Error: {}", e));
+                }
+            }
+            KeyEvent {
+                code: KeyCode::F(2),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                if let HierarchyViewBlocksState::Hierarchy = view_state.blocks_state {
+                    if let Err(e) = begin_rename(view_state, &state.meta_files_repository) {
+                        view_state.message = Some(format!("Error: {}", e));
+                    }
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('R'),
+                ..
+            } => {
+                let result = revert_all_overrides(
+                    view_state,
+                    &mut state.history,
+                    &state.meta_files_repository,
+                );
+                view_state.message = Some(result_to_message(result, "Reverted"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Selects the field the property path starts with when the selected component has it
+fn select_property(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+    property_path: &str,
+) {
+    let Some(component) = get_selected_component(view_state, meta_files_repository) else { return; };
+    let field_name = property_path
+        .split('.')
+        .next()
+        .map(unity::field_name_to_readable)
+        .unwrap_or_default();
+    let field_idx = get_field_rows(
+        meta_files_repository,
+        &view_state.objects_repository,
+        component,
+        &view_state.expanded_fields,
+    )
+    .iter()
+    .position(|row| row.depth == 0 && row.name == field_name);
+    if let Some(idx) = field_idx {
+        view_state.blocks_state = HierarchyViewBlocksState::Component;
+        view_state.fields_list_state.select(Some(idx));
+    }
+}
+
+fn get_selected_component<'a>(
+    view_state: &'a HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> Option<&'a unity::Component> {
+    let repo = &view_state.objects_repository;
+    let named_list = get_named_list(repo, meta_files_repository);
+    let (_, HierarchyEntry::GameObject(go, _)) =
+        named_list.get(view_state.game_objects_list_state.selected()?)? else {
+        return None;
+    };
+    match get_inspector_entries(repo, go).get(view_state.components_list_state.selected()?)? {
+        InspectorEntry::Component(component) => Some(component),
+        InspectorEntry::PrefabOverrides(_) => None,
+    }
+}
+
+/// Returns the game object at the index of the hierarchy list with its transform
+fn get_selected_game_object<'a>(
+    view_state: &'a HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> Option<(&'a unity::GameObject, &'a unity::Transform)> {
+    let named_list = get_named_list(&view_state.objects_repository, meta_files_repository);
+    match named_list.get(view_state.game_objects_list_state.selected()?)? {
+        (_, HierarchyEntry::GameObject(go, transform)) => Some((go, transform)),
+        (_, HierarchyEntry::PrefabInstance(_)) => None,
+    }
+}
+
+fn has_document(view_state: &HierarchyViewState, id: &unity::Id) -> bool {
+    view_state
+        .file
+        .documents
+        .iter()
+        .any(|doc| &doc.id == id && !doc.stripped)
+}
+
+/// Returns the id of the document the selected entry comes from: the game object, or the prefab
+/// instance for the root of one. The objects inside a prefab instance are not in the file
+fn get_selected_document_id(
+    view_state: &HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<Option<unity::Id>, Error> {
+    let repo = &view_state.objects_repository;
+    let named_list = get_named_list(repo, meta_files_repository);
+    let Some(idx) = view_state.game_objects_list_state.selected() else { return Ok(None); };
+    let id = match named_list.get(idx) {
+        Some((_, HierarchyEntry::GameObject(go, _))) => {
+            match repo.get_prefab_instance_of_root(go) {
+                Some(instance) => instance.id.clone(),
+                None => go.id.clone(),
+            }
+        }
+        Some((_, HierarchyEntry::PrefabInstance(instance))) => instance.id.clone(),
+        None => return Ok(None),
+    };
+    if has_document(view_state, &id) {
+        Ok(Some(id))
+    } else {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "objects inside a prefab instance cannot be changed here, edit the prefab instead",
+        ))
+    }
+}
+
+/// Selects the game object or the prefab instance with the id in the hierarchy list
+fn select_hierarchy_entry(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+    id: &unity::Id,
+) {
+    let repo = &view_state.objects_repository;
+    let idx = get_named_list(repo, meta_files_repository)
+        .iter()
+        .position(|(_, entry)| entry.has_id(repo, id));
+    if idx.is_some() {
+        view_state.game_objects_list_state.select(idx);
+    }
+}
+
+/// Returns the name of a game object, or of the root of a prefab instance
+fn get_entry_name(
+    objects_repository: &unity::Repository,
+    meta_files_repository: &MetaFilesRepository,
+    id: &unity::Id,
+) -> String {
+    match objects_repository.get_prefab_instance(id) {
+        Some(instance) => instance.get_name(meta_files_repository),
+        None => objects_repository
+            .get_game_object(id)
+            .map(|go| go.name.clone())
+            .unwrap_or_default(),
+    }
+}
+
+fn get_selected_name(
+    view_state: &HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> String {
+    let named_list = get_named_list(&view_state.objects_repository, meta_files_repository);
+    view_state
+        .game_objects_list_state
+        .selected()
+        .and_then(|idx| named_list.get(idx))
+        .map(|(_, entry)| entry.get_name(meta_files_repository))
+        .unwrap_or_default()
+}
+
+fn adding_filter_shown(view_state: &HierarchyViewState) -> bool {
+    view_state
+        .input
+        .as_ref()
+        .is_some_and(|input| input.target == InputTarget::AddComponent)
+}
+
+/// Selects the component in the components list of the game object
+fn select_component(
+    view_state: &mut HierarchyViewState,
+    game_object_id: &unity::Id,
+    id: &unity::Id,
+) {
+    let repo = &view_state.objects_repository;
+    if let Some(go) = repo.get_game_object(game_object_id) {
+        let idx = get_inspector_entries(repo, go).iter().position(|entry| {
+            matches!(entry, InspectorEntry::Component(component) if component.get_id() == id)
+        });
+        view_state.components_list_state.select(idx);
+    }
+}
+
+/// Selects the object the selected field refers to, an asset is opened if it is a Unity file
+fn jump_to_selected_reference(state: &mut AppState) -> Result<(), Error> {
+    let Screen::HierarchyView(view_state) = &mut state.active_screen else { return Ok(()); };
+    let meta_files_repository = &state.meta_files_repository;
+    let Some(component) = get_selected_component(view_state, meta_files_repository) else { return Ok(()); };
+    let Some(reference) = get_selected_field_row(view_state, meta_files_repository, component)
+        .and_then(|row| row.reference)
+        .filter(|reference| !reference.is_null()) else {
+        return Ok(());
+    };
+    let Some(guid) = &reference.guid else { return select_object(view_state, meta_files_repository, &reference.file_id); };
+
+    let path = meta_files_repository
+        .get_path(guid)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                "the referenced asset is not in the project",
+            )
+        })?;
+    if !fs::is_unity_file(&path) {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            format!("{} cannot be opened here", path.display()),
+        ));
+    }
+    if view_state.dirty {
+        return Err(Error::other("save the file before opening another one"));
+    }
+    let mut screen = Screen::new_hierarchy_view(path, meta_files_repository)?;
+    if let Screen::HierarchyView(opened) = &mut screen {
+        // the main object of an asset is not always in the hierarchy, the file is opened anyway
+        let _ = select_object(opened, meta_files_repository, &reference.file_id);
+    }
+    state.active_screen = screen;
+    state.history.clear();
+    Ok(())
+}
+
+/// Selects the game object, the component or the prefab instance with the id
+fn select_object(
+    view_state: &mut HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+    id: &unity::Id,
+) -> Result<(), Error> {
+    let (game_object_id, component_id) = match view_state.objects_repository.get(id) {
+        Some(unity::Object::GameObject(go)) => (go.id.clone(), None),
+        Some(unity::Object::Component(component)) => (
+            component.get_game_object_id().clone(),
+            Some(component.get_id().clone()),
+        ),
+        Some(unity::Object::PrefabInstance(instance)) => (instance.id.clone(), None),
+        Some(unity::Object::Stripped(_) | unity::Object::SceneRoots(_)) | None => {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("the referenced object {} is not in the file", id),
+            ))
+        }
+    };
+    select_hierarchy_entry(view_state, meta_files_repository, &game_object_id);
+    view_state.fields_list_state.select(None);
+    match component_id {
+        Some(component_id) => {
+            view_state.blocks_state = HierarchyViewBlocksState::GameObject;
+            select_component(view_state, &game_object_id, &component_id);
+        }
+        None => {
+            view_state.blocks_state = HierarchyViewBlocksState::Hierarchy;
+            view_state.components_list_state.select(None);
+        }
+    }
+    Ok(())
+}
+
+fn clamp_selection(list_state: &mut ListState, len: usize) {
+    if let Some(idx) = list_state.selected() {
+        if idx >= len {
+            list_state.select(len.checked_sub(1));
+        }
+    }
+}
+
+/// An entry of the components list, prefab instance roots get their overrides listed first
+#[derive(Clone, Copy)]
+enum InspectorEntry<'a> {
+    Component(&'a unity::Component),
+    PrefabOverrides(&'a unity::object::PrefabInstance),
+}
+
+fn get_inspector_entries<'a>(
+    objects_repository: &'a unity::Repository,
+    selected_game_object: &unity::GameObject,
+) -> Vec<InspectorEntry<'a>> {
+    let mut entries = vec![];
+    if let Some(instance) = objects_repository.get_prefab_instance_of_root(selected_game_object) {
+        entries.push(InspectorEntry::PrefabOverrides(instance));
+    }
+    entries.extend(
+        get_game_object_components(objects_repository, selected_game_object)
+            .unwrap_or_default()
+            .into_iter()
+            .chain(objects_repository.get_removed_components(&selected_game_object.id))
+            .map(InspectorEntry::Component),
+    );
+    entries
+}
+
+fn get_game_object_components<'a>(
+    objects_repository: &'a unity::Repository,
+    selected_game_object: &unity::GameObject,
+) -> Option<Vec<&'a unity::Component>> {
+    // components missing from the file are skipped, they cannot be displayed anyway
+    selected_game_object
+        .component_ids
+        .iter()
+        .filter_map(|id| objects_repository.get_component(id))
+        .map(Some)
+        .collect()
+}
+
+fn get_components_list_items<'a>(
+    meta_files_repository: &MetaFilesRepository,
+    objects_repository: &unity::Repository,
+    entries: &[InspectorEntry],
+) -> Vec<ListItem<'a>> {
+    entries
+        .iter()
+        .map(|entry| match entry {
+            InspectorEntry::Component(comp) => {
+                let name = comp.get_name(meta_files_repository);
+                let style = if comp.has_missing_script(meta_files_repository) {
+                    Style::reset().fg(Color::Red)
+                } else if comp.is_enabled() {
+                    Style::reset()
+                } else {
+                    Style::reset().fg(Color::Gray)
+                };
+                if objects_repository.is_removed_component(comp.get_id()) {
+                    ListItem::new(format!("- {} (Removed)", name)).style(style.fg(Color::Red))
+                } else if objects_repository.is_added_component(comp) {
+                    ListItem::new(format!("+ {}", name)).style(style.fg(Color::Green))
+                } else {
+                    ListItem::new(name).style(style)
+                }
+            }
+            InspectorEntry::PrefabOverrides(instance) => {
+                let name = if objects_repository.is_prefab_instance_resolved(&instance.id) {
+                    "Prefab Overrides"
+                } else {
+                    "Prefab Instance"
+                };
+                ListItem::new(name).style(Style::reset().fg(Color::LightBlue))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,197 @@
+use crate::{
+    ui::history::{History, HistoryEntry},
+    unity::{self, object::GetId, overrides, repository::MetaFilesRepository},
+};
+use std::io::Error;
+
+use super::{
+    fields::{get_components_fields, get_field_rows, PREFAB_INSTANCE_FIELDS_HEADER_LEN},
+    get_inspector_entries,
+    hierarchy::{get_named_list, HierarchyEntry},
+    HierarchyViewState, InspectorEntry,
+};
+
+pub(super) fn result_to_message(result: Result<usize, Error>, action: &str) -> String {
+    match result {
+        Ok(0) => "Nothing to do, no overrides selected".to_owned(),
+        Ok(1) => format!("{} 1 override", action),
+        Ok(count) => format!("{} {} overrides", action, count),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+/// Returns the prefab instance and the indices of the modifications shown by the selected field
+fn get_selected_overrides(
+    view_state: &HierarchyViewState,
+    meta_files_repository: &MetaFilesRepository,
+) -> Option<(unity::object::PrefabInstance, Vec<usize>)> {
+    let repo = &view_state.objects_repository;
+    let named_list = get_named_list(repo, meta_files_repository);
+    let (_, selected_entry) = named_list.get(view_state.game_objects_list_state.selected()?)?;
+    let inspector_entries = match selected_entry {
+        HierarchyEntry::GameObject(go, _) => get_inspector_entries(repo, go),
+        HierarchyEntry::PrefabInstance(instance) => vec![InspectorEntry::PrefabOverrides(instance)],
+    };
+    let field_idx = view_state.fields_list_state.selected()?;
+
+    match inspector_entries.get(view_state.components_list_state.selected()?)? {
+        InspectorEntry::PrefabOverrides(instance) => {
+            let idx = field_idx.checked_sub(PREFAB_INSTANCE_FIELDS_HEADER_LEN)?;
+            (idx < instance.modifications.len()).then(|| ((*instance).clone(), vec![idx]))
+        }
+        InspectorEntry::Component(component) => {
+            // the overrides of nested fields are the ones of the field they are in
+            let field_idx = get_field_rows(
+                meta_files_repository,
+                repo,
+                component,
+                &view_state.expanded_fields,
+            )
+            .get(field_idx)?
+            .field_idx;
+            let field = get_components_fields(meta_files_repository, repo, component)
+                .into_iter()
+                .nth(field_idx)?;
+            let (name, _) = field.split_once(':')?;
+            let link = repo.get_prefab_link(component.get_id())?;
+            let instance = repo.get_prefab_instance(&link.prefab_instance_id)?;
+            let indices = repo
+                .get_overrides(component.get_id())
+                .into_iter()
+                .filter(|(_, m)| {
+                    m.property_path
+                        .split('.')
+                        .next()
+                        .is_some_and(|path| unity::field_name_to_readable(path) == name)
+                })
+                .map(|(idx, _)| idx)
+                .collect::<Vec<usize>>();
+            Some((instance.clone(), indices))
+        }
+    }
+}
+
+pub(super) fn revert_selected_overrides(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<usize, Error> {
+    let Some((instance, indices)) = get_selected_overrides(view_state, meta_files_repository) else { return Ok(0); };
+    if indices.is_empty() {
+        return Ok(0);
+    }
+    let operation = overrides::revert_modifications(&instance, &indices);
+    view_state.push(
+        HistoryEntry::new(
+            format!(
+                "Revert overrides of {}",
+                instance.get_name(meta_files_repository)
+            ),
+            vec![operation],
+        ),
+        history,
+        meta_files_repository,
+    );
+    Ok(indices.len())
+}
+
+/// Writes the selected overrides into the source prefab and removes them from the instance. The
+/// prefab is written right away, undoing only brings back the overrides
+pub(super) fn apply_selected_overrides(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<usize, Error> {
+    let Some((instance, indices)) = get_selected_overrides(view_state, meta_files_repository) else { return Ok(0); };
+    let mut applied = vec![];
+    let mut result = Ok(());
+    for idx in indices {
+        let prefab_path = instance.modifications[idx]
+            .target
+            .guid
+            .as_ref()
+            .and_then(|guid| meta_files_repository.get_path(guid))
+            .ok_or_else(|| Error::other("source prefab not found"));
+        result = prefab_path
+            .and_then(|prefab_path| overrides::apply_modification(&instance, idx, &prefab_path));
+        if result.is_err() {
+            break;
+        }
+        applied.push(idx);
+    }
+    // the ones written before an error are in the prefab, they are removed from the instance too
+    if !applied.is_empty() {
+        let operation = overrides::revert_modifications(&instance, &applied);
+        view_state.push(
+            HistoryEntry::new(
+                format!(
+                    "Apply overrides of {}",
+                    instance.get_name(meta_files_repository)
+                ),
+                vec![operation],
+            ),
+            history,
+            meta_files_repository,
+        );
+    }
+    result.map(|_| applied.len())
+}
+
+/// Reverts everything but the overrides Unity keeps on the instance root and restores the removed
+/// components. Objects added to the instance are kept
+pub(super) fn revert_all_overrides(
+    view_state: &mut HierarchyViewState,
+    history: &mut History,
+    meta_files_repository: &MetaFilesRepository,
+) -> Result<usize, Error> {
+    let repo = &view_state.objects_repository;
+    let named_list = get_named_list(repo, meta_files_repository);
+    let Some(idx) = view_state.game_objects_list_state.selected() else { return Ok(0); };
+    let instance = match named_list.get(idx) {
+        Some((_, HierarchyEntry::PrefabInstance(instance))) => *instance,
+        Some((_, HierarchyEntry::GameObject(go, _))) => {
+            let Some(instance) = repo
+                .get_prefab_link(&go.id)
+                .and_then(|link| repo.get_prefab_instance(&link.prefab_instance_id)) else {
+                return Ok(0);
+            };
+            instance
+        }
+        None => return Ok(0),
+    };
+
+    let is_root_target = |target: &unity::object::ObjectReference| {
+        let Some(object) = repo.get_prefab_instance_object(&instance.id, target) else { return true; };
+        let go = match object {
+            unity::Object::GameObject(go) => Some(go),
+            unity::Object::Component(component) => {
+                repo.get_game_object(component.get_game_object_id())
+            }
+            _ => None,
+        };
+        go.is_some_and(|go| repo.get_prefab_instance_of_root(go).is_some())
+    };
+    let indices = instance
+        .modifications
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| {
+            !(overrides::is_default_override(&m.property_path) && is_root_target(&m.target))
+        })
+        .map(|(idx, _)| idx)
+        .collect::<Vec<usize>>();
+    let count =
+        indices.len() + instance.removed_components.len() + instance.removed_game_objects.len();
+    if count == 0 {
+        return Ok(0);
+    }
+    let entry = HistoryEntry::new(
+        format!(
+            "Revert all overrides of {}",
+            instance.get_name(meta_files_repository)
+        ),
+        vec![overrides::revert_all(instance, &indices)],
+    );
+    view_state.push(entry, history, meta_files_repository);
+    Ok(count)
+}
@@ -0,0 +1,86 @@
+use crate::{
+    ui::{
+        app::AppState,
+        screen::{self, Screen, UsagesList},
+    },
+    unity::{self, usages::Usage},
+};
+use crossterm::event::{Event, KeyCode};
+use std::io::{Error, ErrorKind};
+
+use super::{get_selected_component, select_object, select_property};
+
+/// Lists the files and objects that use the script of the selected component
+pub(super) fn find_selected_script_usages(state: &mut AppState) -> Result<(), Error> {
+    let Screen::HierarchyView(view_state) = &mut state.active_screen else { return Ok(()); };
+    let meta_files_repository = &state.meta_files_repository;
+    let Some(component) = get_selected_component(view_state, meta_files_repository) else { return Ok(()); };
+    let unity::Component::MonoBehaviour(monobehaviour) = component else {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "only the usages of scripts can be found, select a MonoBehaviour",
+        ));
+    };
+    if monobehaviour.script_guid.is_empty() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            "the component has no script",
+        ));
+    }
+    let usages_list = UsagesList::new(monobehaviour.script_guid.clone(), meta_files_repository);
+    state
+        .usages_index
+        .refresh(&state.project, meta_files_repository);
+    view_state.message = screen::parse_errors_message("files", &state.usages_index.get_errors());
+    view_state.usages_list = Some(usages_list);
+    Ok(())
+}
+
+/// Moves in the list of usages, enter goes to the selected one
+pub(super) fn handle_usages_event(event: &Event, state: &mut AppState) {
+    let Event::Key(e) = event else { return; };
+    let Screen::HierarchyView(view_state) = &mut state.active_screen else { return; };
+    let Some(usages_list) = &mut view_state.usages_list else { return; };
+    if let KeyCode::Esc | KeyCode::Char('f') = e.code {
+        view_state.usages_list = None;
+        return;
+    }
+    let usages = state.usages_index.get_usages(&usages_list.guid);
+    let Some(usage) = usages_list.handle_key(e.code, &usages).cloned() else { return; };
+    view_state.usages_list = None;
+    if let Err(e) = open_usage(state, &usage) {
+        let Screen::HierarchyView(view_state) = &mut state.active_screen else { unreachable!() };
+        view_state.message = Some(format!("Error: {}", e));
+    }
+}
+
+/// Selects the object the usage is in and its field, the file is opened if it is not the open one
+pub fn open_usage(state: &mut AppState, usage: &Usage) -> Result<(), Error> {
+    let path = state.project.base_path.join(&usage.path);
+    let meta_files_repository = &state.meta_files_repository;
+    if let Screen::HierarchyView(view_state) = &mut state.active_screen {
+        if view_state.selected_file_path == path {
+            if view_state.objects_repository.get(&usage.file_id).is_none() {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("{} is not in the hierarchy", usage.class_name),
+                ));
+            }
+            select_object(view_state, meta_files_repository, &usage.file_id)?;
+            select_property(view_state, meta_files_repository, &usage.property_path);
+            return Ok(());
+        }
+        if view_state.dirty {
+            return Err(Error::other("save the file before opening another one"));
+        }
+    }
+    let mut screen = Screen::new_hierarchy_view(path, meta_files_repository)?;
+    let Screen::HierarchyView(opened) = &mut screen else { unreachable!() };
+    // documents that are not in the hierarchy, like RenderSettings, leave the first object selected
+    if select_object(opened, meta_files_repository, &usage.file_id).is_ok() {
+        select_property(opened, meta_files_repository, &usage.property_path);
+    }
+    state.active_screen = screen;
+    state.history.clear();
+    Ok(())
+}
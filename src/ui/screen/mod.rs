@@ -1,19 +1,23 @@
 pub mod file_select;
 pub mod hierarchy_view;
 
-use crate::ui::screen::{file_select::FileSelectState, hierarchy_view::HierarchyViewState};
+use crate::{
+    ui::screen::{file_select::FileSelectState, hierarchy_view::HierarchyViewState},
+    unity::{error::ParseError, references, repository::MetaFilesRepository, usages::Usage, Guid},
+};
+use crossterm::event::KeyCode;
 use tui::{
     backend::Backend,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::Spans,
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
     Frame,
 };
 
 pub enum Screen {
     FileSelect(FileSelectState),
-    HierarchyView(HierarchyViewState),
+    HierarchyView(Box<HierarchyViewState>),
 }
 
 pub trait SelectNextPrev {
@@ -39,7 +43,7 @@ impl SelectNextPrev for ListState {
     }
 }
 
-pub(self) fn bordered_list<'a, T, U>(items: T, title: Option<U>) -> List<'a>
+fn bordered_list<'a, T, U>(items: T, title: Option<U>) -> List<'a>
 where
     T: Into<Vec<ListItem<'a>>>,
     U: Into<Spans<'a>>,
@@ -56,7 +60,7 @@ where
     )
 }
 
-pub(self) trait AvailableSize<B: Backend> {
+trait AvailableSize<B: Backend> {
     fn get_available_size(&self) -> Rect;
 }
 
@@ -72,7 +76,7 @@ impl<'a, B: Backend> AvailableSize<B> for Frame<'a, B> {
     }
 }
 
-pub(self) trait FooterRenderer<B: Backend> {
+trait FooterRenderer<B: Backend> {
     fn render_footer(&mut self, text: &str);
 }
 
@@ -94,3 +98,98 @@ impl<'a, B: Backend> FooterRenderer<B> for Frame<'a, B> {
         );
     }
 }
+
+/// Tells how many of the things could not be read, with the first problem
+pub fn parse_errors_message(things: &str, errors: &[ParseError]) -> Option<String> {
+    let first = errors.first()?;
+    Some(format!(
+        "Warning: {} {} could not be read, {}",
+        errors.len(),
+        things,
+        first
+    ))
+}
+
+/// The usages of an asset listed over the right half of a screen
+pub struct UsagesList {
+    pub guid: Guid,
+    /// The asset with its importer, or the missing script, shown in the title
+    pub description: String,
+    pub list_state: ListState,
+}
+
+impl UsagesList {
+    pub fn new(guid: Guid, meta_files_repository: &MetaFilesRepository) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select(Some(0));
+        let description = match meta_files_repository.get(&guid) {
+            Some(asset) => asset.describe(),
+            None => references::missing_script_name(&guid),
+        };
+        UsagesList {
+            guid,
+            description,
+            list_state,
+        }
+    }
+
+    fn render<B: Backend>(&mut self, f: &mut Frame<B>, usages: &[&Usage]) {
+        let size = f.get_available_size();
+        let area = Rect {
+            x: size.width - size.width / 2,
+            width: size.width / 2,
+            ..size
+        };
+        if let Some(idx) = self.list_state.selected() {
+            if idx >= usages.len() {
+                self.list_state
+                    .select(usages.len().checked_sub(1).or(Some(0)));
+            }
+        }
+        let mut items = usages
+            .iter()
+            .map(|usage| {
+                let object = match &usage.hierarchy_path {
+                    Some(hierarchy_path) => format!("{} ({})", hierarchy_path, usage.class_name),
+                    None => usage.class_name.clone(),
+                };
+                ListItem::new(format!(
+                    "{}: {} {}",
+                    usage.path.display(),
+                    object,
+                    usage.property_path
+                ))
+                .style(Style::reset())
+            })
+            .collect::<Vec<ListItem>>();
+        if items.is_empty() {
+            items.push(ListItem::new("No usages").style(Style::reset().fg(Color::Gray)));
+        }
+        let list = bordered_list(
+            items,
+            Some(format!("{} usages of {}", usages.len(), self.description)),
+        );
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    /// Moves in the list, returns the selected usage when it is confirmed
+    fn handle_key<'a>(&mut self, code: KeyCode, usages: &[&'a Usage]) -> Option<&'a Usage> {
+        match code {
+            KeyCode::Char('j') | KeyCode::Down if !usages.is_empty() => {
+                self.list_state.next_if_some(usages.len())
+            }
+            KeyCode::Char('k') | KeyCode::Up if !usages.is_empty() => {
+                self.list_state.prev_if_some(usages.len())
+            }
+            KeyCode::Enter => {
+                return self
+                    .list_state
+                    .selected()
+                    .and_then(|idx| usages.get(idx).copied())
+            }
+            _ => {}
+        }
+        None
+    }
+}
@@ -0,0 +1,81 @@
+use crate::unity::Guid;
+use std::path::{Path, PathBuf};
+
+/// A file or a folder of the project, found from its meta file
+#[derive(Debug, Clone)]
+pub struct Asset {
+    pub guid: Guid,
+    /// The path relative to the project, like `Assets/Scripts/My.Utils.cs`
+    pub path: PathBuf,
+    pub asset_type: AssetType,
+    /// The importer section of the meta file, like `MonoImporter`. None for folders
+    pub importer: Option<String>,
+}
+
+impl Asset {
+    /// The name the editor shows for the asset, its file name without the last extension.
+    /// `My.Utils.cs` is named `My.Utils`
+    pub fn get_name(&self) -> &str {
+        self.path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    }
+
+    /// The path with the importer, like `Assets/Scripts/Mover.cs (MonoImporter)`
+    pub fn describe(&self) -> String {
+        match &self.importer {
+            Some(importer) => format!("{} ({})", self.path.display(), importer),
+            None => self.path.display().to_string(),
+        }
+    }
+
+    pub fn is_script(&self) -> bool {
+        self.asset_type == AssetType::Script
+    }
+}
+
+/// What an asset is, from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetType {
+    Folder,
+    Script,
+    Scene,
+    Prefab,
+    /// Assets stored in Unity's YAML format that are not scenes or prefabs, like ScriptableObjects
+    /// and materials
+    NativeAsset,
+    Texture,
+    Model,
+    Audio,
+    Shader,
+    Other,
+}
+
+impl AssetType {
+    pub fn from_path(path: &Path, is_folder: bool) -> Self {
+        if is_folder {
+            return AssetType::Folder;
+        }
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        match extension.as_str() {
+            "cs" => AssetType::Script,
+            "unity" => AssetType::Scene,
+            "prefab" => AssetType::Prefab,
+            "asset" | "mat" | "anim" | "controller" | "overridecontroller" | "physicmaterial"
+            | "mask" | "mixer" | "rendertexture" | "spriteatlas" | "lighting" => {
+                AssetType::NativeAsset
+            }
+            "png" | "jpg" | "jpeg" | "tga" | "psd" | "tif" | "tiff" | "bmp" | "gif" | "exr"
+            | "hdr" => AssetType::Texture,
+            "fbx" | "obj" | "blend" | "dae" | "3ds" | "max" | "ma" | "mb" => AssetType::Model,
+            "wav" | "mp3" | "ogg" | "aif" | "aiff" | "flac" => AssetType::Audio,
+            "shader" | "shadergraph" | "compute" | "hlsl" | "cginc" => AssetType::Shader,
+            _ => AssetType::Other,
+        }
+    }
+}
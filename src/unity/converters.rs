@@ -37,7 +37,8 @@ impl AsGuid for Yaml {
     fn as_guid(&self) -> Option<Id> {
         match self {
             Yaml::Integer(i) => Some(i.to_string()),
-            Yaml::String(s) => Some(s.clone()),
+            // guids like the built-in 0000000000000000e000000000000000 are read as floats
+            Yaml::String(s) | Yaml::Real(s) => Some(s.clone()),
             _ => None,
         }
     }
@@ -83,10 +84,11 @@ impl GetFromStr for Hash {
 
 pub(super) mod helpers {
     use crate::unity::{
-        converters::{AsF32, GetFromStr},
-        vector::{Vector2, Vector3, Vector4},
+        converters::{AsF32, AsFileId, AsGuid, GetFromStr},
+        object::ObjectReference,
+        vector::{Color, Vector2, Vector3, Vector4},
     };
-    use unity_yaml_rust::yaml::Hash;
+    use unity_yaml_rust::{yaml::Hash, Yaml};
 
     pub fn obj_to_vec4(yaml: &Hash) -> Option<Vector4> {
         let x = yaml.get_from_str("x")?.as_f32()?;
@@ -108,4 +110,102 @@ pub(super) mod helpers {
         let y = yaml.get_from_str("y")?.as_f32()?;
         Some(Vector2 { x, y })
     }
+
+    pub fn obj_to_color(yaml: &Hash) -> Option<Color> {
+        let r = yaml.get_from_str("r")?.as_f32()?;
+        let g = yaml.get_from_str("g")?.as_f32()?;
+        let b = yaml.get_from_str("b")?.as_f32()?;
+        let a = yaml.get_from_str("a")?.as_f32()?;
+        Some(Color { r, g, b, a })
+    }
+
+    pub fn obj_to_reference(yaml: &Hash) -> Option<ObjectReference> {
+        let file_id = yaml.get_from_str("fileID")?.as_file_id()?;
+        let guid = yaml.get_from_str("guid").and_then(|g| g.as_guid());
+        let asset_type = yaml.get_from_str("type").and_then(|t| t.as_i64());
+        Some(ObjectReference {
+            file_id,
+            guid,
+            asset_type,
+        })
+    }
+
+    fn flow_obj(entries: &[(&str, Yaml)]) -> Hash {
+        let mut hash = Hash::new(false);
+        for (key, value) in entries {
+            hash.insert(Yaml::String((*key).to_owned()), value.clone());
+        }
+        hash
+    }
+
+    pub fn vec4_to_obj(vec: &Vector4) -> Hash {
+        flow_obj(&[
+            ("x", f32_to_yaml(vec.x)),
+            ("y", f32_to_yaml(vec.y)),
+            ("z", f32_to_yaml(vec.z)),
+            ("w", f32_to_yaml(vec.w)),
+        ])
+    }
+
+    pub fn vec3_to_obj(vec: &Vector3) -> Hash {
+        flow_obj(&[
+            ("x", f32_to_yaml(vec.x)),
+            ("y", f32_to_yaml(vec.y)),
+            ("z", f32_to_yaml(vec.z)),
+        ])
+    }
+
+    pub fn vec2_to_obj(vec: &Vector2) -> Hash {
+        flow_obj(&[("x", f32_to_yaml(vec.x)), ("y", f32_to_yaml(vec.y))])
+    }
+
+    pub fn color_to_obj(color: &Color) -> Hash {
+        flow_obj(&[
+            ("r", f32_to_yaml(color.r)),
+            ("g", f32_to_yaml(color.g)),
+            ("b", f32_to_yaml(color.b)),
+            ("a", f32_to_yaml(color.a)),
+        ])
+    }
+
+    pub fn reference_to_obj(reference: &ObjectReference) -> Hash {
+        let file_id = match reference.file_id.parse::<i64>() {
+            Ok(id) => Yaml::Integer(id),
+            Err(_) => Yaml::String(reference.file_id.clone()),
+        };
+        match (&reference.guid, reference.asset_type) {
+            (Some(guid), Some(asset_type)) => flow_obj(&[
+                ("fileID", file_id),
+                ("guid", Yaml::String(guid.clone())),
+                ("type", Yaml::Integer(asset_type)),
+            ]),
+            (Some(guid), None) => {
+                flow_obj(&[("fileID", file_id), ("guid", Yaml::String(guid.clone()))])
+            }
+            (None, _) => flow_obj(&[("fileID", file_id)]),
+        }
+    }
+
+    pub fn f32_to_yaml(value: f32) -> Yaml {
+        Yaml::Real(format_float(value, value.abs() as f64))
+    }
+
+    pub fn f64_to_yaml(value: f64) -> Yaml {
+        Yaml::Real(format_float(value, value.abs()))
+    }
+
+    /// Formats a float the way Unity does, with as few digits as needed to read back the same value
+    /// and an exponent for very small and very large values
+    fn format_float<T: std::fmt::Display + std::fmt::LowerExp>(value: T, abs: f64) -> String {
+        if abs != 0.0 && !(1e-5..1e15).contains(&abs) {
+            let s = format!("{:e}", value);
+            if s.contains("e-") {
+                s
+            } else {
+                s.replace('e', "e+")
+            }
+        } else {
+            value.to_string()
+        }
+    }
 }
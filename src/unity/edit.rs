@@ -0,0 +1,1438 @@
+use crate::class_id::{ClassId, PREFAB_INSTANCE_CLASS_ID};
+use crate::unity::{
+    converters::{AsFileId, GetFromStr},
+    object::{GetId, SceneRoots, Transform},
+    prefab::{self, body, body_mut, get_reference_id, instantiated_id, set_value},
+    repository::MetaFilesRepository,
+    version::UnityVersion,
+    writer,
+    yaml::{parse_str, YamlUnityDocument, YamlUnityFile},
+    Component, Id, Object, Repository,
+};
+use crate::util::hash_set;
+use linked_hash_map::LinkedHashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    io::Error,
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use unity_yaml_rust::{yaml::Hash, Yaml};
+
+const TRANSFORM_CLASS_IDS: [ClassId; 2] = [4, 224];
+
+/// A change made to a file, the repository built from the file is changed the same way
+#[derive(Debug, Clone)]
+pub enum Operation {
+    /// The object with the same id as `after` was replaced
+    Set {
+        before: Box<Object>,
+        after: Box<Object>,
+    },
+    /// The document was added to the file at the index
+    Insert {
+        index: usize,
+        document: YamlUnityDocument,
+    },
+    /// The document at the index was removed from the file
+    Remove {
+        index: usize,
+        document: YamlUnityDocument,
+    },
+}
+
+impl Operation {
+    pub fn set(before: Object, after: Object) -> Operation {
+        Operation::Set {
+            before: Box::new(before),
+            after: Box::new(after),
+        }
+    }
+
+    /// Returns the operation that undoes this one
+    pub fn reversed(&self) -> Operation {
+        match self {
+            Operation::Set { before, after } => Operation::Set {
+                before: after.clone(),
+                after: before.clone(),
+            },
+            Operation::Insert { index, document } => Operation::Remove {
+                index: *index,
+                document: document.clone(),
+            },
+            Operation::Remove { index, document } => Operation::Insert {
+                index: *index,
+                document: document.clone(),
+            },
+        }
+    }
+}
+
+/// Applies the operations in order to the file and to the repository built from it
+pub fn apply_operations(
+    operations: &[Operation],
+    file: &mut YamlUnityFile,
+    repo: &mut Repository,
+    meta_files_repository: &MetaFilesRepository,
+) {
+    // the objects derived from other documents are read again from the file after the changes
+    let mut rebuild = false;
+    for operation in operations {
+        match operation {
+            Operation::Set { after, .. } => {
+                // stripped documents only link to the prefab, the rest comes from it
+                if let Some(doc) = file
+                    .documents
+                    .iter_mut()
+                    .find(|doc| doc.id == *after.get_id() && !doc.stripped)
+                {
+                    writer::update_document(doc, after);
+                }
+                repo.set_object((**after).clone());
+                // the parent and the root order of the instance root come from the modifications,
+                // the root order of the objects at the root of newer scenes from SceneRoots
+                rebuild |= matches!(**after, Object::PrefabInstance(_) | Object::SceneRoots(_));
+            }
+            Operation::Insert { index, document } => {
+                file.documents.insert(*index, document.clone());
+                repo.insert_document(document);
+                rebuild |= is_prefab_instance_document(document);
+            }
+            Operation::Remove { index, document } => {
+                file.documents.remove(*index);
+                repo.remove_object(&document.id);
+                rebuild |= is_prefab_instance_document(document);
+            }
+        }
+    }
+    // the contents of prefab instances come from their prefabs, they are merged in again
+    if rebuild {
+        let docs = prefab::resolve_prefab_instances(file.documents.clone(), meta_files_repository);
+        (*repo, _) = crate::unity::construct_repository(docs);
+    }
+}
+
+fn is_prefab_instance_document(doc: &YamlUnityDocument) -> bool {
+    doc.stripped || doc.class_id == PREFAB_INSTANCE_CLASS_ID
+}
+
+/// Makes file IDs that are not used in the file yet. Like the ones Unity makes they are random, so
+/// copies of a file edited separately are unlikely to get the same ones
+pub struct FileIdGenerator {
+    used: HashSet<Id>,
+    /// The state of a xorshift generator, it is never 0
+    state: u64,
+}
+
+impl FileIdGenerator {
+    pub fn new(file: &YamlUnityFile, repo: &Repository) -> Self {
+        let used = file
+            .documents
+            .iter()
+            .map(|doc| doc.id.clone())
+            .chain(repo.get_ids().cloned())
+            .collect();
+        // seeded once from the time and the process, two editors started together differ anyway
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+        let state = (nanos ^ u64::from(process::id()).rotate_left(32)) | 1;
+        Self { used, state }
+    }
+
+    /// Returns a positive id that is not in the file, the ids already used are skipped
+    pub fn next_id(&mut self) -> Id {
+        loop {
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 7;
+            self.state ^= self.state << 17;
+            let id = self.state & 0x7FFF_FFFF_FFFF_FFFF;
+            if id != 0 && self.used.insert(id.to_string()) {
+                return id.to_string();
+            }
+        }
+    }
+}
+
+/// Returns the operations that create an empty game object under the transform, or at the root of
+/// the file without one. It gets a RectTransform if its parent has one, like in the editor.
+/// Returns the id of the new game object with them
+pub fn create_game_object(
+    file: &YamlUnityFile,
+    repo: &Repository,
+    ids: &mut FileIdGenerator,
+    parent_id: Option<&Id>,
+    version: Option<&UnityVersion>,
+) -> Option<(Id, Vec<Operation>)> {
+    let parent = match parent_id {
+        Some(id) => Some(repo.get_transform(id)?),
+        None => None,
+    };
+    let game_object_id = ids.next_id();
+    let transform_id = ids.next_id();
+    let root_order = match parent {
+        Some(parent) => parent.get_children_ids().len(),
+        None => repo.get_unparented_transforms().len(),
+    };
+    let text = new_game_object_text(
+        &game_object_id,
+        &transform_id,
+        parent.map_or("0", |parent| parent.get_id().as_str()),
+        uses_root_order(file, version).then_some(root_order),
+        matches!(parent, Some(Transform::RectTransform(_))),
+    );
+
+    let mut operations = parse_str(&text)
+        .ok()?
+        .into_iter()
+        .enumerate()
+        .map(|(idx, document)| Operation::Insert {
+            index: file.documents.len() + idx,
+            document,
+        })
+        .collect::<Vec<Operation>>();
+    match parent {
+        Some(parent) => {
+            let mut after = parent.clone();
+            children_ids_mut(&mut after).push(transform_id);
+            operations.push(set_transform(parent, after));
+        }
+        None => {
+            if let Some(scene_roots) = repo.get_scene_roots() {
+                let mut root_ids = scene_roots.root_ids.clone();
+                root_ids.push(transform_id);
+                operations.extend(set_scene_roots(repo, root_ids));
+            }
+        }
+    }
+    Some((game_object_id, operations))
+}
+
+/// Newer versions of Unity do not write the root order of transforms anymore. The transforms in the
+/// file tell if it is written, the version of the project does for a file without any
+fn uses_root_order(file: &YamlUnityFile, version: Option<&UnityVersion>) -> bool {
+    let mut transforms = file
+        .documents
+        .iter()
+        .filter(|doc| !doc.stripped && TRANSFORM_CLASS_IDS.contains(&doc.class_id))
+        .filter_map(|doc| body(&doc.document))
+        .peekable();
+    if transforms.peek().is_none() {
+        return version.is_none_or(|version| version.has_field("Transform", "m_RootOrder"));
+    }
+    transforms.any(|body| body.get_from_str("m_RootOrder").is_some())
+}
+
+fn new_game_object_text(
+    game_object_id: &Id,
+    transform_id: &Id,
+    father_id: &str,
+    root_order: Option<usize>,
+    rect: bool,
+) -> String {
+    let mut lines = vec![
+        format!("--- !u!1 &{}", game_object_id),
+        "GameObject:".to_owned(),
+        "  m_ObjectHideFlags: 0".to_owned(),
+        "  m_CorrespondingSourceObject: {fileID: 0}".to_owned(),
+        "  m_PrefabInstance: {fileID: 0}".to_owned(),
+        "  m_PrefabAsset: {fileID: 0}".to_owned(),
+        "  serializedVersion: 6".to_owned(),
+        "  m_Component:".to_owned(),
+        format!("  - component: {{fileID: {}}}", transform_id),
+        format!("  m_Layer: {}", if rect { 5 } else { 0 }),
+        "  m_Name: GameObject".to_owned(),
+        "  m_TagString: Untagged".to_owned(),
+        "  m_Icon: {fileID: 0}".to_owned(),
+        "  m_NavMeshLayer: 0".to_owned(),
+        "  m_StaticEditorFlags: 0".to_owned(),
+        "  m_IsActive: 1".to_owned(),
+    ];
+    if rect {
+        lines.push(format!("--- !u!224 &{}", transform_id));
+        lines.push("RectTransform:".to_owned());
+    } else {
+        lines.push(format!("--- !u!4 &{}", transform_id));
+        lines.push("Transform:".to_owned());
+    }
+    lines.extend([
+        "  m_ObjectHideFlags: 0".to_owned(),
+        "  m_CorrespondingSourceObject: {fileID: 0}".to_owned(),
+        "  m_PrefabInstance: {fileID: 0}".to_owned(),
+        "  m_PrefabAsset: {fileID: 0}".to_owned(),
+        format!("  m_GameObject: {{fileID: {}}}", game_object_id),
+    ]);
+    if !rect {
+        lines.push("  serializedVersion: 2".to_owned());
+    }
+    lines.extend([
+        "  m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}".to_owned(),
+        "  m_LocalPosition: {x: 0, y: 0, z: 0}".to_owned(),
+        "  m_LocalScale: {x: 1, y: 1, z: 1}".to_owned(),
+        "  m_ConstrainProportionsScale: 0".to_owned(),
+        "  m_Children: []".to_owned(),
+        format!("  m_Father: {{fileID: {}}}", father_id),
+    ]);
+    if let Some(root_order) = root_order {
+        lines.push(format!("  m_RootOrder: {}", root_order));
+    }
+    lines.push("  m_LocalEulerAnglesHint: {x: 0, y: 0, z: 0}".to_owned());
+    if rect {
+        lines.extend([
+            "  m_AnchorMin: {x: 0.5, y: 0.5}".to_owned(),
+            "  m_AnchorMax: {x: 0.5, y: 0.5}".to_owned(),
+            "  m_AnchoredPosition: {x: 0, y: 0}".to_owned(),
+            "  m_SizeDelta: {x: 100, y: 100}".to_owned(),
+            "  m_Pivot: {x: 0.5, y: 0.5}".to_owned(),
+        ]);
+    }
+    lines.join("\n")
+}
+
+/// Returns the operations that delete a game object or a prefab instance with everything under it:
+/// the components, the children and the prefab instances parented to them
+pub fn delete_game_object(file: &YamlUnityFile, repo: &Repository, id: &Id) -> Vec<Operation> {
+    let removed = get_subtree_ids(file, id);
+    let mut operations = unlink_from_parents(repo, &removed);
+    if let Some(scene_roots) = repo.get_scene_roots() {
+        let mut root_ids = scene_roots.root_ids.clone();
+        root_ids.retain(|root_id| !removed.contains(root_id));
+        operations.extend(set_scene_roots(repo, root_ids));
+    }
+    // removed from the back so the indices of the ones before stay the same
+    for (index, document) in file.documents.iter().enumerate().rev() {
+        if removed.contains(&document.id) {
+            operations.push(Operation::Remove {
+                index,
+                document: document.clone(),
+            });
+        }
+    }
+    operations
+}
+
+/// Returns the operations that copy a game object or a prefab instance with everything under it,
+/// the copy gets the same parent. Returns the id of the copy with them
+pub fn duplicate_game_object(
+    file: &YamlUnityFile,
+    repo: &Repository,
+    ids: &mut FileIdGenerator,
+    id: &Id,
+) -> Option<(Id, Vec<Operation>)> {
+    let subtree = get_subtree_ids(file, id);
+    let docs = file
+        .documents
+        .iter()
+        .filter(|doc| subtree.contains(&doc.id))
+        .collect::<Vec<&YamlUnityDocument>>();
+
+    let mut new_ids = docs
+        .iter()
+        .filter(|doc| !doc.stripped)
+        .map(|doc| (doc.id.clone(), ids.next_id()))
+        .collect::<HashMap<Id, Id>>();
+    // stripped objects get the ids Unity gives to the objects of the new prefab instance
+    for doc in docs.iter().filter(|doc| doc.stripped) {
+        let instance_id = new_ids.get(&get_reference_id(doc, "m_PrefabInstance")?)?;
+        let source_id = get_reference_id(doc, "m_CorrespondingSourceObject")?;
+        new_ids.insert(doc.id.clone(), instantiated_id(instance_id, &source_id)?);
+    }
+
+    let copy_id = new_ids.get(id)?.clone();
+    // a prefab instance is named by its root game object
+    let original = repo.get_game_object(id).or_else(|| {
+        repo.get_game_object_ids()
+            .iter()
+            .filter_map(|go_id| repo.get_game_object(go_id))
+            .find(|go| {
+                repo.get_prefab_instance_of_root(go)
+                    .is_some_and(|instance| instance.id == *id)
+            })
+    });
+    let names = original.map(|go| {
+        let copy_name = get_copy_name(&go.name, &get_sibling_names(repo, &go.id));
+        (go.name.clone(), copy_name)
+    });
+    // the copy is added after the last child of the parent
+    let root_order = match get_transforms(repo).into_iter().find(|transform| {
+        !subtree.contains(transform.get_id())
+            && transform
+                .get_children_ids()
+                .iter()
+                .any(|child_id| subtree.contains(child_id))
+    }) {
+        Some(parent) => parent.get_children_ids().len(),
+        None => repo.get_unparented_transforms().len(),
+    };
+    let mut operations = vec![];
+    for (idx, doc) in docs.into_iter().enumerate() {
+        let mut copy = doc.clone();
+        copy.id = new_ids.get(&doc.id)?.clone();
+        remap_references(&mut copy.document, &new_ids);
+        if let (true, Some((name, copy_name)), Some(body)) =
+            (doc.id == *id, &names, body_mut(&mut copy.document))
+        {
+            if is_prefab_instance_document(doc) {
+                rename_prefab_instance(body, name, copy_name);
+            } else {
+                set_value(body, "m_Name", Yaml::String(copy_name.clone()));
+            }
+        }
+        let is_copied_transform = get_reference_id(doc, "m_GameObject").as_ref() == Some(id);
+        if let (true, Some(body)) = (is_copied_transform, body_mut(&mut copy.document)) {
+            if body.get_from_str("m_RootOrder").is_some() {
+                set_value(body, "m_RootOrder", Yaml::Integer(root_order as i64));
+            }
+        }
+        operations.push(Operation::Insert {
+            index: file.documents.len() + idx,
+            document: copy,
+        });
+    }
+
+    // the copies of the objects under the same parent as the original
+    for transform in get_transforms(repo) {
+        if subtree.contains(transform.get_id()) {
+            continue;
+        }
+        let copied_children = transform
+            .get_children_ids()
+            .iter()
+            .filter_map(|child_id| new_ids.get(child_id))
+            .cloned()
+            .collect::<Vec<Id>>();
+        if !copied_children.is_empty() {
+            let mut after = transform.clone();
+            children_ids_mut(&mut after).extend(copied_children);
+            operations.push(set_transform(transform, after));
+        }
+    }
+    // a copy of an object at the root of a newer scene is listed after the others
+    if let Some(scene_roots) = repo.get_scene_roots() {
+        let copied_root = get_scene_root_id(repo, id)
+            .filter(|root_id| scene_roots.root_ids.contains(root_id))
+            .and_then(|root_id| new_ids.get(&root_id));
+        if let Some(copied_root) = copied_root {
+            let mut root_ids = scene_roots.root_ids.clone();
+            root_ids.push(copied_root.clone());
+            operations.extend(set_scene_roots(repo, root_ids));
+        }
+    }
+    Some((copy_id, operations))
+}
+
+/// Returns the operations that rename a game object
+pub fn rename_game_object(repo: &Repository, id: &Id, name: &str) -> Option<Vec<Operation>> {
+    let before = repo.get_game_object(id)?;
+    let mut after = before.clone();
+    after.name = name.to_owned();
+    Some(vec![Operation::set(
+        Object::GameObject(before.clone()),
+        Object::GameObject(after),
+    )])
+}
+
+/// Returns the operations that add the component document to the end of the file and to the
+/// components of its game object. Returns the id of the component with them
+pub fn add_component(
+    file: &YamlUnityFile,
+    repo: &Repository,
+    game_object_id: &Id,
+    document: YamlUnityDocument,
+) -> Option<(Id, Vec<Operation>)> {
+    if !has_own_document(file, game_object_id) {
+        return None;
+    }
+    let before = repo.get_game_object(game_object_id)?;
+    let mut after = before.clone();
+    after.component_ids.push(document.id.clone());
+    let id = document.id.clone();
+    Some((
+        id,
+        vec![
+            Operation::Insert {
+                index: file.documents.len(),
+                document,
+            },
+            Operation::set(
+                Object::GameObject(before.clone()),
+                Object::GameObject(after),
+            ),
+        ],
+    ))
+}
+
+/// Returns the operations that remove the component from the file and from the components of its
+/// game object. Transforms are only removed with their game object
+pub fn remove_component(
+    file: &YamlUnityFile,
+    repo: &Repository,
+    id: &Id,
+) -> Option<Vec<Operation>> {
+    let component = repo.get_component(id)?;
+    let game_object_id = component.get_game_object_id();
+    let (index, document) = file
+        .documents
+        .iter()
+        .enumerate()
+        .find(|(_, doc)| doc.id == *id && !doc.stripped)?;
+    if TRANSFORM_CLASS_IDS.contains(&document.class_id) || !has_own_document(file, game_object_id) {
+        return None;
+    }
+    let before = repo.get_game_object(game_object_id)?;
+    let mut after = before.clone();
+    after
+        .component_ids
+        .retain(|component_id| component_id != id);
+    Some(vec![
+        Operation::set(
+            Object::GameObject(before.clone()),
+            Object::GameObject(after),
+        ),
+        Operation::Remove {
+            index,
+            document: document.clone(),
+        },
+    ])
+}
+
+/// Objects merged in from a prefab can only be changed through the modifications of the instance
+fn has_own_document(file: &YamlUnityFile, id: &Id) -> bool {
+    file.documents
+        .iter()
+        .any(|doc| doc.id == *id && !doc.stripped)
+}
+
+/// Returns the id of the transform the game object or the prefab instance is a child of, None for
+/// the objects at the root of the file
+pub fn get_hierarchy_parent(repo: &Repository, id: &Id) -> Option<Id> {
+    let parent_id = match repo.get_prefab_instance(id) {
+        Some(instance) => &instance.transform_parent_id,
+        None => get_game_object_transform(repo, id)?.get_father_id(),
+    };
+    (parent_id != "0").then(|| parent_id.clone())
+}
+
+/// Returns the ids of the game objects and the prefab instances under the transform, or at the root
+/// of the file, in the order they are shown in the hierarchy. Objects that come from a prefab are
+/// not listed, they cannot be moved
+pub fn get_hierarchy_siblings(
+    file: &YamlUnityFile,
+    repo: &Repository,
+    parent_id: Option<&Id>,
+) -> Vec<Id> {
+    let mut siblings = match parent_id {
+        Some(parent_id) => repo
+            .get_transform(parent_id)
+            .map(|parent| parent.get_children_ids().clone())
+            .unwrap_or_default()
+            .iter()
+            .filter(|child_id| file.documents.iter().any(|doc| &doc.id == *child_id))
+            .filter_map(|child_id| get_hierarchy_entry(file, repo, child_id))
+            .collect::<Vec<Id>>(),
+        None => file
+            .documents
+            .iter()
+            .filter(|doc| !doc.stripped)
+            .filter_map(|doc| match repo.get(&doc.id)? {
+                Object::Component(Component::Transform(transform)) if !transform.has_parent() => {
+                    Some(transform.get_game_object_id().clone())
+                }
+                Object::PrefabInstance(instance) if !instance.has_parent() => {
+                    Some(instance.id.clone())
+                }
+                _ => None,
+            })
+            .collect(),
+    };
+    siblings.sort_by_key(|id| get_hierarchy_root_order(repo, id));
+    siblings
+}
+
+/// Returns the id of the game object or the prefab instance of the transform if it is in the file.
+/// Transforms of objects from a prefab return their prefab instance
+pub fn get_hierarchy_entry(
+    file: &YamlUnityFile,
+    repo: &Repository,
+    transform_id: &Id,
+) -> Option<Id> {
+    let doc = file.documents.iter().find(|doc| &doc.id == transform_id);
+    match doc {
+        Some(doc) if !doc.stripped => Some(
+            repo.get_transform(transform_id)?
+                .get_game_object_id()
+                .clone(),
+        ),
+        Some(doc) => get_reference_id(doc, "m_PrefabInstance"),
+        None => repo
+            .get_prefab_link(transform_id)
+            .map(|link| link.prefab_instance_id.clone()),
+    }
+}
+
+fn get_hierarchy_root_order(repo: &Repository, id: &Id) -> i64 {
+    match repo.get_prefab_instance(id) {
+        Some(instance) => repo.get_prefab_instance_root_order(instance),
+        None => {
+            get_game_object_transform(repo, id).map_or(0, |transform| transform.get_root_order())
+        }
+    }
+}
+
+fn get_game_object_transform<'a>(
+    repo: &'a Repository,
+    game_object_id: &Id,
+) -> Option<&'a Transform> {
+    repo.get_game_object(game_object_id)?
+        .component_ids
+        .iter()
+        .find_map(|id| repo.get_transform(id))
+}
+
+/// Returns the id the transform of the game object or of the prefab instance root has in the file,
+/// it is the one listed in the children of its parent
+pub fn get_hierarchy_transform_id(file: &YamlUnityFile, repo: &Repository, id: &Id) -> Option<Id> {
+    let Some(instance) = repo.get_prefab_instance(id) else { return get_game_object_transform(repo, id).map(|transform| transform.get_id().clone()); };
+    let candidates = file
+        .documents
+        .iter()
+        .filter(|doc| doc.stripped && TRANSFORM_CLASS_IDS.contains(&doc.class_id))
+        .filter(|doc| get_reference_id(doc, "m_PrefabInstance").as_ref() == Some(id))
+        .map(|doc| doc.id.clone())
+        .collect::<Vec<Id>>();
+    let listed_in_parent = repo
+        .get_transform(&instance.transform_parent_id)
+        .and_then(|parent| {
+            candidates
+                .iter()
+                .find(|id| parent.get_children_ids().contains(id))
+        });
+    let resolved_root = candidates.iter().find(|id| {
+        repo.get_transform(id)
+            .is_some_and(|transform| transform.get_father_id() == &instance.transform_parent_id)
+    });
+    match (listed_in_parent.or(resolved_root), candidates.as_slice()) {
+        (Some(id), _) => Some(id.clone()),
+        (None, [only]) => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the operations that move a game object or a prefab instance to the index among the
+/// children of the transform, or among the objects at the root of the file. The children of both
+/// parents get their root order numbered again, and their children listed in that order
+pub fn move_game_object(
+    file: &YamlUnityFile,
+    repo: &Repository,
+    id: &Id,
+    parent_id: Option<&Id>,
+    index: usize,
+) -> Result<Vec<Operation>, Error> {
+    if let Some(parent_id) = parent_id {
+        if !file
+            .documents
+            .iter()
+            .any(|doc| &doc.id == parent_id && !doc.stripped)
+        {
+            return Err(Error::other(
+                "objects cannot be moved into a prefab instance here, edit the prefab instead",
+            ));
+        }
+        if get_subtree_ids(file, id).contains(parent_id) {
+            return Err(Error::other(
+                "an object cannot be moved into its own children",
+            ));
+        }
+    }
+    let old_parent_id = get_hierarchy_parent(repo, id);
+    let mut operations = vec![];
+    let mut transform_id = get_hierarchy_transform_id(file, repo, id);
+    // Unity only writes the transform of a prefab instance root to the file when it is needed
+    if transform_id.is_none() && parent_id.is_some() {
+        if let Some(document) = new_stripped_root_transform(repo, id) {
+            transform_id = Some(document.id.clone());
+            operations.push(Operation::Insert {
+                index: file.documents.len(),
+                document,
+            });
+        }
+    }
+    if transform_id.is_none() && (old_parent_id.is_some() || parent_id.is_some()) {
+        return Err(Error::other(
+            "the transform of the prefab instance root is not in the file",
+        ));
+    }
+
+    // the objects as they are after the move, kept in the order they were changed
+    let mut changed = LinkedHashMap::<Id, Object>::new();
+
+    let mut siblings = get_hierarchy_siblings(file, repo, parent_id);
+    siblings.retain(|sibling| sibling != id);
+    siblings.insert(index.min(siblings.len()), id.clone());
+    let mut old_siblings = get_hierarchy_siblings(file, repo, old_parent_id.as_ref());
+    old_siblings.retain(|sibling| sibling != id);
+
+    // the moved object
+    match repo.get_prefab_instance(id) {
+        Some(instance) => {
+            let mut after = instance.clone();
+            after.transform_parent_id = parent_id.map_or("0".to_owned(), |id| id.clone());
+            changed.insert(id.clone(), Object::PrefabInstance(after));
+        }
+        None => {
+            let transform = get_game_object_transform(repo, id)
+                .ok_or_else(|| Error::other("the game object has no transform"))?;
+            let mut after = transform.clone();
+            *father_id_mut(&mut after) = parent_id.map_or("0".to_owned(), |id| id.clone());
+            changed.insert(
+                transform.get_id().clone(),
+                Object::Component(Component::Transform(after)),
+            );
+        }
+    }
+    // the old parent
+    if let (Some(old_parent_id), Some(transform_id)) = (&old_parent_id, &transform_id) {
+        if Some(old_parent_id) != parent_id {
+            if let Some(old_parent) = repo.get_transform(old_parent_id) {
+                let mut after = old_parent.clone();
+                children_ids_mut(&mut after).retain(|child_id| child_id != transform_id);
+                changed.insert(
+                    old_parent_id.clone(),
+                    Object::Component(Component::Transform(after)),
+                );
+            }
+        }
+    }
+    // the new parent lists its children in their new order
+    if let Some(parent) = parent_id.and_then(|parent_id| repo.get_transform(parent_id)) {
+        let mut children = siblings
+            .iter()
+            .filter_map(|sibling| get_hierarchy_transform_id(file, repo, sibling))
+            .collect::<Vec<Id>>();
+        // children that could not be read stay at the end
+        let unread = parent
+            .get_children_ids()
+            .iter()
+            .filter(|child_id| {
+                !children.contains(child_id) && Some(*child_id) != transform_id.as_ref()
+            })
+            .cloned()
+            .collect::<Vec<Id>>();
+        children.extend(unread);
+        let mut after = parent.clone();
+        *children_ids_mut(&mut after) = children;
+        changed.insert(
+            parent.get_id().clone(),
+            Object::Component(Component::Transform(after)),
+        );
+    }
+
+    for (idx, sibling) in siblings.iter().enumerate() {
+        set_root_order(repo, &mut changed, sibling, idx as i64);
+    }
+    if old_parent_id.as_ref() != parent_id {
+        for (idx, sibling) in old_siblings.iter().enumerate() {
+            set_root_order(repo, &mut changed, sibling, idx as i64);
+        }
+    }
+
+    for (object_id, after) in changed {
+        let Some(before) = repo.get(&object_id) else { continue; };
+        operations.push(Operation::set(before.clone(), after));
+    }
+
+    // newer scenes list the objects at the root in SceneRoots
+    let roots = match (parent_id, &old_parent_id) {
+        (None, _) => Some(&siblings),
+        (Some(_), None) => Some(&old_siblings),
+        (Some(_), Some(_)) => None,
+    };
+    if let (Some(roots), Some(scene_roots)) = (roots, repo.get_scene_roots()) {
+        let moved_id = get_scene_root_id(repo, id);
+        let mut root_ids = roots
+            .iter()
+            .filter_map(|root| get_scene_root_id(repo, root))
+            .collect::<Vec<Id>>();
+        // roots that could not be read stay at the end
+        let unread = scene_roots
+            .root_ids
+            .iter()
+            .filter(|root_id| !root_ids.contains(root_id) && Some(*root_id) != moved_id.as_ref())
+            .cloned()
+            .collect::<Vec<Id>>();
+        root_ids.extend(unread);
+        operations.extend(set_scene_roots(repo, root_ids));
+    }
+    Ok(operations)
+}
+
+/// Returns the id SceneRoots lists the game object or the prefab instance by
+fn get_scene_root_id(repo: &Repository, id: &Id) -> Option<Id> {
+    match repo.get_prefab_instance(id) {
+        Some(instance) => Some(instance.id.clone()),
+        None => get_game_object_transform(repo, id).map(|transform| transform.get_id().clone()),
+    }
+}
+
+/// Returns the operation that lists the roots in SceneRoots, None for the files without it or if
+/// they are already listed that way
+fn set_scene_roots(repo: &Repository, root_ids: Vec<Id>) -> Option<Operation> {
+    let before = repo.get_scene_roots()?;
+    if before.root_ids == root_ids {
+        return None;
+    }
+    let after = SceneRoots {
+        id: before.id.clone(),
+        root_ids,
+    };
+    Some(Operation::set(
+        Object::SceneRoots(before.clone()),
+        Object::SceneRoots(after),
+    ))
+}
+
+/// Returns the stripped document of the root transform of a prefab instance that was merged in
+fn new_stripped_root_transform(repo: &Repository, instance_id: &Id) -> Option<YamlUnityDocument> {
+    let instance = repo.get_prefab_instance(instance_id)?;
+    let transform = get_transforms(repo).into_iter().find(|transform| {
+        transform.get_father_id() == &instance.transform_parent_id
+            && repo
+                .get_prefab_link(transform.get_id())
+                .is_some_and(|link| &link.prefab_instance_id == instance_id)
+    })?;
+    let source = &repo
+        .get_prefab_link(transform.get_id())?
+        .corresponding_source;
+    let (class_id, class_name) = match transform {
+        Transform::Transform3D(_) => (4, "Transform"),
+        Transform::RectTransform(_) => (224, "RectTransform"),
+    };
+    let text = [
+        format!("--- !u!{} &{} stripped", class_id, transform.get_id()),
+        format!("{}:", class_name),
+        format!(
+            "  m_CorrespondingSourceObject: {{fileID: {}, guid: {}, type: 3}}",
+            source.file_id,
+            source.guid.as_deref()?
+        ),
+        format!("  m_PrefabInstance: {{fileID: {}}}", instance_id),
+        "  m_PrefabAsset: {fileID: 0}".to_owned(),
+    ]
+    .join("\n");
+    parse_str(&text).ok()?.pop()
+}
+
+/// Changes the root order of the game object or of the prefab instance in the changed objects
+fn set_root_order(
+    repo: &Repository,
+    changed: &mut LinkedHashMap<Id, Object>,
+    id: &Id,
+    root_order: i64,
+) {
+    let object_id = match repo.get_prefab_instance(id) {
+        Some(instance) => instance.id.clone(),
+        None => match get_game_object_transform(repo, id) {
+            Some(transform) => transform.get_id().clone(),
+            None => return,
+        },
+    };
+    let Some(object) = changed.get(&object_id).or_else(|| repo.get(&object_id)) else { return; };
+    let mut after = object.clone();
+    match &mut after {
+        Object::PrefabInstance(instance) => {
+            let Some(m) = instance
+                .modifications
+                .iter_mut()
+                .find(|m| m.property_path == "m_RootOrder") else {
+                return;
+            };
+            if m.value.as_i64() == Some(root_order) {
+                return;
+            }
+            m.value = Yaml::Integer(root_order);
+        }
+        Object::Component(Component::Transform(transform)) => {
+            let current = root_order_mut(transform);
+            if *current == root_order {
+                return;
+            }
+            *current = root_order;
+        }
+        _ => return,
+    }
+    changed.insert(object_id, after);
+}
+
+/// Returns the ids of the documents of the object and everything under it. The documents are
+/// followed through their links, so objects that could not be read are found too
+fn get_subtree_ids(file: &YamlUnityFile, id: &Id) -> HashSet<Id> {
+    let mut ids = hash_set![id.clone()];
+    loop {
+        let count = ids.len();
+        for doc in file.documents.iter() {
+            if ids.contains(&doc.id) {
+                continue;
+            }
+            let is_linked = ["m_GameObject", "m_Father", "m_PrefabInstance"]
+                .iter()
+                .filter_map(|key| get_reference_id(doc, key))
+                .chain(get_transform_parent_id(doc))
+                .any(|id| ids.contains(&id));
+            if is_linked {
+                ids.insert(doc.id.clone());
+                // the game object of a child transform
+                if let Some(game_object_id) = get_reference_id(doc, "m_GameObject") {
+                    ids.insert(game_object_id);
+                }
+            }
+        }
+        if ids.len() == count {
+            return ids;
+        }
+    }
+}
+
+fn get_transform_parent_id(doc: &YamlUnityDocument) -> Option<Id> {
+    body(&doc.document)?
+        .get_from_str("m_Modification")?
+        .as_hash()?
+        .get_from_str("m_TransformParent")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()
+}
+
+/// Returns the operations that remove the objects from the children of the transforms they are in
+fn unlink_from_parents(repo: &Repository, removed: &HashSet<Id>) -> Vec<Operation> {
+    get_transforms(repo)
+        .into_iter()
+        .filter(|transform| !removed.contains(transform.get_id()))
+        .filter(|transform| {
+            transform
+                .get_children_ids()
+                .iter()
+                .any(|id| removed.contains(id))
+        })
+        .map(|transform| {
+            let mut after = transform.clone();
+            children_ids_mut(&mut after).retain(|id| !removed.contains(id));
+            set_transform(transform, after)
+        })
+        .collect()
+}
+
+fn get_transforms(repo: &Repository) -> Vec<&Transform> {
+    repo.get_ids()
+        .filter_map(|id| repo.get_transform(id))
+        .collect()
+}
+
+fn children_ids_mut(transform: &mut Transform) -> &mut Vec<Id> {
+    match transform {
+        Transform::Transform3D(t) => &mut t.children_ids,
+        Transform::RectTransform(t) => &mut t.children_ids,
+    }
+}
+
+fn father_id_mut(transform: &mut Transform) -> &mut Id {
+    match transform {
+        Transform::Transform3D(t) => &mut t.father_id,
+        Transform::RectTransform(t) => &mut t.father_id,
+    }
+}
+
+fn root_order_mut(transform: &mut Transform) -> &mut i64 {
+    match transform {
+        Transform::Transform3D(t) => &mut t.root_order,
+        Transform::RectTransform(t) => &mut t.root_order,
+    }
+}
+
+fn set_transform(before: &Transform, after: Transform) -> Operation {
+    Operation::set(
+        Object::Component(Component::Transform(before.clone())),
+        Object::Component(Component::Transform(after)),
+    )
+}
+
+fn get_sibling_names(repo: &Repository, game_object_id: &Id) -> HashSet<String> {
+    let father_id = get_transforms(repo)
+        .into_iter()
+        .find(|transform| transform.get_game_object_id() == game_object_id)
+        .map(|transform| transform.get_father_id().clone());
+    let siblings = match father_id.as_ref().and_then(|id| repo.get_transform(id)) {
+        Some(father) => father
+            .get_children_ids()
+            .iter()
+            .filter_map(|id| repo.get_transform(id))
+            .collect(),
+        None => repo.get_unparented_transforms(),
+    };
+    siblings
+        .into_iter()
+        .filter_map(|transform| repo.get_game_object(transform.get_game_object_id()))
+        .map(|go| go.name.clone())
+        .collect()
+}
+
+/// Changes the name override of the prefab instance, the name comes from the prefab without one
+fn rename_prefab_instance(body: &mut Hash, name: &str, new_name: &str) {
+    let Some(Yaml::Hash(modification)) = body.get_mut(&Yaml::String("m_Modification".to_owned())) else { return; };
+    let Some(Yaml::Array(modifications)) =
+        modification.get_mut(&Yaml::String("m_Modifications".to_owned())) else {
+        return;
+    };
+    for item in modifications.iter_mut() {
+        let Yaml::Hash(item) = item else { continue; };
+        let is_name = item
+            .get_from_str("propertyPath")
+            .and_then(|path| path.as_str())
+            == Some("m_Name");
+        if is_name && item.get_from_str("value").and_then(|value| value.as_str()) == Some(name) {
+            set_value(item, "value", Yaml::String(new_name.to_owned()));
+        }
+    }
+}
+
+/// Copies are named like in the editor, `Name (1)`, `Name (2)` and so on
+fn get_copy_name(name: &str, sibling_names: &HashSet<String>) -> String {
+    let base = match name.rsplit_once(" (") {
+        Some((base, number))
+            if number
+                .strip_suffix(')')
+                .is_some_and(|number| number.parse::<u32>().is_ok()) =>
+        {
+            base
+        }
+        _ => name,
+    };
+    (1..)
+        .map(|number| format!("{} ({})", base, number))
+        .find(|name| !sibling_names.contains(name))
+        .unwrap()
+}
+
+/// Points the references to objects in the file at their copies
+fn remap_references(yaml: &mut Yaml, new_ids: &HashMap<Id, Id>) {
+    match yaml {
+        Yaml::Hash(hash) => {
+            let new_id = hash
+                .get_from_str("fileID")
+                .filter(|_| hash.get_from_str("guid").is_none())
+                .and_then(|id| id.as_file_id())
+                .and_then(|id| new_ids.get(&id));
+            match new_id.and_then(|id| id.parse::<i64>().ok()) {
+                Some(new_id) => set_value(hash, "fileID", Yaml::Integer(new_id)),
+                None => hash
+                    .iter_mut()
+                    .for_each(|(_, value)| remap_references(value, new_ids)),
+            }
+        }
+        Yaml::Array(items) => items
+            .iter_mut()
+            .for_each(|item| remap_references(item, new_ids)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unity::{
+        object::Field,
+        template,
+        test_util::{Edited, SCENE},
+    };
+
+    fn set_component(before: Component, after: Component) -> Vec<Operation> {
+        vec![Operation::set(
+            Object::Component(before),
+            Object::Component(after),
+        )]
+    }
+
+    #[test]
+    fn edited_field_changes_its_line_and_is_undone() {
+        let mut edited = Edited::open(SCENE);
+        let before = edited.component("102");
+        let mut after = before.clone();
+        let Component::MonoBehaviour(mono) = &mut after else { panic!("expected a MonoBehaviour"); };
+        mono.fields.insert("speed".to_owned(), Field::F64(3.25));
+        let operations = set_component(before, after);
+
+        edited.apply(&operations);
+        assert_eq!(edited.text(), SCENE.replace("speed: 1.5", "speed: 3.25"));
+        let Component::MonoBehaviour(mono) = edited.component("102") else { panic!("expected a MonoBehaviour"); };
+        assert!(matches!(mono.fields.get("speed"), Some(Field::F64(speed)) if *speed == 3.25));
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+    }
+
+    #[test]
+    fn edited_transform_only_changes_the_edited_value() {
+        let mut edited = Edited::open(SCENE);
+        let before = edited.component("121");
+        let mut after = before.clone();
+        let Component::Transform(Transform::Transform3D(transform)) = &mut after else { panic!("expected a Transform"); };
+        transform.local_position.z = -5.0;
+        let operations = set_component(before, after);
+
+        edited.apply(&operations);
+        assert_eq!(
+            edited.text(),
+            SCENE.replace("{x: 0, y: 1, z: -10}", "{x: 0, y: 1, z: -5}")
+        );
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+    }
+
+    /// The scene the way newer versions of Unity write it, the order of the roots is in SceneRoots
+    fn scene_with_scene_roots() -> String {
+        let mut lines = SCENE
+            .lines()
+            .filter(|line| !line.starts_with("  m_RootOrder:"))
+            .collect::<Vec<&str>>();
+        lines.extend([
+            "--- !u!1660057539 &9223372036854775807",
+            "SceneRoots:",
+            "  m_ObjectHideFlags: 0",
+            "  m_Roots:",
+            "  - {fileID: 101}",
+            "  - {fileID: 121}",
+        ]);
+        lines.join("\n") + "\n"
+    }
+
+    fn scene_roots(edited: &Edited) -> Vec<&str> {
+        edited
+            .repo
+            .get_scene_roots()
+            .unwrap()
+            .root_ids
+            .iter()
+            .map(|id| id.as_str())
+            .collect()
+    }
+
+    fn transform_id(edited: &Edited, game_object_id: &Id) -> Id {
+        get_game_object_transform(&edited.repo, game_object_id)
+            .unwrap()
+            .get_id()
+            .clone()
+    }
+
+    fn root_names(edited: &Edited) -> Vec<String> {
+        get_hierarchy_siblings(&edited.file, &edited.repo, None)
+            .iter()
+            .map(|id| edited.repo.get_game_object(id).unwrap().name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn created_game_object_is_added_to_its_parent_and_undone() {
+        let mut edited = Edited::open(SCENE);
+        let mut ids = FileIdGenerator::new(&edited.file, &edited.repo);
+        let (id, operations) = create_game_object(
+            &edited.file,
+            &edited.repo,
+            &mut ids,
+            Some(&"101".to_owned()),
+            None,
+        )
+        .unwrap();
+
+        edited.apply(&operations);
+        let transform_id = transform_id(&edited, &id);
+        let parent = edited.repo.get_transform(&"101".to_owned()).unwrap();
+        assert_eq!(
+            parent.get_children_ids(),
+            &["111".to_owned(), transform_id.clone()]
+        );
+        let transform = edited.repo.get_transform(&transform_id).unwrap();
+        assert_eq!(transform.get_father_id(), "101");
+        assert_eq!(transform.get_root_order(), 1);
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+        assert!(edited.repo.get_game_object(&id).is_none());
+    }
+
+    #[test]
+    fn deleted_game_object_takes_its_children_and_components_along() {
+        let mut edited = Edited::open(SCENE);
+        let operations = delete_game_object(&edited.file, &edited.repo, &"100".to_owned());
+
+        edited.apply(&operations);
+        let ids = edited
+            .file
+            .documents
+            .iter()
+            .map(|doc| doc.id.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, ["120", "121"]);
+        assert_eq!(root_names(&edited), ["Camera"]);
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+        assert_eq!(root_names(&edited), ["Player", "Camera"]);
+    }
+
+    #[test]
+    fn duplicated_game_object_gets_a_new_name_next_to_the_original() {
+        let mut edited = Edited::open(SCENE);
+        let mut ids = FileIdGenerator::new(&edited.file, &edited.repo);
+        let (copy_id, operations) =
+            duplicate_game_object(&edited.file, &edited.repo, &mut ids, &"110".to_owned()).unwrap();
+
+        edited.apply(&operations);
+        assert_eq!(
+            edited.repo.get_game_object(&copy_id).unwrap().name,
+            "Weapon (1)"
+        );
+        let copy_transform_id = transform_id(&edited, &copy_id);
+        let parent = edited.repo.get_transform(&"101".to_owned()).unwrap();
+        assert_eq!(
+            parent.get_children_ids(),
+            &["111".to_owned(), copy_transform_id.clone()]
+        );
+        let copy_transform = edited.repo.get_transform(&copy_transform_id).unwrap();
+        assert_eq!(copy_transform.get_father_id(), "101");
+        assert_eq!(copy_transform.get_root_order(), 1);
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+    }
+
+    #[test]
+    fn renamed_game_object_is_undone() {
+        let mut edited = Edited::open(SCENE);
+        let operations =
+            rename_game_object(&edited.repo, &"120".to_owned(), "Main Camera").unwrap();
+
+        edited.apply(&operations);
+        assert_eq!(
+            edited.text(),
+            SCENE.replace("m_Name: Camera", "m_Name: Main Camera")
+        );
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+    }
+
+    #[test]
+    fn scene_roots_follow_created_deleted_and_duplicated_roots() {
+        let scene = scene_with_scene_roots();
+        let mut edited = Edited::open(&scene);
+        let mut ids = FileIdGenerator::new(&edited.file, &edited.repo);
+
+        let (id, created) =
+            create_game_object(&edited.file, &edited.repo, &mut ids, None, None).unwrap();
+        edited.apply(&created);
+        let created_transform_id = transform_id(&edited, &id);
+        assert_eq!(
+            scene_roots(&edited),
+            ["101", "121", created_transform_id.as_str()]
+        );
+        assert_eq!(root_names(&edited), ["Player", "Camera", "GameObject"]);
+
+        let (copy_id, duplicated) =
+            duplicate_game_object(&edited.file, &edited.repo, &mut ids, &"100".to_owned()).unwrap();
+        edited.apply(&duplicated);
+        let copy_transform_id = transform_id(&edited, &copy_id);
+        assert_eq!(
+            scene_roots(&edited),
+            [
+                "101",
+                "121",
+                created_transform_id.as_str(),
+                copy_transform_id.as_str()
+            ]
+        );
+
+        let deleted = delete_game_object(&edited.file, &edited.repo, &"100".to_owned());
+        edited.apply(&deleted);
+        assert_eq!(
+            scene_roots(&edited),
+            [
+                "121",
+                created_transform_id.as_str(),
+                copy_transform_id.as_str()
+            ]
+        );
+        assert_eq!(root_names(&edited), ["Camera", "GameObject", "Player (1)"]);
+
+        edited.undo(&deleted);
+        edited.undo(&duplicated);
+        edited.undo(&created);
+        assert_eq!(edited.text(), scene);
+        assert_eq!(scene_roots(&edited), ["101", "121"]);
+    }
+
+    #[test]
+    fn reordered_roots_get_their_root_order_numbered_again() {
+        let mut edited = Edited::open(SCENE);
+        let operations =
+            move_game_object(&edited.file, &edited.repo, &"120".to_owned(), None, 0).unwrap();
+
+        edited.apply(&operations);
+        assert_eq!(root_names(&edited), ["Camera", "Player"]);
+        // Player has Weapon as a child, Camera has none
+        let player = "  - {fileID: 111}\n  m_Father: {fileID: 0}\n  m_RootOrder: ";
+        let camera = "  m_Children: []\n  m_Father: {fileID: 0}\n  m_RootOrder: ";
+        assert_eq!(SCENE.matches(player).count(), 1);
+        assert_eq!(SCENE.matches(camera).count(), 1);
+        let expected = SCENE
+            .replace(&format!("{}0", player), &format!("{}1", player))
+            .replace(&format!("{}1", camera), &format!("{}0", camera));
+        assert_eq!(edited.text(), expected);
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+        assert_eq!(root_names(&edited), ["Player", "Camera"]);
+    }
+
+    #[test]
+    fn reparented_game_object_leaves_its_old_parent() {
+        let mut edited = Edited::open(SCENE);
+        let operations =
+            move_game_object(&edited.file, &edited.repo, &"110".to_owned(), None, 1).unwrap();
+
+        edited.apply(&operations);
+        assert_eq!(root_names(&edited), ["Player", "Weapon", "Camera"]);
+        let old_parent = edited.repo.get_transform(&"101".to_owned()).unwrap();
+        assert!(old_parent.get_children_ids().is_empty());
+        let transform = edited.repo.get_transform(&"111".to_owned()).unwrap();
+        assert_eq!(transform.get_father_id(), "0");
+        assert_eq!(transform.get_root_order(), 1);
+        assert_eq!(
+            edited
+                .repo
+                .get_transform(&"121".to_owned())
+                .unwrap()
+                .get_root_order(),
+            2
+        );
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+    }
+
+    #[test]
+    fn game_object_cannot_be_moved_into_its_children() {
+        let edited = Edited::open(SCENE);
+        let result = move_game_object(
+            &edited.file,
+            &edited.repo,
+            &"100".to_owned(),
+            Some(&"111".to_owned()),
+            0,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn scene_roots_follow_moved_roots() {
+        let scene = scene_with_scene_roots();
+        let mut edited = Edited::open(&scene);
+
+        let reordered =
+            move_game_object(&edited.file, &edited.repo, &"120".to_owned(), None, 0).unwrap();
+        edited.apply(&reordered);
+        assert_eq!(scene_roots(&edited), ["121", "101"]);
+        assert_eq!(root_names(&edited), ["Camera", "Player"]);
+
+        let unparented =
+            move_game_object(&edited.file, &edited.repo, &"110".to_owned(), None, 1).unwrap();
+        edited.apply(&unparented);
+        assert_eq!(scene_roots(&edited), ["121", "111", "101"]);
+
+        let parented = move_game_object(
+            &edited.file,
+            &edited.repo,
+            &"120".to_owned(),
+            Some(&"101".to_owned()),
+            0,
+        )
+        .unwrap();
+        edited.apply(&parented);
+        assert_eq!(scene_roots(&edited), ["111", "101"]);
+        assert_eq!(root_names(&edited), ["Weapon", "Player"]);
+
+        edited.undo(&parented);
+        edited.undo(&unparented);
+        edited.undo(&reordered);
+        assert_eq!(edited.text(), scene);
+    }
+
+    fn component_ids(edited: &Edited, game_object_id: &str) -> Vec<Id> {
+        edited
+            .repo
+            .get_game_object(&game_object_id.to_owned())
+            .unwrap()
+            .component_ids
+            .clone()
+    }
+
+    #[test]
+    fn added_component_is_listed_on_its_game_object_and_undone() {
+        let mut edited = Edited::open(SCENE);
+        let mut ids = FileIdGenerator::new(&edited.file, &edited.repo);
+        let id = ids.next_id();
+        let document =
+            template::new_built_in_component("BoxCollider", &id, &"120".to_owned()).unwrap();
+        let (added_id, operations) =
+            add_component(&edited.file, &edited.repo, &"120".to_owned(), document).unwrap();
+        assert_eq!(added_id, id);
+
+        edited.apply(&operations);
+        assert_eq!(
+            component_ids(&edited, "120"),
+            ["121".to_owned(), id.clone()]
+        );
+        assert_eq!(edited.component(&id).get_game_object_id(), "120");
+        let text = edited.text();
+        assert!(text.contains(&format!("  - component: {{fileID: {}}}\n", id)));
+        assert_eq!(edited.file.documents.last().unwrap().id, id);
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+        assert!(edited.repo.get_component(&id).is_none());
+    }
+
+    #[test]
+    fn removed_component_leaves_its_game_object_and_is_undone() {
+        let mut edited = Edited::open(SCENE);
+        let operations = remove_component(&edited.file, &edited.repo, &"102".to_owned()).unwrap();
+
+        edited.apply(&operations);
+        assert_eq!(component_ids(&edited, "100"), ["101"]);
+        assert!(edited.file.documents.iter().all(|doc| doc.id != "102"));
+        assert!(edited.repo.get_component(&"102".to_owned()).is_none());
+
+        edited.undo(&operations);
+        assert_eq!(edited.text(), SCENE);
+        assert_eq!(component_ids(&edited, "100"), ["101", "102"]);
+    }
+
+    #[test]
+    fn transform_is_not_removed_without_its_game_object() {
+        let edited = Edited::open(SCENE);
+
+        assert!(remove_component(&edited.file, &edited.repo, &"101".to_owned()).is_none());
+    }
+
+    #[test]
+    fn generated_ids_are_not_in_the_file() {
+        let edited = Edited::open(SCENE);
+        let mut ids = FileIdGenerator::new(&edited.file, &edited.repo);
+        let generated = (0..1000).map(|_| ids.next_id()).collect::<HashSet<Id>>();
+
+        assert_eq!(generated.len(), 1000);
+        assert!(generated
+            .iter()
+            .all(|id| edited.repo.get(id).is_none() && id.parse::<i64>().unwrap() > 0));
+    }
+}
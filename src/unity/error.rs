@@ -0,0 +1,104 @@
+use crate::unity::Id;
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+use unity_yaml_rust::ScanError;
+
+/// A problem found while reading a Unity file or a meta file, with where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub message: String,
+    /// None when the text did not come from a file
+    pub path: Option<PathBuf>,
+    /// The fileID of the document the problem is in
+    pub file_id: Option<Id>,
+    pub class_name: Option<String>,
+    /// The line of the file, counted from 1
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The file could not be read from the disk
+    Io,
+    /// The text is not valid YAML, nothing in the file could be read
+    Syntax,
+    /// The `--- !u!<class id> &<file id>` line of a document could not be read
+    Header,
+    /// A document is missing a field or has a value of an unexpected type
+    Document,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, message: impl Into<String>) -> Self {
+        ParseError {
+            kind,
+            message: message.into(),
+            path: None,
+            file_id: None,
+            class_name: None,
+            line: None,
+        }
+    }
+
+    /// The YAML parser adds the position to its message, it is kept in `line` instead
+    pub fn from_scan_error(error: &ScanError) -> Self {
+        let message = error.to_string();
+        let message = match message.rfind(" at line ") {
+            Some(idx) => message[..idx].to_owned(),
+            None => message,
+        };
+        ParseError::new(ParseErrorKind::Syntax, message).with_line(Some(error.marker().line()))
+    }
+
+    pub fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_path_buf());
+        self
+    }
+
+    pub fn with_document(mut self, file_id: &Id, class_name: &str) -> Self {
+        self.file_id = Some(file_id.clone());
+        self.class_name = Some(class_name.to_owned());
+        self
+    }
+
+    pub fn with_line(mut self, line: Option<usize>) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+/// Written like `Assets/Main.unity:12: Transform &4: message`, leaving out what is not known
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}:", path.display())?;
+            if let Some(line) = self.line {
+                write!(f, "{}:", line)?;
+            }
+            write!(f, " ")?;
+        } else if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        match (&self.class_name, &self.file_id) {
+            (Some(class_name), Some(file_id)) => write!(f, "{} &{}: ", class_name, file_id)?,
+            (None, Some(file_id)) => write!(f, "&{}: ", file_id)?,
+            _ => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(error: ParseError) -> Self {
+        let kind = match error.kind {
+            ParseErrorKind::Io => io::ErrorKind::Other,
+            _ => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, error)
+    }
+}
@@ -1,7 +1,20 @@
+pub mod asset;
 mod converters;
+pub mod edit;
+pub mod error;
 pub mod object;
+pub mod overrides;
+pub mod prefab;
+pub mod references;
 pub mod repository;
+pub mod script;
+pub mod template;
+#[cfg(test)]
+pub mod test_util;
+pub mod usages;
 pub mod vector;
+pub mod version;
+pub mod writer;
 pub mod yaml;
 
 pub type Id = String;
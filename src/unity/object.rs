@@ -1,10 +1,11 @@
 use super::{
-    vector::{Quaternion, Vector2, Vector3, Vector4},
+    vector::{Color, Quaternion, Vector2, Vector3, Vector4},
     Id,
 };
+use crate::unity::references::missing_script_name;
 use crate::unity::repository::MetaFilesRepository;
 use crate::unity::Guid;
-use std::{cmp::Ordering, collections::HashMap};
+use linked_hash_map::LinkedHashMap;
 use unity_yaml_rust::Yaml;
 
 pub trait GetId {
@@ -15,6 +16,9 @@ pub trait GetId {
 pub enum Object {
     GameObject(GameObject),
     Component(Component),
+    PrefabInstance(PrefabInstance),
+    Stripped(StrippedObject),
+    SceneRoots(SceneRoots),
 }
 
 impl GetId for Object {
@@ -22,6 +26,9 @@ impl GetId for Object {
         match self {
             Object::GameObject(go) => go.get_id(),
             Object::Component(c) => c.get_id(),
+            Object::PrefabInstance(p) => p.get_id(),
+            Object::Stripped(s) => s.get_id(),
+            Object::SceneRoots(r) => r.get_id(),
         }
     }
 }
@@ -34,7 +41,6 @@ pub struct GameObject {
     pub active: bool,
     pub layer: u8,
     pub tag: String,
-    pub transform_id: Id,
 }
 
 impl GetId for GameObject {
@@ -48,7 +54,7 @@ pub struct MonoBehaviour {
     pub id: String,
     // pub name: String, // this needs to be read from a meta file
     pub enabled: bool,
-    pub fields: HashMap<String, Field>,
+    pub fields: LinkedHashMap<String, Field>,
     pub game_object_id: Id,
     pub script_guid: Guid,
 }
@@ -63,6 +69,14 @@ impl GetId for MonoBehaviour {
 pub enum Component {
     MonoBehaviour(MonoBehaviour),
     Transform(Transform),
+    Camera(Camera),
+    Light(Light),
+    MeshFilter(MeshFilter),
+    Renderer(Renderer),
+    Collider(Collider),
+    Rigidbody(Rigidbody),
+    Canvas(Canvas),
+    Generic(GenericComponent),
 }
 
 impl GetId for Component {
@@ -70,16 +84,43 @@ impl GetId for Component {
         match self {
             Component::MonoBehaviour(c) => c.get_id(),
             Component::Transform(c) => c.get_id(),
+            Component::Camera(c) => c.get_id(),
+            Component::Light(c) => c.get_id(),
+            Component::MeshFilter(c) => c.get_id(),
+            Component::Renderer(c) => c.get_id(),
+            Component::Collider(c) => c.get_id(),
+            Component::Rigidbody(c) => c.get_id(),
+            Component::Canvas(c) => c.get_id(),
+            Component::Generic(c) => c.get_id(),
         }
     }
 }
 
 impl Component {
-    /// Returns None for components that do not have the name stored in them, for example MonoBehaviours
-    pub fn get_name(&self, meta_files: &MetaFilesRepository) -> Option<String> {
+    /// MonoBehaviours are named after their script, `Missing Script (guid …)` if it is not in the
+    /// project
+    pub fn get_name(&self, meta_files: &MetaFilesRepository) -> String {
         match self {
-            Component::MonoBehaviour(m) => meta_files.get(&m.script_guid).cloned(),
-            Component::Transform(t) => Some(t.get_name()),
+            Component::MonoBehaviour(m) => match meta_files.get_name(&m.script_guid) {
+                Some(name) => name.to_owned(),
+                None => missing_script_name(&m.script_guid),
+            },
+            Component::Transform(t) => t.get_name(),
+            Component::Camera(_) => "Camera".to_owned(),
+            Component::Light(_) => "Light".to_owned(),
+            Component::MeshFilter(_) => "MeshFilter".to_owned(),
+            Component::Renderer(r) => r.get_name(),
+            Component::Collider(c) => c.get_name(),
+            Component::Rigidbody(r) => r.get_name(),
+            Component::Canvas(_) => "Canvas".to_owned(),
+            Component::Generic(g) => g.class_name.clone(),
+        }
+    }
+
+    pub fn has_missing_script(&self, meta_files: &MetaFilesRepository) -> bool {
+        match self {
+            Component::MonoBehaviour(m) => meta_files.get(&m.script_guid).is_none(),
+            _ => false,
         }
     }
 
@@ -87,10 +128,50 @@ impl Component {
         match self {
             Component::MonoBehaviour(m) => &m.game_object_id,
             Component::Transform(t) => t.get_game_object_id(),
+            Component::Camera(c) => &c.game_object_id,
+            Component::Light(l) => &l.game_object_id,
+            Component::MeshFilter(m) => &m.game_object_id,
+            Component::Renderer(r) => r.get_game_object_id(),
+            Component::Collider(c) => c.get_game_object_id(),
+            Component::Rigidbody(r) => r.get_game_object_id(),
+            Component::Canvas(c) => &c.game_object_id,
+            Component::Generic(g) => &g.game_object_id,
+        }
+    }
+
+    /// Components that cannot be disabled in the editor, like Transforms, are always enabled
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Component::MonoBehaviour(m) => m.enabled,
+            Component::Transform(_) => true,
+            Component::Camera(c) => c.enabled,
+            Component::Light(l) => l.enabled,
+            Component::MeshFilter(_) => true,
+            Component::Renderer(r) => r.is_enabled(),
+            Component::Collider(c) => c.is_enabled(),
+            Component::Rigidbody(_) => true,
+            Component::Canvas(c) => c.enabled,
+            Component::Generic(g) => g.enabled.unwrap_or(true),
         }
     }
 }
 
+/// A reference to another object, either in the same file (no guid) or in another asset
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectReference {
+    pub file_id: Id,
+    pub guid: Option<Guid>,
+    /// How the asset is stored, 2 for assets with Unity YAML and 3 for the ones imported from another
+    /// format, like scripts and models. None for references in the same file
+    pub asset_type: Option<i64>,
+}
+
+impl ObjectReference {
+    pub fn is_null(&self) -> bool {
+        self.file_id == "0"
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Transform3D {
     pub id: Id,
@@ -176,6 +257,27 @@ impl Transform {
         }
     }
 
+    pub fn get_local_position(&self) -> &Vector3 {
+        match self {
+            Transform::Transform3D(t) => &t.local_position,
+            Transform::RectTransform(t) => &t.local_position,
+        }
+    }
+
+    pub fn get_local_rotation(&self) -> &Quaternion {
+        match self {
+            Transform::Transform3D(t) => &t.local_rotation,
+            Transform::RectTransform(t) => &t.local_rotation,
+        }
+    }
+
+    pub fn get_local_scale(&self) -> &Vector3 {
+        match self {
+            Transform::Transform3D(t) => &t.local_scale,
+            Transform::RectTransform(t) => &t.local_scale,
+        }
+    }
+
     pub fn has_parent(&self) -> bool {
         self.get_father_id() != "0"
     }
@@ -186,11 +288,587 @@ impl Transform {
             Transform::RectTransform(t) => t.root_order,
         }
     }
+}
+
+/// Where a transform ends up in the scene after applying the transforms of all its parents
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorldTransform {
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    /// The scales of the parents multiplied together, like Unity's `lossyScale` it is not exact
+    /// when a parent is rotated and scaled unevenly
+    pub lossy_scale: Vector3,
+}
+
+impl Default for WorldTransform {
+    fn default() -> Self {
+        WorldTransform {
+            position: Vector3::default(),
+            rotation: Quaternion::IDENTITY,
+            lossy_scale: Vector3::ONE,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Camera {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub clear_flags: i64,
+    pub background_color: Color,
+    pub orthographic: bool,
+    pub orthographic_size: f32,
+    pub field_of_view: f32,
+    pub near_clip_plane: f32,
+    pub far_clip_plane: f32,
+    pub depth: f32,
+    pub culling_mask: i64,
+    pub target_display: i64,
+}
+
+impl GetId for Camera {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Light {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub light_type: i64,
+    pub color: Color,
+    pub intensity: f32,
+    pub range: f32,
+    pub spot_angle: f32,
+    pub shadows_type: i64,
+}
+
+impl GetId for Light {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MeshFilter {
+    pub id: Id,
+    pub game_object_id: Id,
+    pub mesh: ObjectReference,
+}
+
+impl GetId for MeshFilter {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MeshRenderer {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub cast_shadows: i64,
+    pub receive_shadows: bool,
+    pub materials: Vec<ObjectReference>,
+}
+
+impl GetId for MeshRenderer {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SkinnedMeshRenderer {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub cast_shadows: i64,
+    pub receive_shadows: bool,
+    pub materials: Vec<ObjectReference>,
+    pub mesh: ObjectReference,
+    pub root_bone: ObjectReference,
+    pub update_when_offscreen: bool,
+}
+
+impl GetId for SkinnedMeshRenderer {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpriteRenderer {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub materials: Vec<ObjectReference>,
+    pub sprite: ObjectReference,
+    pub color: Color,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub sorting_order: i64,
+}
+
+impl GetId for SpriteRenderer {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Renderer {
+    Mesh(MeshRenderer),
+    SkinnedMesh(SkinnedMeshRenderer),
+    Sprite(SpriteRenderer),
+}
+
+impl GetId for Renderer {
+    fn get_id(&self) -> &Id {
+        match self {
+            Renderer::Mesh(r) => r.get_id(),
+            Renderer::SkinnedMesh(r) => r.get_id(),
+            Renderer::Sprite(r) => r.get_id(),
+        }
+    }
+}
+
+impl Renderer {
+    pub fn get_name(&self) -> String {
+        match self {
+            Renderer::Mesh(_) => "MeshRenderer".to_owned(),
+            Renderer::SkinnedMesh(_) => "SkinnedMeshRenderer".to_owned(),
+            Renderer::Sprite(_) => "SpriteRenderer".to_owned(),
+        }
+    }
+
+    pub fn get_game_object_id(&self) -> &Id {
+        match self {
+            Renderer::Mesh(r) => &r.game_object_id,
+            Renderer::SkinnedMesh(r) => &r.game_object_id,
+            Renderer::Sprite(r) => &r.game_object_id,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Renderer::Mesh(r) => r.enabled,
+            Renderer::SkinnedMesh(r) => r.enabled,
+            Renderer::Sprite(r) => r.enabled,
+        }
+    }
+
+    pub fn get_materials(&self) -> &Vec<ObjectReference> {
+        match self {
+            Renderer::Mesh(r) => &r.materials,
+            Renderer::SkinnedMesh(r) => &r.materials,
+            Renderer::Sprite(r) => &r.materials,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BoxCollider {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub is_trigger: bool,
+    pub material: ObjectReference,
+    pub center: Vector3,
+    pub size: Vector3,
+}
+
+impl GetId for BoxCollider {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SphereCollider {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub is_trigger: bool,
+    pub material: ObjectReference,
+    pub center: Vector3,
+    pub radius: f32,
+}
+
+impl GetId for SphereCollider {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CapsuleCollider {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub is_trigger: bool,
+    pub material: ObjectReference,
+    pub center: Vector3,
+    pub radius: f32,
+    pub height: f32,
+    pub direction: i64,
+}
+
+impl GetId for CapsuleCollider {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MeshCollider {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub is_trigger: bool,
+    pub material: ObjectReference,
+    pub convex: bool,
+    pub mesh: ObjectReference,
+}
+
+impl GetId for MeshCollider {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BoxCollider2D {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub is_trigger: bool,
+    pub material: ObjectReference,
+    pub offset: Vector2,
+    pub size: Vector2,
+}
+
+impl GetId for BoxCollider2D {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CircleCollider2D {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub is_trigger: bool,
+    pub material: ObjectReference,
+    pub offset: Vector2,
+    pub radius: f32,
+}
+
+impl GetId for CircleCollider2D {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Collider {
+    BoxCollider(BoxCollider),
+    SphereCollider(SphereCollider),
+    CapsuleCollider(CapsuleCollider),
+    MeshCollider(MeshCollider),
+    BoxCollider2D(BoxCollider2D),
+    CircleCollider2D(CircleCollider2D),
+}
+
+impl GetId for Collider {
+    fn get_id(&self) -> &Id {
+        match self {
+            Collider::BoxCollider(c) => c.get_id(),
+            Collider::SphereCollider(c) => c.get_id(),
+            Collider::CapsuleCollider(c) => c.get_id(),
+            Collider::MeshCollider(c) => c.get_id(),
+            Collider::BoxCollider2D(c) => c.get_id(),
+            Collider::CircleCollider2D(c) => c.get_id(),
+        }
+    }
+}
 
-    pub fn partial_cmp_by_root_order(&self, other: &Transform) -> Ordering {
-        self.get_root_order()
-            .partial_cmp(&other.get_root_order())
-            .unwrap()
+impl Collider {
+    pub fn get_name(&self) -> String {
+        match self {
+            Collider::BoxCollider(_) => "BoxCollider".to_owned(),
+            Collider::SphereCollider(_) => "SphereCollider".to_owned(),
+            Collider::CapsuleCollider(_) => "CapsuleCollider".to_owned(),
+            Collider::MeshCollider(_) => "MeshCollider".to_owned(),
+            Collider::BoxCollider2D(_) => "BoxCollider2D".to_owned(),
+            Collider::CircleCollider2D(_) => "CircleCollider2D".to_owned(),
+        }
+    }
+
+    pub fn get_game_object_id(&self) -> &Id {
+        match self {
+            Collider::BoxCollider(c) => &c.game_object_id,
+            Collider::SphereCollider(c) => &c.game_object_id,
+            Collider::CapsuleCollider(c) => &c.game_object_id,
+            Collider::MeshCollider(c) => &c.game_object_id,
+            Collider::BoxCollider2D(c) => &c.game_object_id,
+            Collider::CircleCollider2D(c) => &c.game_object_id,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Collider::BoxCollider(c) => c.enabled,
+            Collider::SphereCollider(c) => c.enabled,
+            Collider::CapsuleCollider(c) => c.enabled,
+            Collider::MeshCollider(c) => c.enabled,
+            Collider::BoxCollider2D(c) => c.enabled,
+            Collider::CircleCollider2D(c) => c.enabled,
+        }
+    }
+
+    pub fn is_trigger(&self) -> bool {
+        match self {
+            Collider::BoxCollider(c) => c.is_trigger,
+            Collider::SphereCollider(c) => c.is_trigger,
+            Collider::CapsuleCollider(c) => c.is_trigger,
+            Collider::MeshCollider(c) => c.is_trigger,
+            Collider::BoxCollider2D(c) => c.is_trigger,
+            Collider::CircleCollider2D(c) => c.is_trigger,
+        }
+    }
+
+    pub fn get_material(&self) -> &ObjectReference {
+        match self {
+            Collider::BoxCollider(c) => &c.material,
+            Collider::SphereCollider(c) => &c.material,
+            Collider::CapsuleCollider(c) => &c.material,
+            Collider::MeshCollider(c) => &c.material,
+            Collider::BoxCollider2D(c) => &c.material,
+            Collider::CircleCollider2D(c) => &c.material,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rigidbody3D {
+    pub id: Id,
+    pub game_object_id: Id,
+    pub mass: f32,
+    pub drag: f32,
+    pub angular_drag: f32,
+    pub use_gravity: bool,
+    pub is_kinematic: bool,
+    pub interpolate: i64,
+    pub constraints: i64,
+    pub collision_detection: i64,
+}
+
+impl GetId for Rigidbody3D {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rigidbody2D {
+    pub id: Id,
+    pub game_object_id: Id,
+    pub body_type: i64,
+    pub simulated: bool,
+    pub mass: f32,
+    pub linear_drag: f32,
+    pub angular_drag: f32,
+    pub gravity_scale: f32,
+    pub interpolate: i64,
+    pub constraints: i64,
+    pub collision_detection: i64,
+}
+
+impl GetId for Rigidbody2D {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Rigidbody {
+    Rigidbody3D(Rigidbody3D),
+    Rigidbody2D(Rigidbody2D),
+}
+
+impl GetId for Rigidbody {
+    fn get_id(&self) -> &Id {
+        match self {
+            Rigidbody::Rigidbody3D(r) => r.get_id(),
+            Rigidbody::Rigidbody2D(r) => r.get_id(),
+        }
+    }
+}
+
+impl Rigidbody {
+    pub fn get_name(&self) -> String {
+        match self {
+            Rigidbody::Rigidbody3D(_) => "Rigidbody".to_owned(),
+            Rigidbody::Rigidbody2D(_) => "Rigidbody2D".to_owned(),
+        }
+    }
+
+    pub fn get_game_object_id(&self) -> &Id {
+        match self {
+            Rigidbody::Rigidbody3D(r) => &r.game_object_id,
+            Rigidbody::Rigidbody2D(r) => &r.game_object_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    pub id: Id,
+    pub enabled: bool,
+    pub game_object_id: Id,
+    pub render_mode: i64,
+    pub camera: ObjectReference,
+    pub plane_distance: f32,
+    pub pixel_perfect: bool,
+    pub sorting_order: i64,
+    pub target_display: i64,
+}
+
+impl GetId for Canvas {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+/// A single property override of a prefab instance
+#[derive(Debug, Clone)]
+pub struct PropertyModification {
+    pub target: ObjectReference,
+    pub property_path: String,
+    pub value: Yaml,
+    pub object_reference: ObjectReference,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefabInstance {
+    pub id: Id,
+    pub source_prefab: ObjectReference,
+    /// Id of the Transform the instance root is parented to, "0" for root objects
+    pub transform_parent_id: Id,
+    pub modifications: Vec<PropertyModification>,
+    pub removed_components: Vec<ObjectReference>,
+    /// Older versions of Unity cannot remove game objects from an instance
+    pub removed_game_objects: Vec<ObjectReference>,
+}
+
+impl GetId for PrefabInstance {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+impl PrefabInstance {
+    pub fn has_parent(&self) -> bool {
+        self.transform_parent_id != "0"
+    }
+
+    /// Without the source prefab loaded, the name comes from the m_Name override or the prefab file
+    /// name
+    pub fn get_name(&self, meta_files: &MetaFilesRepository) -> String {
+        if let Some(name) = self
+            .get_modification("m_Name")
+            .and_then(|m| m.value.as_str())
+        {
+            name.to_owned()
+        } else if let Some(name) = self
+            .source_prefab
+            .guid
+            .as_ref()
+            .and_then(|guid| meta_files.get_name(guid))
+        {
+            name.to_owned()
+        } else {
+            "<Missing Prefab>".to_owned()
+        }
+    }
+
+    /// Returns the value of the first modification of the given property, regardless of its target
+    pub fn get_modification(&self, property_path: &str) -> Option<&PropertyModification> {
+        self.modifications
+            .iter()
+            .find(|m| m.property_path == property_path)
+    }
+
+    /// Returns the m_RootOrder override of the root transform, `root_transform` is the object of
+    /// the source prefab it comes from. Every transform has an m_RootOrder, so when the root is
+    /// not known an override is only taken if all of them have the same target
+    pub fn get_root_order(&self, root_transform: Option<&ObjectReference>) -> i64 {
+        let mut root_orders = self
+            .modifications
+            .iter()
+            .filter(|m| m.property_path == "m_RootOrder");
+        let modification = match root_transform {
+            Some(root) => {
+                root_orders.find(|m| m.target.file_id == root.file_id && m.target.guid == root.guid)
+            }
+            None => root_orders
+                .next()
+                .filter(|first| root_orders.all(|m| m.target.file_id == first.target.file_id)),
+        };
+        modification
+            .and_then(|m| m.value.as_i64())
+            .unwrap_or_default()
+    }
+}
+
+/// An object that belongs to a prefab instance. Only the links to the instance and the source object are stored in the file
+#[derive(Debug, Clone)]
+pub struct StrippedObject {
+    pub id: Id,
+    pub class_name: String,
+    pub corresponding_source: ObjectReference,
+    pub prefab_instance_id: Id,
+}
+
+impl GetId for StrippedObject {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+/// The objects at the root of a scene in their order, written instead of m_RootOrder by newer
+/// versions of Unity. Game objects are listed by their transform, prefab instances by themselves
+#[derive(Debug, Clone)]
+pub struct SceneRoots {
+    pub id: Id,
+    pub root_ids: Vec<Id>,
+}
+
+impl GetId for SceneRoots {
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+}
+
+/// Any component without a dedicated model. Fields other than the common component ones are kept as they are
+#[derive(Debug, Clone)]
+pub struct GenericComponent {
+    pub id: Id,
+    pub class_name: String,
+    /// None for components that cannot be disabled
+    pub enabled: Option<bool>,
+    pub game_object_id: Id,
+    pub fields: LinkedHashMap<String, Field>,
+}
+
+impl GetId for GenericComponent {
+    fn get_id(&self) -> &Id {
+        &self.id
     }
 }
 
@@ -203,5 +881,10 @@ pub enum Field {
     I64(i64),
     Str(String),
     Bool(bool),
+    Color(Color),
+    /// A list of values, usually of the same kind
+    Array(Vec<Field>),
+    /// A reference to an object of the same file or to an asset
+    Reference(ObjectReference),
     Yaml(Yaml),
 }
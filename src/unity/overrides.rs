@@ -0,0 +1,115 @@
+use crate::unity::{
+    converters::GetFromStr,
+    edit::Operation,
+    object::PrefabInstance,
+    prefab::{self, parse_property_path, PathStep},
+    writer, yaml, Object,
+};
+use std::{
+    io::{Error, ErrorKind},
+    path::Path,
+};
+
+/// Overrides Unity keeps on the instance root when reverting everything
+const DEFAULT_OVERRIDES: [&str; 2] = ["m_Name", "m_RootOrder"];
+const DEFAULT_OVERRIDE_PREFIXES: [&str; 8] = [
+    "m_LocalPosition.",
+    "m_LocalRotation.",
+    "m_LocalEulerAnglesHint.",
+    "m_AnchoredPosition.",
+    "m_SizeDelta.",
+    "m_AnchorMin.",
+    "m_AnchorMax.",
+    "m_Pivot.",
+];
+
+/// Returns true if the property is overridden on every instance root and stays when reverting all
+pub fn is_default_override(property_path: &str) -> bool {
+    DEFAULT_OVERRIDES.contains(&property_path)
+        || DEFAULT_OVERRIDE_PREFIXES
+            .iter()
+            .any(|prefix| property_path.starts_with(prefix))
+}
+
+/// Returns the operation that removes the modifications with the given indices from the
+/// PrefabInstance
+pub fn revert_modifications(instance: &PrefabInstance, indices: &[usize]) -> Operation {
+    revert(instance, indices, false)
+}
+
+/// Returns the operation that removes the modifications with the given indices from the
+/// PrefabInstance and brings back the components and game objects it removed
+pub fn revert_all(instance: &PrefabInstance, indices: &[usize]) -> Operation {
+    revert(instance, indices, true)
+}
+
+fn revert(instance: &PrefabInstance, indices: &[usize], restore_removed: bool) -> Operation {
+    let mut after = instance.clone();
+    after.modifications = instance
+        .modifications
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !indices.contains(idx))
+        .map(|(_, m)| m.clone())
+        .collect();
+    if restore_removed {
+        after.removed_components.clear();
+        after.removed_game_objects.clear();
+    }
+    Operation::set(
+        Object::PrefabInstance(instance.clone()),
+        Object::PrefabInstance(after),
+    )
+}
+
+/// Writes the value of a modification into its target in the source prefab. The prefab is written
+/// right away, the modification is removed from the instance with [`revert_modifications`]
+pub fn apply_modification(
+    instance: &PrefabInstance,
+    index: usize,
+    prefab_path: &Path,
+) -> Result<(), Error> {
+    let modification = instance
+        .modifications
+        .get(index)
+        .ok_or_else(|| invalid_data(format!("override {} not found", index)))?;
+    let property_path = &modification.property_path;
+    let steps = parse_property_path(property_path)
+        .ok_or_else(|| invalid_data(format!("invalid property path {}", property_path)))?;
+    if steps.iter().any(|step| matches!(step, PathStep::ArraySize)) {
+        return Err(Error::other("changing array sizes is not supported"));
+    }
+
+    let mut prefab =
+        yaml::parse_unity_file(prefab_path).map_err(|e| invalid_data(e.to_string()))?;
+    let target_id = &modification.target.file_id;
+    let document = prefab
+        .documents
+        .iter_mut()
+        .find(|doc| &doc.id == target_id)
+        .ok_or_else(|| invalid_data(format!("object {} not found", target_id)))?;
+    if document.stripped {
+        return Err(invalid_data(
+            "overrides of objects from nested prefabs cannot be applied".to_owned(),
+        ));
+    }
+    let body = prefab::body_yaml_mut(&mut document.document)
+        .ok_or_else(|| invalid_data(format!("object {} cannot be read", target_id)))?;
+    let current = prefab::get_property(body, &steps)
+        .ok_or_else(|| invalid_data(format!("{} not found in the source prefab", property_path)))?;
+    let is_reference = current
+        .as_hash()
+        .is_some_and(|hash| hash.get_from_str("fileID").is_some());
+    let reference = &modification.object_reference;
+    if is_reference && reference.guid.is_none() && !reference.is_null() {
+        return Err(invalid_data(
+            "references to objects outside of the prefab cannot be applied".to_owned(),
+        ));
+    }
+    prefab::apply_modification(body, modification);
+    writer::write_file(prefab_path, &prefab)
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,717 @@
+use crate::class_id::PREFAB_INSTANCE_CLASS_ID;
+use crate::unity::{
+    converters::{helpers::obj_to_reference, AsFileId, GetFromStr},
+    object::PropertyModification,
+    repository::MetaFilesRepository,
+    yaml::{self, YamlUnityDocument},
+    Guid, Id,
+};
+use crate::util::{hash_map, hash_set};
+use std::collections::{HashMap, HashSet};
+use unity_yaml_rust::{yaml::Hash, Yaml};
+
+/// Merges the contents of source prefabs into the documents of a scene or prefab file.
+///
+/// Every PrefabInstance whose source prefab can be found gets its objects copied in with the ids
+/// Unity gives them in the instantiating file, the modifications applied and the removed
+/// components dropped. The stripped documents of resolved instances are replaced by the merged
+/// objects, the PrefabInstance documents themselves are kept. Removed components stay in the
+/// documents, they are only no longer listed by their game objects. Instances whose source prefab is
+/// missing or which reference themselves are left as they are.
+pub fn resolve_prefab_instances(
+    docs: Vec<YamlUnityDocument>,
+    meta_files_repository: &MetaFilesRepository,
+) -> Vec<YamlUnityDocument> {
+    PrefabResolver {
+        meta_files_repository,
+        resolved: hash_map![],
+        in_progress: hash_set![],
+    }
+    .resolve(docs)
+}
+
+/// Computes the id an object of a source prefab gets in the file instantiating the prefab
+pub fn instantiated_id(instance_id: &Id, source_id: &Id) -> Option<Id> {
+    let instance_id = instance_id.parse::<i64>().ok()? as u64;
+    let source_id = source_id.parse::<i64>().ok()? as u64;
+    Some(((instance_id ^ source_id) & 0x7FFF_FFFF_FFFF_FFFF).to_string())
+}
+
+struct PrefabResolver<'a> {
+    meta_files_repository: &'a MetaFilesRepository,
+    /// resolved contents of already loaded prefabs, None if the prefab could not be loaded
+    resolved: HashMap<Guid, Option<Vec<YamlUnityDocument>>>,
+    /// guards against prefabs containing instances of themselves
+    in_progress: HashSet<Guid>,
+}
+
+impl PrefabResolver<'_> {
+    fn resolve(&mut self, mut docs: Vec<YamlUnityDocument>) -> Vec<YamlUnityDocument> {
+        let instances = docs
+            .iter()
+            .filter(|doc| doc.class_id == PREFAB_INSTANCE_CLASS_ID && !doc.stripped)
+            .filter_map(|doc| Some((doc.id.clone(), body(&doc.document)?.clone())))
+            .collect::<Vec<(Id, Hash)>>();
+
+        for (instance_id, instance) in instances {
+            let Some(merged) = self.instantiate(&instance_id, &instance) else { continue; };
+
+            docs.retain(|doc| {
+                !(doc.stripped
+                    && get_reference_id(doc, "m_PrefabInstance").as_ref() == Some(&instance_id))
+            });
+            let merged_ids = merged
+                .iter()
+                .map(|doc| doc.id.clone())
+                .collect::<HashSet<Id>>();
+            // the merged objects are inserted where the PrefabInstance document is
+            let position = docs
+                .iter()
+                .position(|doc| doc.id == instance_id)
+                .map_or(docs.len(), |idx| idx + 1);
+            docs.splice(position..position, merged);
+            link_added_objects(&mut docs, &merged_ids);
+        }
+
+        docs
+    }
+
+    /// Returns the objects of the source prefab as they appear in the instantiating file
+    fn instantiate(&mut self, instance_id: &Id, instance: &Hash) -> Option<Vec<YamlUnityDocument>> {
+        let source_prefab = obj_to_reference(instance.get_from_str("m_SourcePrefab")?.as_hash()?)?;
+        let guid = source_prefab.guid?;
+        let mut docs = self.load_prefab(&guid)?;
+        let modification = instance.get_from_str("m_Modification")?.as_hash()?;
+
+        // removed components are kept so they can still be inspected, they are only unlinked
+        // from their game objects, removed game objects are dropped with everything below them
+        let removed_game_objects = removed_game_object_ids(
+            &docs,
+            references_to_ids(modification.get_from_str("m_RemovedGameObjects")),
+        );
+        docs.retain(|doc| !removed_game_objects.contains(&doc.id));
+        let mut removed = references_to_ids(modification.get_from_str("m_RemovedComponents"));
+        removed.extend(removed_game_objects);
+
+        let father_ids = docs
+            .iter()
+            .filter_map(|doc| Some((doc.id.clone(), get_reference_id(doc, "m_Father")?)))
+            .collect::<HashMap<Id, Id>>();
+
+        for doc in docs.iter_mut() {
+            let source_id = doc.id.clone();
+            doc.id = instantiated_id(instance_id, &source_id)?;
+            let Some(body) = body_mut(&mut doc.document) else { continue; };
+
+            remove_references(body, "m_Component", &removed);
+            remove_references(body, "m_Children", &removed);
+            remap_local_ids(body, instance_id);
+            set_value(
+                body,
+                "m_CorrespondingSourceObject",
+                reference_to_yaml(&source_id, Some(&guid)),
+            );
+            set_value(
+                body,
+                "m_PrefabInstance",
+                reference_to_yaml(instance_id, None),
+            );
+            if father_ids.get(&source_id).is_some_and(|id| id == "0") {
+                set_value(
+                    body,
+                    "m_Father",
+                    modification
+                        .get_from_str("m_TransformParent")
+                        .cloned()
+                        .unwrap_or_else(|| reference_to_yaml(&"0".to_owned(), None)),
+                );
+            }
+        }
+
+        if let Some(Yaml::Array(modifications)) = modification.get_from_str("m_Modifications") {
+            for modification in modifications.iter().filter_map(modification_from_yaml) {
+                let Some(target_id) = instantiated_id(instance_id, &modification.target.file_id) else { continue; };
+                let Some(doc) = docs.iter_mut().find(|doc| doc.id == target_id) else { continue; };
+                if let Some(body) = body_yaml_mut(&mut doc.document) {
+                    apply_modification(body, &modification);
+                }
+            }
+        }
+
+        Some(docs)
+    }
+
+    /// Loads a prefab file with all the instances inside it resolved
+    fn load_prefab(&mut self, guid: &Guid) -> Option<Vec<YamlUnityDocument>> {
+        if let Some(docs) = self.resolved.get(guid) {
+            return docs.clone();
+        }
+        if !self.in_progress.insert(guid.clone()) {
+            return None;
+        }
+
+        let docs = self
+            .meta_files_repository
+            .get_path(guid)
+            .and_then(|path| yaml::parse_file(&path).ok())
+            .map(|docs| self.resolve(docs))
+            .map(|docs| {
+                // the PrefabInstance documents of nested prefabs and the components removed by
+                // them only matter in the prefab file
+                let removed = nested_removed_components(&docs);
+                docs.into_iter()
+                    .filter(|doc| doc.class_id != PREFAB_INSTANCE_CLASS_ID)
+                    .filter(|doc| {
+                        let instance_id = get_reference_id(doc, "m_PrefabInstance");
+                        let source_id = get_reference_id(doc, "m_CorrespondingSourceObject");
+                        !instance_id
+                            .zip(source_id)
+                            .is_some_and(|key| removed.contains(&key))
+                    })
+                    .collect::<Vec<YamlUnityDocument>>()
+            });
+
+        self.in_progress.remove(guid);
+        self.resolved.insert(guid.clone(), docs.clone());
+        docs
+    }
+}
+
+/// Objects added to a prefab instance in the instantiating file are not listed by the merged
+/// objects yet and neither is the merged root by its new parent, these are added to m_Component
+/// and m_Children of their game objects and parents
+fn link_added_objects(docs: &mut [YamlUnityDocument], merged_ids: &HashSet<Id>) {
+    let mut components = vec![];
+    let mut children = vec![];
+    for doc in docs.iter() {
+        let is_merged = merged_ids.contains(&doc.id);
+        if let Some(go_id) = get_reference_id(doc, "m_GameObject") {
+            if !is_merged && merged_ids.contains(&go_id) {
+                components.push((go_id, doc.id.clone()));
+            }
+        }
+        if let Some(father_id) = get_reference_id(doc, "m_Father") {
+            if is_merged != merged_ids.contains(&father_id) && father_id != "0" {
+                children.push((father_id, doc.id.clone()));
+            }
+        }
+    }
+
+    for (owner_id, id, key, wrap) in components
+        .into_iter()
+        .map(|(owner, id)| (owner, id, "m_Component", true))
+        .chain(
+            children
+                .into_iter()
+                .map(|(owner, id)| (owner, id, "m_Children", false)),
+        )
+    {
+        let Some(owner) = docs.iter_mut().find(|doc| doc.id == owner_id) else { continue; };
+        let Some(body) = body_mut(&mut owner.document) else { continue; };
+        let Some(Yaml::Array(list)) = body.get_mut(&Yaml::String(key.to_owned())) else { continue; };
+        if list
+            .iter()
+            .any(|item| list_item_id(item).as_ref() == Some(&id))
+        {
+            continue;
+        }
+        let reference = reference_to_yaml(&id, None);
+        list.push(if wrap {
+            let mut component = Hash::new(true);
+            component.insert(Yaml::String("component".to_owned()), reference);
+            Yaml::Hash(component)
+        } else {
+            reference
+        });
+    }
+}
+
+/// Removed game objects take their components and the objects below them in the hierarchy along
+fn removed_game_object_ids(docs: &[YamlUnityDocument], mut removed: HashSet<Id>) -> HashSet<Id> {
+    loop {
+        let len = removed.len();
+        for doc in docs.iter() {
+            let go_id = get_reference_id(doc, "m_GameObject");
+            if go_id.as_ref().is_some_and(|id| removed.contains(id)) {
+                removed.insert(doc.id.clone());
+            }
+            let father_id = get_reference_id(doc, "m_Father");
+            if father_id.as_ref().is_some_and(|id| removed.contains(id)) {
+                if let Some(go_id) = go_id {
+                    removed.insert(go_id);
+                }
+            }
+        }
+        if removed.len() == len {
+            return removed;
+        }
+    }
+}
+
+/// Returns the instance and source ids of the components removed by the PrefabInstances in docs
+fn nested_removed_components(docs: &[YamlUnityDocument]) -> HashSet<(Id, Id)> {
+    docs.iter()
+        .filter(|doc| doc.class_id == PREFAB_INSTANCE_CLASS_ID)
+        .flat_map(|doc| {
+            let removed = body(&doc.document)
+                .and_then(|body| body.get_from_str("m_Modification")?.as_hash())
+                .map(|modification| {
+                    references_to_ids(modification.get_from_str("m_RemovedComponents"))
+                })
+                .unwrap_or_default();
+            removed.into_iter().map(|id| (doc.id.clone(), id))
+        })
+        .collect()
+}
+
+fn references_to_ids(yaml: Option<&Yaml>) -> HashSet<Id> {
+    let Some(Yaml::Array(references)) = yaml else { return hash_set![]; };
+    references
+        .iter()
+        .filter_map(|reference| obj_to_reference(reference.as_hash()?))
+        .map(|reference| reference.file_id)
+        .collect()
+}
+
+fn modification_from_yaml(yaml: &Yaml) -> Option<PropertyModification> {
+    let map = yaml.as_hash()?;
+    Some(PropertyModification {
+        target: obj_to_reference(map.get_from_str("target")?.as_hash()?)?,
+        property_path: map.get_from_str("propertyPath")?.as_str()?.to_owned(),
+        value: map.get_from_str("value").cloned().unwrap_or(Yaml::Null),
+        object_reference: obj_to_reference(map.get_from_str("objectReference")?.as_hash()?)?,
+    })
+}
+
+/// A single step of a property path
+pub enum PathStep<'a> {
+    Key(&'a str),
+    ArraySize,
+    ArrayData(usize),
+}
+
+/// Splits a property path like `m_Materials.Array.data[0]` into steps
+pub fn parse_property_path(path: &str) -> Option<Vec<PathStep<'_>>> {
+    let mut steps = vec![];
+    let mut parts = path.split('.');
+    while let Some(part) = parts.next() {
+        if part != "Array" {
+            steps.push(PathStep::Key(part));
+            continue;
+        }
+        let part = parts.next()?;
+        if part == "size" {
+            steps.push(PathStep::ArraySize);
+        } else {
+            let idx = part
+                .strip_prefix("data[")?
+                .strip_suffix(']')?
+                .parse()
+                .ok()?;
+            steps.push(PathStep::ArrayData(idx));
+        }
+    }
+    Some(steps)
+}
+
+pub(super) fn apply_modification(body: &mut Yaml, modification: &PropertyModification) {
+    if let Some(steps) = parse_property_path(&modification.property_path) {
+        set_property(body, &steps, modification);
+    }
+}
+
+/// Returns the value the steps of a property path lead to
+pub(super) fn get_property<'a>(node: &'a Yaml, steps: &[PathStep]) -> Option<&'a Yaml> {
+    let Some((step, rest)) = steps.split_first() else { return Some(node); };
+    match (step, node) {
+        (PathStep::Key(key), Yaml::Hash(hash)) => get_property(hash.get_from_str(key)?, rest),
+        (PathStep::ArrayData(idx), Yaml::Array(items)) => get_property(items.get(*idx)?, rest),
+        _ => None,
+    }
+}
+
+fn set_property(node: &mut Yaml, steps: &[PathStep], modification: &PropertyModification) {
+    let Some((step, rest)) = steps.split_first() else { return; };
+    match (step, node) {
+        (PathStep::Key(key), Yaml::Hash(hash)) => {
+            if rest.is_empty() {
+                let value = modification_value(hash.get_from_str(key), modification);
+                set_value(hash, key, value);
+            } else {
+                let key = Yaml::String((*key).to_owned());
+                if hash.get(&key).is_none() {
+                    hash.insert(key.clone(), Yaml::Hash(Hash::new(false)));
+                }
+                if let Some(child) = hash.get_mut(&key) {
+                    set_property(child, rest, modification);
+                }
+            }
+        }
+        (PathStep::ArraySize, Yaml::Array(items)) => {
+            let Some(size) = modification.value.as_i64() else { return; };
+            let filler = items.last().cloned().unwrap_or(Yaml::Null);
+            items.resize(size.max(0) as usize, filler);
+        }
+        (PathStep::ArrayData(idx), Yaml::Array(items)) => {
+            let Some(item) = items.get_mut(*idx) else { return; };
+            if rest.is_empty() {
+                *item = modification_value(Some(item), modification);
+            } else {
+                set_property(item, rest, modification);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fields holding references are overridden with objectReference, everything else with value
+fn modification_value(current: Option<&Yaml>, modification: &PropertyModification) -> Yaml {
+    let is_reference = current
+        .and_then(|yaml| yaml.as_hash())
+        .is_some_and(|hash| hash.get_from_str("fileID").is_some());
+    if is_reference || (!modification.object_reference.is_null() && modification.value.is_null()) {
+        let reference = &modification.object_reference;
+        reference_to_yaml(&reference.file_id, reference.guid.as_ref())
+    } else {
+        modification.value.clone()
+    }
+}
+
+/// Local references in the source prefab point to the objects of the prefab, these get the ids of
+/// the instantiated objects
+fn remap_local_ids(body: &mut Hash, instance_id: &Id) {
+    fn remap(yaml: &mut Yaml, instance_id: &Id) {
+        match yaml {
+            Yaml::Hash(hash) => {
+                let is_local_reference = hash.get_from_str("guid").is_none()
+                    && hash
+                        .get_from_str("fileID")
+                        .and_then(|id| id.as_file_id())
+                        .is_some_and(|id| id != "0");
+                if is_local_reference {
+                    let Some(id) = hash.get_from_str("fileID").and_then(|id| id.as_file_id()) else { return; };
+                    if let Some(id) =
+                        instantiated_id(instance_id, &id).and_then(|id| id.parse().ok())
+                    {
+                        set_value(hash, "fileID", Yaml::Integer(id));
+                    }
+                } else {
+                    hash.iter_mut()
+                        .for_each(|(_, value)| remap(value, instance_id));
+                }
+            }
+            Yaml::Array(items) => items.iter_mut().for_each(|item| remap(item, instance_id)),
+            _ => {}
+        }
+    }
+
+    body.iter_mut()
+        .for_each(|(_, value)| remap(value, instance_id));
+}
+
+fn remove_references(body: &mut Hash, key: &str, removed: &HashSet<Id>) {
+    if let Some(Yaml::Array(items)) = body.get_mut(&Yaml::String(key.to_owned())) {
+        items.retain(|item| list_item_id(item).is_none_or(|id| !removed.contains(&id)));
+    }
+}
+
+/// Reads the id from both `- component: {fileID: 1}` and `- {fileID: 1}` list items
+pub(super) fn list_item_id(item: &Yaml) -> Option<Id> {
+    let map = item.as_hash()?;
+    let map = match map.get_from_str("component") {
+        Some(component) => component.as_hash()?,
+        None => map,
+    };
+    map.get_from_str("fileID")?.as_file_id()
+}
+
+/// Replaces the value in place so the order of the keys stays the same as in the file
+pub(super) fn set_value(hash: &mut Hash, key: &str, value: Yaml) {
+    let key = Yaml::String(key.to_owned());
+    match hash.get_mut(&key) {
+        Some(current) => *current = value,
+        None => {
+            hash.insert(key, value);
+        }
+    }
+}
+
+fn reference_to_yaml(file_id: &Id, guid: Option<&Guid>) -> Yaml {
+    let mut hash = Hash::new(false);
+    hash.insert(
+        Yaml::String("fileID".to_owned()),
+        file_id
+            .parse::<i64>()
+            .map(Yaml::Integer)
+            .unwrap_or_else(|_| Yaml::String(file_id.clone())),
+    );
+    if let Some(guid) = guid {
+        hash.insert(Yaml::String("guid".to_owned()), Yaml::String(guid.clone()));
+        hash.insert(Yaml::String("type".to_owned()), Yaml::Integer(3));
+    }
+    Yaml::Hash(hash)
+}
+
+pub(super) fn get_reference_id(doc: &YamlUnityDocument, key: &str) -> Option<Id> {
+    let reference = body(&doc.document)?.get_from_str(key)?.as_hash()?;
+    Some(obj_to_reference(reference)?.file_id)
+}
+
+/// Every document is a map with the class name as its only key
+pub(super) fn body(document: &Yaml) -> Option<&Hash> {
+    document.as_hash()?.iter().next()?.1.as_hash()
+}
+
+pub(super) fn body_mut(document: &mut Yaml) -> Option<&mut Hash> {
+    match body_yaml_mut(document)? {
+        Yaml::Hash(body) => Some(body),
+        _ => None,
+    }
+}
+
+pub(super) fn body_yaml_mut(document: &mut Yaml) -> Option<&mut Yaml> {
+    match document {
+        Yaml::Hash(hash) => Some(hash.iter_mut().next()?.1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_property, instantiated_id, parse_property_path, resolve_prefab_instances};
+    use crate::{
+        fs::ProjectFiles,
+        unity::{
+            repository::construct_meta_repository,
+            yaml::{parse_unity_str, YamlUnityDocument},
+        },
+    };
+    use std::fs;
+    use unity_yaml_rust::Yaml;
+
+    const WEAPON_GUID: &str = "9a8b7c6d5e4f30211203f4e5d6c7b8a9";
+    const LOOP_GUID: &str = "2b3c4d5e6f708192a3b4c5d6e7f80912";
+
+    /// Weapon with a MeshRenderer using two materials and the child Muzzle
+    const WEAPON: &str = "\
+%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &1000
+GameObject:
+  m_Component:
+  - component: {fileID: 2000}
+  - component: {fileID: 5000}
+  m_Name: Weapon
+--- !u!4 &2000
+Transform:
+  m_GameObject: {fileID: 1000}
+  m_LocalPosition: {x: 0, y: 0, z: 0}
+  m_Children:
+  - {fileID: 4000}
+  m_Father: {fileID: 0}
+--- !u!1 &3000
+GameObject:
+  m_Component:
+  - component: {fileID: 4000}
+  m_Name: Muzzle
+--- !u!4 &4000
+Transform:
+  m_GameObject: {fileID: 3000}
+  m_LocalPosition: {x: 0, y: 0, z: 1}
+  m_Children: []
+  m_Father: {fileID: 2000}
+--- !u!23 &5000
+MeshRenderer:
+  m_GameObject: {fileID: 1000}
+  m_Materials:
+  - {fileID: 2100000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 2}
+  - {fileID: 2100000, guid: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, type: 2}
+";
+
+    /// A prefab holding an instance of itself, Unity does not allow this but a copied file can
+    const LOOP: &str = "\
+%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &1000
+GameObject:
+  m_Component:
+  - component: {fileID: 2000}
+  m_Name: Loop
+--- !u!4 &2000
+Transform:
+  m_GameObject: {fileID: 1000}
+  m_Children: []
+  m_Father: {fileID: 0}
+--- !u!1001 &6000
+PrefabInstance:
+  m_Modification:
+    m_TransformParent: {fileID: 2000}
+    m_Modifications: []
+    m_RemovedComponents: []
+  m_SourcePrefab: {fileID: 100100000, guid: 2b3c4d5e6f708192a3b4c5d6e7f80912, type: 3}
+";
+
+    /// A scene with an instance of the prefab with the guid and the given modification fields
+    fn scene(guid: &str, modification: &str) -> String {
+        format!(
+            "\
+%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1001 &700
+PrefabInstance:
+  m_ObjectHideFlags: 0
+  serializedVersion: 2
+  m_Modification:
+    m_TransformParent: {{fileID: 0}}
+{}
+  m_SourcePrefab: {{fileID: 100100000, guid: {}, type: 3}}
+--- !u!4 &1388 stripped
+Transform:
+  m_CorrespondingSourceObject: {{fileID: 2000, guid: {}, type: 3}}
+  m_PrefabInstance: {{fileID: 700}}
+  m_PrefabAsset: {{fileID: 0}}
+",
+            modification, guid, guid
+        )
+    }
+
+    /// Writes the prefabs with their meta files to a project of its own and resolves the scene
+    fn resolve(name: &str, scene: &str) -> Vec<YamlUnityDocument> {
+        let dir = std::env::temp_dir().join(format!("unity_tui_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut project = ProjectFiles::new(&dir);
+        for (file, guid, content) in [
+            ("Weapon.prefab", WEAPON_GUID, WEAPON),
+            ("Loop.prefab", LOOP_GUID, LOOP),
+        ] {
+            fs::write(dir.join(file), content).unwrap();
+            let meta_path = dir.join(format!("{}.meta", file));
+            fs::write(
+                &meta_path,
+                format!("fileFormatVersion: 2\nguid: {}\nPrefabImporter:\n", guid),
+            )
+            .unwrap();
+            project.meta_files.push(meta_path);
+        }
+
+        let (meta_files, errors) = construct_meta_repository(&project);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let docs = resolve_prefab_instances(parse_unity_str(scene).documents, &meta_files);
+        fs::remove_dir_all(&dir).unwrap();
+        docs
+    }
+
+    fn find<'a>(docs: &'a [YamlUnityDocument], id: &str) -> Option<&'a YamlUnityDocument> {
+        docs.iter().find(|doc| doc.id == id)
+    }
+
+    /// Reads the value the property path leads to in the body of the document
+    fn property<'a>(doc: &'a YamlUnityDocument, path: &str) -> Option<&'a Yaml> {
+        let body = doc.document.as_hash()?.iter().next()?.1;
+        get_property(body, &parse_property_path(path)?)
+    }
+
+    #[test]
+    fn instantiated_ids_xor_the_instance_and_source_ids() {
+        assert_eq!(
+            instantiated_id(&"700".to_owned(), &"2000".to_owned()).as_deref(),
+            Some("1388")
+        );
+        // negative ids are read as their two's complement and the sign bit is dropped
+        assert_eq!(
+            instantiated_id(&"-1".to_owned(), &"1".to_owned()).as_deref(),
+            Some("9223372036854775806")
+        );
+        assert_eq!(
+            instantiated_id(&"-7000000000".to_owned(), &"123456789012".to_owned()).as_deref(),
+            Some("9223371911769972756")
+        );
+        assert_eq!(instantiated_id(&"700".to_owned(), &"abc".to_owned()), None);
+    }
+
+    #[test]
+    fn source_objects_get_merged_in_with_the_modifications() {
+        let modification = "    m_Modifications:
+    - target: {fileID: 2000, guid: 9a8b7c6d5e4f30211203f4e5d6c7b8a9, type: 3}
+      propertyPath: m_LocalPosition.x
+      value: 3
+      objectReference: {fileID: 0}
+    - target: {fileID: 5000, guid: 9a8b7c6d5e4f30211203f4e5d6c7b8a9, type: 3}
+      propertyPath: m_Materials.Array.data[1]
+      value: 
+      objectReference: {fileID: 2100000, guid: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, type: 2}
+    m_RemovedComponents: []";
+        let docs = resolve("merged", &scene(WEAPON_GUID, modification));
+
+        let ids = docs
+            .iter()
+            .map(|doc| doc.id.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, vec!["700", "340", "1388", "2308", "3356", "4404"]);
+        assert!(docs.iter().all(|doc| !doc.stripped));
+
+        let transform = find(&docs, "1388").unwrap();
+        assert_eq!(
+            property(transform, "m_LocalPosition.x").and_then(Yaml::as_i64),
+            Some(3)
+        );
+        assert_eq!(
+            property(transform, "m_LocalPosition.z").and_then(Yaml::as_i64),
+            Some(0)
+        );
+        assert_eq!(
+            property(transform, "m_Children.Array.data[0].fileID").and_then(Yaml::as_i64),
+            Some(3356)
+        );
+        assert_eq!(
+            property(transform, "m_PrefabInstance.fileID").and_then(Yaml::as_i64),
+            Some(700)
+        );
+        assert_eq!(
+            property(transform, "m_CorrespondingSourceObject.fileID").and_then(Yaml::as_i64),
+            Some(2000)
+        );
+
+        let renderer = find(&docs, "4404").unwrap();
+        assert_eq!(
+            property(renderer, "m_Materials.Array.data[0].guid").and_then(Yaml::as_str),
+            Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+        );
+        assert_eq!(
+            property(renderer, "m_Materials.Array.data[1].guid").and_then(Yaml::as_str),
+            Some("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb")
+        );
+    }
+
+    #[test]
+    fn removed_game_objects_are_dropped_with_their_components() {
+        let modification = "    m_Modifications: []
+    m_RemovedComponents: []
+    m_RemovedGameObjects:
+    - {fileID: 3000, guid: 9a8b7c6d5e4f30211203f4e5d6c7b8a9, type: 3}";
+        let docs = resolve("removed", &scene(WEAPON_GUID, modification));
+
+        assert!(find(&docs, "2308").is_none());
+        assert!(find(&docs, "3356").is_none());
+        let transform = find(&docs, "1388").unwrap();
+        assert_eq!(
+            property(transform, "m_Children"),
+            Some(&Yaml::Array(vec![]))
+        );
+    }
+
+    #[test]
+    fn prefabs_including_themselves_are_resolved_once() {
+        let modification = "    m_Modifications: []
+    m_RemovedComponents: []";
+        let docs = resolve("loop", &scene(LOOP_GUID, modification));
+
+        // the nested instance cannot be resolved so it is left out with the PrefabInstance
+        // documents of the source prefab
+        let ids = docs
+            .iter()
+            .map(|doc| doc.id.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, vec!["700", "340", "1388"]);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::class_id::CLASS_IDS;
+use crate::unity::{
+    converters::{helpers::obj_to_reference, GetFromStr},
+    object::ObjectReference,
+    repository::MetaFilesRepository,
+    yaml::YamlUnityDocument,
+    Guid, Id,
+};
+use std::collections::HashSet;
+use unity_yaml_rust::Yaml;
+
+/// Assets that come with the editor, like the default materials, have guids starting with zeros
+/// and no meta file
+const BUILT_IN_GUID_PREFIX: &str = "0000000000000000";
+
+/// A reference of a document to a script, an asset or an object that cannot be found
+#[derive(Debug, Clone)]
+pub struct BrokenReference {
+    /// The document the reference is in
+    pub file_id: Id,
+    pub class_name: String,
+    /// The line of the header of the document, counted from 1
+    pub line: Option<usize>,
+    /// The field the reference is in, like `m_Materials.Array.data[0]`
+    pub property_path: String,
+    pub reference: ObjectReference,
+    pub kind: BrokenReferenceKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrokenReferenceKind {
+    /// The script of a MonoBehaviour is not in the project or was removed
+    Script,
+    /// The guid is not the one of any asset of the project
+    Asset,
+    /// No object of the file has the fileID
+    Object,
+}
+
+impl BrokenReferenceKind {
+    /// Describes the reference the way the inspector shows it, like `Missing Script (guid …)`
+    pub fn describe(&self, reference: &ObjectReference) -> String {
+        match (self, &reference.guid) {
+            (BrokenReferenceKind::Script, Some(guid)) => missing_script_name(guid),
+            (BrokenReferenceKind::Script, None) => "Missing Script".to_owned(),
+            (BrokenReferenceKind::Asset, Some(guid)) => {
+                format!("Missing Asset (guid {})", guid)
+            }
+            (BrokenReferenceKind::Asset, None) | (BrokenReferenceKind::Object, _) => {
+                format!("Missing Object (fileID {})", reference.file_id)
+            }
+        }
+    }
+}
+
+/// The name of a MonoBehaviour whose script cannot be found, an empty guid for a removed script
+pub fn missing_script_name(guid: &Guid) -> String {
+    if guid.is_empty() {
+        "Missing Script".to_owned()
+    } else {
+        format!("Missing Script (guid {})", guid)
+    }
+}
+
+pub fn is_built_in_guid(guid: &Guid) -> bool {
+    guid.starts_with(BUILT_IN_GUID_PREFIX)
+}
+
+/// Checks a reference that is not a script, `has_object` tells if an object of the file has the
+/// fileID. Null references are not broken
+pub fn check_reference(
+    reference: &ObjectReference,
+    has_object: impl Fn(&Id) -> bool,
+    meta_files: &MetaFilesRepository,
+) -> Option<BrokenReferenceKind> {
+    if reference.is_null() {
+        return None;
+    }
+    match &reference.guid {
+        Some(guid) if is_built_in_guid(guid) || meta_files.get(guid).is_some() => None,
+        Some(_) => Some(BrokenReferenceKind::Asset),
+        None if has_object(&reference.file_id) => None,
+        None => Some(BrokenReferenceKind::Object),
+    }
+}
+
+/// Finds the broken references of the documents of a file, in the order of the file. References
+/// to other files are only checked against the project, not against the objects of the other file
+pub fn find_broken_references(
+    documents: &[YamlUnityDocument],
+    meta_files: &MetaFilesRepository,
+) -> Vec<BrokenReference> {
+    let ids = documents
+        .iter()
+        .map(|doc| &doc.id)
+        .collect::<HashSet<&Id>>();
+    let mut out = vec![];
+    // stripped documents only link to the prefab instance, which is checked itself
+    for doc in documents
+        .iter()
+        .filter(|doc| !doc.is_unreadable() && !doc.stripped)
+    {
+        let Some((class_name, references)) = get_document_references(doc) else { continue; };
+        for (property_path, reference) in references {
+            let kind = if class_name == "MonoBehaviour" && property_path == "m_Script" {
+                let guid = reference.guid.as_ref();
+                match guid {
+                    Some(guid) if meta_files.get(guid).is_some() => None,
+                    _ => Some(BrokenReferenceKind::Script),
+                }
+            } else {
+                check_reference(&reference, |id| ids.contains(id), meta_files)
+            };
+            if let Some(kind) = kind {
+                out.push(BrokenReference {
+                    file_id: doc.id.clone(),
+                    class_name: class_name.to_owned(),
+                    line: doc.line,
+                    property_path,
+                    reference,
+                    kind,
+                });
+            }
+        }
+    }
+    out
+}
+
+/// Returns the class name of the document and its references with their property paths, in the
+/// order of the document. The targets of the modifications of a prefab instance are in its source
+/// prefab, only `m_SourcePrefab` is kept of the references to it
+pub fn get_document_references(
+    doc: &YamlUnityDocument,
+) -> Option<(&str, Vec<(String, ObjectReference)>)> {
+    let (class_name, body) = doc.document.as_hash()?.iter().next()?;
+    let class_name = CLASS_IDS
+        .get(&doc.class_id)
+        .copied()
+        .or_else(|| class_name.as_str())
+        .unwrap_or_default();
+    let mut references = vec![];
+    collect_references(body, String::new(), &mut references);
+    if class_name == "PrefabInstance" {
+        let source_guid = body
+            .as_hash()
+            .and_then(|hash| hash.get_from_str("m_SourcePrefab"))
+            .and_then(|source| source.as_hash())
+            .and_then(obj_to_reference)
+            .and_then(|source| source.guid);
+        references.retain(|(path, reference)| {
+            path == "m_SourcePrefab" || source_guid.is_none() || reference.guid != source_guid
+        });
+    }
+    Some((class_name, references))
+}
+
+/// Collects every `{fileID: …}` value under the node with its property path
+fn collect_references(node: &Yaml, path: String, out: &mut Vec<(String, ObjectReference)>) {
+    match node {
+        Yaml::Hash(hash) => {
+            if let Some(reference) = obj_to_reference(hash) {
+                out.push((path, reference));
+                return;
+            }
+            for (key, value) in hash.iter() {
+                let Some(key) = key.as_str() else { continue; };
+                let path = if path.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_references(value, path, out);
+            }
+        }
+        Yaml::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                collect_references(item, format!("{}.Array.data[{}]", path, idx), out);
+            }
+        }
+        _ => {}
+    }
+}
@@ -3,43 +3,102 @@ use crate::{
     class_id::CLASS_IDS,
     fs::ProjectFiles,
     unity::{
+        asset::{Asset, AssetType},
         converters::{
-            helpers::{obj_to_vec2, obj_to_vec3, obj_to_vec4},
-            AsFileId, GetFromStr,
+            helpers::{obj_to_color, obj_to_reference, obj_to_vec2, obj_to_vec3, obj_to_vec4},
+            AsF32, AsFileId, GetFromStr,
+        },
+        error::{ParseError, ParseErrorKind},
+        object::{
+            BoxCollider, BoxCollider2D, Camera, Canvas, CapsuleCollider, CircleCollider2D,
+            Collider, Field, GenericComponent, GetId, Light, MeshCollider, MeshFilter,
+            MeshRenderer, ObjectReference, PrefabInstance, PropertyModification, Renderer,
+            Rigidbody, Rigidbody2D, Rigidbody3D, SceneRoots, SkinnedMeshRenderer, SphereCollider,
+            SpriteRenderer, StrippedObject, WorldTransform,
         },
-        object::Field,
         yaml::YamlUnityDocument,
         Component, GameObject, Guid, Id, MonoBehaviour, Object, RectTransform, Transform,
         Transform3D,
     },
-    util::hash_map,
+    util::{hash_map, hash_set},
 };
 use linked_hash_map::LinkedHashMap;
 use std::{
     collections::{HashMap, HashSet},
-    io::Read,
+    path::{Path, PathBuf},
 };
 use unity_yaml_rust::Yaml;
 
-pub struct Repository(LinkedHashMap<Id, Object>);
-pub struct MetaFilesRepository(HashMap<Guid, String>);
+pub struct Repository {
+    objects: LinkedHashMap<Id, Object>,
+    prefab_links: HashMap<Id, PrefabLink>,
+}
+
+/// The assets of the project by guid and by path, read from the meta files
+pub struct MetaFilesRepository {
+    base_path: PathBuf,
+    assets: HashMap<Guid, Asset>,
+    guids_by_path: HashMap<PathBuf, Guid>,
+}
+
+/// Links an object merged in from a source prefab to the instance it belongs to
+#[derive(Debug, Clone)]
+pub struct PrefabLink {
+    pub prefab_instance_id: Id,
+    pub corresponding_source: ObjectReference,
+}
 
 impl MetaFilesRepository {
-    pub fn get(&self, guid: &Guid) -> Option<&String> {
-        self.0.get(guid)
+    pub fn get(&self, guid: &Guid) -> Option<&Asset> {
+        self.assets.get(guid)
+    }
+
+    /// Returns the asset at the path, relative to the project or not
+    pub fn get_by_path(&self, path: &Path) -> Option<&Asset> {
+        let relative = path.strip_prefix(&self.base_path).unwrap_or(path);
+        self.assets.get(self.guids_by_path.get(relative)?)
     }
-}
 
-impl From<HashMap<Guid, String>> for MetaFilesRepository {
-    fn from(map: HashMap<Guid, String>) -> Self {
-        Self(map)
+    /// Returns the name the editor shows for the asset, see [`Asset::get_name`]
+    pub fn get_name(&self, guid: &Guid) -> Option<&str> {
+        Some(self.assets.get(guid)?.get_name())
+    }
+
+    /// Returns the path of the asset the meta file with the given guid belongs to
+    pub fn get_path(&self, guid: &Guid) -> Option<PathBuf> {
+        Some(self.base_path.join(&self.assets.get(guid)?.path))
+    }
+
+    /// Returns the path of the project the meta files were read from
+    pub fn get_base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Returns the path of the asset relative to the project, like it is shown in the editor
+    pub fn get_relative_path(&self, guid: &Guid) -> Option<&Path> {
+        Some(&self.assets.get(guid)?.path)
+    }
+
+    /// Returns the C# scripts, sorted by name and then by path
+    pub fn get_scripts(&self) -> Vec<&Asset> {
+        let mut scripts = self
+            .assets
+            .values()
+            .filter(|asset| asset.is_script())
+            .collect::<Vec<&Asset>>();
+        scripts.sort_by(|a, b| (a.get_name(), &a.path).cmp(&(b.get_name(), &b.path)));
+        scripts
     }
 }
 
 impl Repository {
+    pub fn get_ids(&self) -> impl Iterator<Item = &Id> {
+        self.objects.keys()
+    }
+
     /// Returns all Ids that point to GameObjects
     pub fn get_game_object_ids(&self) -> HashSet<Id> {
-        self.0
+        self.objects
             .iter()
             .filter_map(|(id, obj)| match obj {
                 Object::GameObject(_) => Some(id.clone()),
@@ -50,7 +109,7 @@ impl Repository {
 
     /// Returns all Ids of Transforms that do not have a parent
     pub fn get_unparented_transforms(&self) -> Vec<&Transform> {
-        self.0
+        self.objects
             .iter()
             .filter_map(|(_, obj)| match obj {
                 Object::Component(Component::Transform(transform)) => Some(transform),
@@ -84,80 +143,561 @@ impl Repository {
         }
     }
 
-    /// Returns a MonoBehaviour. Returns none if id was not found or if found object is not a MonoBehaviour
-    pub fn get_monobehaviour(&self, id: &Id) -> Option<&MonoBehaviour> {
-        match self.get_component(id)? {
-            Component::MonoBehaviour(mono) => Some(mono),
+    /// Returns the world position, rotation and scale of a Transform by applying the transforms of
+    /// its parents up to the root. A parent that is not in the repository, like the transform of an
+    /// unresolved prefab instance, is taken as the root
+    pub fn get_world_transform(&self, id: &Id) -> Option<WorldTransform> {
+        let mut transform = self.get_transform(id)?;
+        let mut world = WorldTransform {
+            position: *transform.get_local_position(),
+            rotation: *transform.get_local_rotation(),
+            lossy_scale: *transform.get_local_scale(),
+        };
+        let mut visited = hash_set![id.clone()];
+        while transform.has_parent() {
+            let father_id = transform.get_father_id();
+            // a broken file could have a transform as its own ancestor
+            if !visited.insert(father_id.clone()) {
+                break;
+            }
+            let Some(father) = self.get_transform(father_id) else { break; };
+            let rotation = father.get_local_rotation();
+            let scale = father.get_local_scale();
+            world.position = rotation
+                .rotate(world.position.scale(scale))
+                .add(father.get_local_position());
+            world.rotation = rotation.mul(&world.rotation);
+            world.lossy_scale = world.lossy_scale.scale(scale);
+            transform = father;
+        }
+        world.rotation = world.rotation.normalized();
+        Some(world)
+    }
+
+    /// Returns all PrefabInstances in the file
+    pub fn get_prefab_instances(&self) -> Vec<&PrefabInstance> {
+        self.objects
+            .iter()
+            .filter_map(|(_, obj)| match obj {
+                Object::PrefabInstance(instance) => Some(instance),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns a PrefabInstance. Returns none if id was not found or if found object is not a PrefabInstance
+    pub fn get_prefab_instance(&self, id: &Id) -> Option<&PrefabInstance> {
+        match self.get(id)? {
+            Object::PrefabInstance(instance) => Some(instance),
+            _ => None,
+        }
+    }
+
+    /// Returns a stripped object. Returns none if id was not found or if found object is not stripped
+    pub fn get_stripped(&self, id: &Id) -> Option<&StrippedObject> {
+        match self.get(id)? {
+            Object::Stripped(stripped) => Some(stripped),
             _ => None,
         }
     }
 
+    /// Returns true if the object is a stripped object of the prefab instance or was merged in from it
+    pub fn belongs_to_prefab_instance(&self, id: &Id, instance_id: &Id) -> bool {
+        self.get_stripped(id)
+            .is_some_and(|s| &s.prefab_instance_id == instance_id)
+            || self
+                .get_prefab_link(id)
+                .is_some_and(|link| &link.prefab_instance_id == instance_id)
+    }
+
+    /// Returns all Transforms added in this file as children of objects of the given prefab instance
+    pub fn get_transforms_added_to_prefab_instance(&self, instance_id: &Id) -> Vec<&Transform> {
+        self.objects
+            .iter()
+            .filter_map(|(_, obj)| match obj {
+                Object::Component(Component::Transform(transform)) => Some(transform),
+                _ => None,
+            })
+            .filter(|transform| {
+                self.belongs_to_prefab_instance(transform.get_father_id(), instance_id)
+                    && !self.belongs_to_prefab_instance(transform.get_id(), instance_id)
+            })
+            .collect()
+    }
+
+    /// Returns all components added in this file to game objects of the given prefab instance
+    pub fn get_components_added_to_prefab_instance(&self, instance_id: &Id) -> Vec<&Component> {
+        self.objects
+            .iter()
+            .filter_map(|(_, obj)| match obj {
+                Object::Component(component) => Some(component),
+                _ => None,
+            })
+            .filter(|component| {
+                self.belongs_to_prefab_instance(component.get_game_object_id(), instance_id)
+                    && !self.belongs_to_prefab_instance(component.get_id(), instance_id)
+            })
+            .collect()
+    }
+
+    /// Returns true if the component was added to a game object of a prefab instance in this file
+    pub fn is_added_component(&self, component: &Component) -> bool {
+        let instance_id = match self.get_stripped(component.get_game_object_id()) {
+            Some(stripped) => &stripped.prefab_instance_id,
+            None => match self.get_prefab_link(component.get_game_object_id()) {
+                Some(link) => &link.prefab_instance_id,
+                None => return false,
+            },
+        };
+        !self.belongs_to_prefab_instance(component.get_id(), instance_id)
+    }
+
+    /// Returns true if the component comes from a source prefab and is removed by its prefab instance
+    pub fn is_removed_component(&self, id: &Id) -> bool {
+        self.get_prefab_link(id).is_some_and(|link| {
+            self.get_prefab_instance(&link.prefab_instance_id)
+                .is_some_and(|instance| {
+                    instance
+                        .removed_components
+                        .contains(&link.corresponding_source)
+                })
+        })
+    }
+
+    /// Returns the components of the game object which are removed by its prefab instance
+    pub fn get_removed_components(&self, game_object_id: &Id) -> Vec<&Component> {
+        self.objects
+            .iter()
+            .filter_map(|(id, obj)| match obj {
+                Object::Component(component) => Some((id, component)),
+                _ => None,
+            })
+            .filter(|(id, component)| {
+                component.get_game_object_id() == game_object_id && self.is_removed_component(id)
+            })
+            .map(|(_, component)| component)
+            .collect()
+    }
+
+    /// Returns the object of a prefab instance created from the given object of the source prefab
+    pub fn get_prefab_instance_object(
+        &self,
+        instance_id: &Id,
+        source: &ObjectReference,
+    ) -> Option<&Object> {
+        let (id, _) = self.prefab_links.iter().find(|(_, link)| {
+            &link.prefab_instance_id == instance_id
+                && link.corresponding_source.file_id == source.file_id
+                && link.corresponding_source.guid == source.guid
+        })?;
+        self.get(id)
+    }
+
+    /// Returns the modifications of the prefab instance made to the object with the given id,
+    /// together with their index in the modification list of the instance
+    pub fn get_overrides(&self, id: &Id) -> Vec<(usize, &PropertyModification)> {
+        let Some(link) = self.get_prefab_link(id) else { return vec![]; };
+        let Some(instance) = self.get_prefab_instance(&link.prefab_instance_id) else { return vec![]; };
+        instance
+            .modifications
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| {
+                m.target.file_id == link.corresponding_source.file_id
+                    && m.target.guid == link.corresponding_source.guid
+            })
+            .collect()
+    }
+
+    /// Returns the prefab instance if the game object is the root of one
+    pub fn get_prefab_instance_of_root(&self, game_object: &GameObject) -> Option<&PrefabInstance> {
+        let link = self.get_prefab_link(&game_object.id)?;
+        let instance = self.get_prefab_instance(&link.prefab_instance_id)?;
+        let transform = game_object
+            .component_ids
+            .iter()
+            .find_map(|id| self.get_transform(id))?;
+        (transform.get_father_id() == &instance.transform_parent_id).then_some(instance)
+    }
+
+    /// Returns the object of the source prefab the root transform of the instance comes from. It is
+    /// known from the transform merged in from the prefab, or from a stripped transform listed in
+    /// the children of the parent of the instance
+    pub fn get_prefab_instance_root_source(
+        &self,
+        instance: &PrefabInstance,
+    ) -> Option<&ObjectReference> {
+        let siblings = self
+            .get_transform(&instance.transform_parent_id)
+            .map(|parent| parent.get_children_ids());
+        self.objects.values().find_map(|object| match object {
+            Object::Component(Component::Transform(transform))
+                if transform.get_father_id() == &instance.transform_parent_id =>
+            {
+                self.get_prefab_link(transform.get_id())
+                    .filter(|link| link.prefab_instance_id == instance.id)
+                    .map(|link| &link.corresponding_source)
+            }
+            Object::Stripped(stripped)
+                if stripped.prefab_instance_id == instance.id
+                    && siblings.is_some_and(|siblings| siblings.contains(&stripped.id)) =>
+            {
+                Some(&stripped.corresponding_source)
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns the order of the root of the prefab instance among its siblings. Instances at the
+    /// root of a scene listed in SceneRoots are ordered by it
+    pub fn get_prefab_instance_root_order(&self, instance: &PrefabInstance) -> i64 {
+        let listed = self
+            .get_scene_roots()
+            .filter(|_| !instance.has_parent())
+            .and_then(|roots| roots.root_ids.iter().position(|id| id == &instance.id));
+        match listed {
+            Some(position) => position as i64,
+            None => instance.get_root_order(self.get_prefab_instance_root_source(instance)),
+        }
+    }
+
+    /// Returns the roots of the scene, only newer versions of Unity write them
+    pub fn get_scene_roots(&self) -> Option<&SceneRoots> {
+        self.objects.values().find_map(|object| match object {
+            Object::SceneRoots(roots) => Some(roots),
+            _ => None,
+        })
+    }
+
+    /// Returns all PrefabInstances whose parent is a stripped object of the given prefab instance
+    pub fn get_prefab_instances_nested_in(&self, instance_id: &Id) -> Vec<&PrefabInstance> {
+        self.get_prefab_instances()
+            .into_iter()
+            .filter(|nested| {
+                self.get_stripped(&nested.transform_parent_id)
+                    .is_some_and(|s| &s.prefab_instance_id == instance_id)
+            })
+            .collect()
+    }
+
+    /// Returns the prefab link of an object merged in from a prefab instance
+    pub fn get_prefab_link(&self, id: &Id) -> Option<&PrefabLink> {
+        self.prefab_links.get(id)
+    }
+
+    /// Returns true if the contents of the source prefab were merged into the repository
+    pub fn is_prefab_instance_resolved(&self, instance_id: &Id) -> bool {
+        self.prefab_links
+            .values()
+            .any(|link| &link.prefab_instance_id == instance_id)
+    }
+
     pub fn get(&self, id: &Id) -> Option<&Object> {
-        self.0.get(id)
+        self.objects.get(id)
     }
-}
 
-impl From<LinkedHashMap<Id, Object>> for Repository {
-    fn from(map: LinkedHashMap<Id, Object>) -> Self {
-        Self(map)
+    /// Replaces the object with the same id and returns it, the object is added if there is none
+    pub fn set_object(&mut self, object: Object) -> Option<Object> {
+        match self.objects.get_mut(object.get_id()) {
+            // replaced in place so the objects keep their order
+            Some(current) => Some(std::mem::replace(current, object)),
+            None => {
+                self.objects.insert(object.get_id().clone(), object);
+                None
+            }
+        }
     }
-}
 
-pub fn construct_meta_repository(project: &ProjectFiles) -> Option<MetaFilesRepository> {
-    println!("Loading project, please wait...");
-    let mut repo = hash_map![];
+    /// Adds the object of a document the same way [`construct_repository`] does
+    pub fn insert_document(&mut self, doc: &YamlUnityDocument) {
+        let (repo, _) = construct_repository(vec![doc.clone()]);
+        self.prefab_links.extend(repo.prefab_links);
+        for (_, object) in repo.objects {
+            self.set_object(object);
+        }
+    }
 
-    for path in project.meta_files.iter() {
-        let name = path.file_name()?.to_str()?.split('.').next()?.to_owned();
+    pub fn remove_object(&mut self, id: &Id) -> Option<Object> {
+        self.prefab_links.remove(id);
+        self.objects.remove(id)
+    }
+}
 
-        let mut file = std::fs::File::open(path).ok()?;
-        let mut content = String::new();
-        file.read_to_string(&mut content).ok()?;
-        let parsed = unity_yaml_rust::YamlLoader::load_from_str(&content).ok()?;
-        assert_eq!(parsed.len(), 1);
+/// Reads the guids of the assets from their meta files. Meta files that cannot be read are left out
+/// and reported
+pub fn construct_meta_repository(project: &ProjectFiles) -> (MetaFilesRepository, Vec<ParseError>) {
+    let mut assets = HashMap::<Guid, Asset>::new();
+    let mut guids_by_path = hash_map![];
+    let mut errors = vec![];
 
-        let Yaml::Hash(map) = &parsed[0] else { return None; };
-        let Yaml::String(guid) = map.get_from_str("guid")?.clone() else { return None; };
-        repo.insert(guid, name);
+    for meta_path in project.meta_files.iter() {
+        let relative = meta_path
+            .strip_prefix(&project.base_path)
+            .unwrap_or(meta_path);
+        let asset = match asset_from_meta_file(meta_path, &relative.with_extension("")) {
+            Ok(asset) => asset,
+            Err(error) => {
+                errors.push(error.with_path(relative));
+                continue;
+            }
+        };
+        // copied assets keep the guid of their meta file until Unity gives them a new one
+        if let Some(other) = assets.get(&asset.guid) {
+            errors.push(
+                ParseError::new(
+                    ParseErrorKind::Document,
+                    format!(
+                        "the guid {} is also used by {}",
+                        asset.guid,
+                        other.path.display()
+                    ),
+                )
+                .with_path(relative),
+            );
+            continue;
+        }
+        guids_by_path.insert(asset.path.clone(), asset.guid.clone());
+        assets.insert(asset.guid.clone(), asset);
     }
 
-    Some(repo.into())
+    let repository = MetaFilesRepository {
+        base_path: project.base_path.clone(),
+        assets,
+        guids_by_path,
+    };
+    (repository, errors)
+}
+
+/// Reads the asset at `path`, relative to the project, from its meta file
+fn asset_from_meta_file(meta_path: &Path, path: &Path) -> Result<Asset, ParseError> {
+    let content = std::fs::read_to_string(meta_path)
+        .map_err(|e| ParseError::new(ParseErrorKind::Io, e.to_string()))?;
+    let parsed = unity_yaml_rust::YamlLoader::load_from_str(&content)
+        .map_err(|e| ParseError::from_scan_error(&e))?;
+    let map = parsed
+        .first()
+        .and_then(|doc| doc.as_hash())
+        .ok_or_else(|| ParseError::new(ParseErrorKind::Document, "the meta file is empty"))?;
+    let guid = map
+        .get_from_str("guid")
+        .and_then(|guid| guid.as_guid())
+        .ok_or_else(|| ParseError::new(ParseErrorKind::Document, "the meta file has no guid"))?;
+    // written as `folderAsset: yes`, which is a string for the YAML parser
+    let is_folder = map
+        .get_from_str("folderAsset")
+        .is_some_and(|value| value.as_str() == Some("yes") || value.as_bool() == Some(true));
+    let importer = map
+        .iter()
+        .filter_map(|(key, _)| key.as_str())
+        .find(|key| key.ends_with("Importer"))
+        .map(|key| key.to_owned());
+
+    Ok(Asset {
+        guid,
+        path: path.to_path_buf(),
+        asset_type: AssetType::from_path(path, is_folder),
+        importer,
+    })
 }
 
-pub fn construct_repository(yaml: Vec<YamlUnityDocument>) -> Option<Repository> {
+/// Builds the repository of the objects in the documents. Documents that cannot be read are left
+/// out and reported, documents of classes without a model are left out silently
+pub fn construct_repository(yaml: Vec<YamlUnityDocument>) -> (Repository, Vec<ParseError>) {
     let mut repo = LinkedHashMap::<Id, Object>::new();
+    let mut errors = vec![];
+    let mut prefab_links = hash_map![];
+    let mut without_root_order = vec![];
+    let mut scene_roots = vec![];
     for doc in yaml.iter() {
-        if let Some(class_name) = CLASS_IDS.get(&doc.class_id) {
-            match *class_name {
-                "GameObject" => {
-                    repo.insert(
-                        doc.id.clone(),
-                        Object::GameObject(game_object_from_yaml(doc, class_name)?),
-                    );
-                }
-                // TODO: this does not account for built-in components, i.e. Camera
-                "MonoBehaviour" => {
-                    repo.insert(
-                        doc.id.clone(),
-                        Object::Component(Component::MonoBehaviour(monobehaviour_from_yaml(
-                            doc, class_name,
-                        )?)),
-                    );
-                }
-                "Transform" | "RectTransform" => {
-                    repo.insert(
-                        doc.id.clone(),
-                        Object::Component(Component::Transform(transform_from_yaml(
-                            doc, class_name,
-                        )?)),
-                    );
-                }
-                _ => {} // TODO: other types of serialized entities, like RenderSettings
+        // it is reported when the file is read
+        if doc.is_unreadable() {
+            continue;
+        }
+        if document_class_name(doc) == Some("SceneRoots") {
+            if let Some(roots) = scene_roots_from_yaml(doc) {
+                scene_roots = roots.root_ids.clone();
+                repo.insert(roots.id.clone(), Object::SceneRoots(roots));
+            }
+            continue;
+        }
+        let Some(class_name) = CLASS_IDS
+            .get(&doc.class_id)
+            .copied()
+            .or_else(|| document_class_name(doc)) else {
+            continue;
+        };
+        let object = if doc.stripped {
+            stripped_object_from_yaml(doc, class_name).map(Object::Stripped)
+        } else if class_name == "PrefabInstance" {
+            prefab_instance_from_yaml(doc, class_name).map(Object::PrefabInstance)
+        } else if class_name == "GameObject" {
+            game_object_from_yaml(doc, class_name).map(Object::GameObject)
+        } else if let Some(component) = component_from_yaml(doc, class_name) {
+            Some(Object::Component(component))
+        } else if is_component_document(doc, class_name) {
+            None
+        } else {
+            continue; // TODO: other types of serialized entities, like RenderSettings
+        };
+        let Some(object) = object else {
+            errors.push(
+                ParseError::new(
+                    ParseErrorKind::Document,
+                    "a field is missing or has a value of an unexpected type",
+                )
+                .with_document(&doc.id, class_name)
+                .with_line(doc.line),
+            );
+            continue;
+        };
+        if !doc.stripped {
+            if let Some(link) = prefab_link_from_yaml(doc, class_name) {
+                prefab_links.insert(doc.id.clone(), link);
+            }
+        }
+        if let Object::Component(Component::Transform(transform)) = &object {
+            if !has_root_order(doc) {
+                without_root_order.push(transform.get_id().clone());
+            }
+        }
+        repo.insert(object.get_id().clone(), object);
+    }
+    derive_root_orders(&mut repo, &without_root_order, &scene_roots);
+
+    let repository = Repository {
+        objects: repo,
+        prefab_links,
+    };
+    (repository, errors)
+}
+
+/// Components are the documents that belong to a game object
+fn is_component_document(doc: &YamlUnityDocument, class_name: &str) -> bool {
+    doc.document
+        .as_hash()
+        .and_then(|map| map.get_from_str(class_name))
+        .and_then(|body| body.as_hash())
+        .is_some_and(|body| body.get_from_str("m_GameObject").is_some())
+}
+
+fn has_root_order(doc: &YamlUnityDocument) -> bool {
+    doc.document
+        .as_hash()
+        .and_then(|map| map.iter().next())
+        .and_then(|(_, body)| body.as_hash())
+        .is_some_and(|body| body.get_from_str("m_RootOrder").is_some())
+}
+
+/// The ids of the transforms at the root of a scene in their order, only written by the versions
+/// of Unity that do not write m_RootOrder
+fn scene_roots_from_yaml(doc: &YamlUnityDocument) -> Option<SceneRoots> {
+    let root_ids = doc
+        .document
+        .as_hash()?
+        .get_from_str("SceneRoots")?
+        .as_hash()?
+        .get_from_str("m_Roots")?
+        .as_vec()?
+        .iter()
+        .map(|root| root.as_hash()?.get_from_str("fileID")?.as_file_id())
+        .collect::<Option<Vec<Id>>>()?;
+    Some(SceneRoots {
+        id: doc.id.clone(),
+        root_ids,
+    })
+}
+
+/// Sets the root order of the transforms that were read without one to their index in the
+/// m_Children of their father. The transforms at the root of the file are ordered like in
+/// SceneRoots, the ones missing from it come after in the order of the file
+fn derive_root_orders(objects: &mut LinkedHashMap<Id, Object>, ids: &[Id], scene_roots: &[Id]) {
+    let mut unlisted_roots = 0;
+    for id in ids {
+        let Some(Object::Component(Component::Transform(transform))) = objects.get(id) else { continue; };
+        let father_id = transform.get_father_id();
+        let root_order = if !transform.has_parent() {
+            scene_roots
+                .iter()
+                .position(|root_id| root_id == id)
+                .unwrap_or_else(|| {
+                    unlisted_roots += 1;
+                    scene_roots.len() + unlisted_roots - 1
+                })
+        } else {
+            match objects.get(father_id) {
+                Some(Object::Component(Component::Transform(father))) => father
+                    .get_children_ids()
+                    .iter()
+                    .position(|child_id| child_id == id)
+                    .unwrap_or_default(),
+                _ => 0,
+            }
+        };
+        match objects.get_mut(id) {
+            Some(Object::Component(Component::Transform(Transform::Transform3D(t)))) => {
+                t.root_order = root_order as i64
+            }
+            Some(Object::Component(Component::Transform(Transform::RectTransform(t)))) => {
+                t.root_order = root_order as i64
             }
+            _ => {}
         }
     }
+}
+
+/// Objects stored in the file itself have no prefab instance, only the ones merged in from a prefab do
+fn prefab_link_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<PrefabLink> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let prefab_instance_id = map
+        .get_from_str("m_PrefabInstance")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    if prefab_instance_id == "0" {
+        return None;
+    }
+    let corresponding_source =
+        obj_to_reference(map.get_from_str("m_CorrespondingSourceObject")?.as_hash()?)?;
+
+    Some(PrefabLink {
+        prefab_instance_id,
+        corresponding_source,
+    })
+}
+
+/// Returns the name of the document's root key, used for class IDs missing from CLASS_IDS
+fn document_class_name(doc: &YamlUnityDocument) -> Option<&str> {
+    doc.document.as_hash()?.iter().next()?.0.as_str()
+}
+
+/// Parses a component into its dedicated model if it has one, falling back to a generic component otherwise
+fn component_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Component> {
+    let component = match class_name {
+        "MonoBehaviour" => monobehaviour_from_yaml(doc, class_name).map(Component::MonoBehaviour),
+        "Transform" | "RectTransform" => {
+            transform_from_yaml(doc, class_name).map(Component::Transform)
+        }
+        "Camera" => camera_from_yaml(doc, class_name).map(Component::Camera),
+        "Light" => light_from_yaml(doc, class_name).map(Component::Light),
+        "MeshFilter" => mesh_filter_from_yaml(doc, class_name).map(Component::MeshFilter),
+        "MeshRenderer" | "SkinnedMeshRenderer" | "SpriteRenderer" => {
+            renderer_from_yaml(doc, class_name).map(Component::Renderer)
+        }
+        "BoxCollider" | "SphereCollider" | "CapsuleCollider" | "MeshCollider" | "BoxCollider2D"
+        | "CircleCollider2D" => collider_from_yaml(doc, class_name).map(Component::Collider),
+        "Rigidbody" | "Rigidbody2D" => {
+            rigidbody_from_yaml(doc, class_name).map(Component::Rigidbody)
+        }
+        "Canvas" => canvas_from_yaml(doc, class_name).map(Component::Canvas),
+        _ => None,
+    };
 
-    Some(repo.into())
+    // a transform read as a generic component would take its game object out of the hierarchy
+    if component.is_none() && matches!(class_name, "Transform" | "RectTransform") {
+        return None;
+    }
+    component.or_else(|| generic_component_from_yaml(doc, class_name).map(Component::Generic))
 }
 
 fn game_object_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<GameObject> {
@@ -173,14 +713,11 @@ fn game_object_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Ga
         .to_vec()
         .iter()
         .map(|y| {
-            let map = y
-                .as_hash()
-                .unwrap()
-                .get_from_str("component")
-                .unwrap()
-                .as_hash()
-                .unwrap();
-            map.get_from_str("fileID")?.as_file_id()
+            y.as_hash()?
+                .get_from_str("component")?
+                .as_hash()?
+                .get_from_str("fileID")?
+                .as_file_id()
         })
         .collect::<Option<Vec<Id>>>()?;
 
@@ -191,7 +728,76 @@ fn game_object_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Ga
         active,
         layer,
         tag,
-        transform_id: "".to_owned(),
+    })
+}
+
+fn stripped_object_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<StrippedObject> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let corresponding_source =
+        obj_to_reference(map.get_from_str("m_CorrespondingSourceObject")?.as_hash()?)?;
+    let prefab_instance_id = map
+        .get_from_str("m_PrefabInstance")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+
+    Some(StrippedObject {
+        id: doc.id.clone(),
+        class_name: class_name.to_owned(),
+        corresponding_source,
+        prefab_instance_id,
+    })
+}
+
+fn prefab_instance_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<PrefabInstance> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let source_prefab = obj_to_reference(map.get_from_str("m_SourcePrefab")?.as_hash()?)?;
+    let modification = map.get_from_str("m_Modification")?.as_hash()?;
+    let transform_parent_id = modification
+        .get_from_str("m_TransformParent")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let modifications = modification
+        .get_from_str("m_Modifications")?
+        .as_vec()?
+        .iter()
+        .map(|y| {
+            let map = y.as_hash()?;
+            Some(PropertyModification {
+                target: obj_to_reference(map.get_from_str("target")?.as_hash()?)?,
+                property_path: map.get_from_str("propertyPath")?.as_str()?.to_owned(),
+                value: map.get_from_str("value")?.clone(),
+                object_reference: obj_to_reference(
+                    map.get_from_str("objectReference")?.as_hash()?,
+                )?,
+            })
+        })
+        .collect::<Option<Vec<PropertyModification>>>()?;
+    let removed_components = modification
+        .get_from_str("m_RemovedComponents")?
+        .as_vec()?
+        .iter()
+        .map(|y| obj_to_reference(y.as_hash()?))
+        .collect::<Option<Vec<ObjectReference>>>()?;
+    let removed_game_objects = match modification.get_from_str("m_RemovedGameObjects") {
+        Some(removed) => removed
+            .as_vec()?
+            .iter()
+            .map(|y| obj_to_reference(y.as_hash()?))
+            .collect::<Option<Vec<ObjectReference>>>()?,
+        None => vec![],
+    };
+
+    Some(PrefabInstance {
+        id: doc.id.clone(),
+        source_prefab,
+        transform_parent_id,
+        modifications,
+        removed_components,
+        removed_game_objects,
     })
 }
 
@@ -204,7 +810,7 @@ fn monobehaviour_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<
     let mut comp = MonoBehaviour {
         id: doc.id.clone(),
         enabled: false,
-        fields: hash_map![],
+        fields: LinkedHashMap::new(),
         game_object_id: "".to_string(),
         script_guid: "".to_string(),
     };
@@ -216,49 +822,99 @@ fn monobehaviour_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<
             "m_GameObject" => {
                 comp.game_object_id = value.as_hash()?.get_from_str("fileID")?.as_file_id()?
             }
+            // a removed script is written as a null reference without a guid
             "m_Script" => {
-                comp.script_guid = value.as_hash()?.get_from_str("guid")?.as_guid()?;
+                if let Some(guid) = value.as_hash()?.get_from_str("guid") {
+                    comp.script_guid = guid.as_guid()?;
+                }
             }
             LAST_COMMON_MONO_FIELD_NAME => past_common = true,
-            _ if past_common => match value {
-                Yaml::Hash(map) => {
-                    if let Some(vec4) = obj_to_vec4(map) {
-                        comp.fields.insert(key.to_owned(), Field::Vector4(vec4));
-                    } else if let Some(vec3) = obj_to_vec3(map) {
-                        comp.fields.insert(key.to_owned(), Field::Vector3(vec3));
-                    } else if let Some(vec2) = obj_to_vec2(map) {
-                        comp.fields.insert(key.to_owned(), Field::Vector2(vec2));
-                    } else {
-                        comp.fields
-                            .insert(key.to_owned(), Field::Yaml(value.clone()));
-                    }
-                }
-                Yaml::Real(f) => {
-                    let f = f.parse();
-                    assert!(f.is_ok());
-                    comp.fields.insert(key.to_owned(), Field::F64(f.unwrap()));
-                }
-                Yaml::Integer(i) => {
-                    comp.fields.insert(key.to_owned(), Field::I64(*i));
-                }
-                Yaml::String(s) => {
-                    comp.fields.insert(key.to_owned(), Field::Str(s.clone()));
-                }
-                Yaml::Boolean(b) => {
-                    comp.fields.insert(key.to_owned(), Field::Bool(*b));
-                }
-                Yaml::BadValue => return None,
-                _ => {
-                    comp.fields
-                        .insert(key.to_owned(), Field::Yaml(value.clone()));
-                }
-            },
+            _ if past_common => {
+                comp.fields.insert(key.to_owned(), field_from_yaml(value)?);
+            }
             _ => {}
         }
     }
     Some(comp)
 }
 
+/// Fields that every component has and that are stored outside of the generic field map
+const COMMON_COMPONENT_FIELD_NAMES: [&str; 7] = [
+    "m_ObjectHideFlags",
+    "m_CorrespondingSourceObject",
+    "m_PrefabInstance",
+    "m_PrefabAsset",
+    "m_GameObject",
+    "m_Enabled",
+    "serializedVersion",
+];
+
+fn generic_component_from_yaml(
+    doc: &YamlUnityDocument,
+    class_name: &str,
+) -> Option<GenericComponent> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let enabled = match map.get_from_str("m_Enabled") {
+        Some(value) => Some(value.as_i64()? > 0),
+        None => None,
+    };
+
+    let mut fields = LinkedHashMap::new();
+    for (key, value) in map.iter() {
+        let key = key.as_str()?;
+        if !COMMON_COMPONENT_FIELD_NAMES.contains(&key) {
+            fields.insert(key.to_owned(), field_from_yaml(value)?);
+        }
+    }
+
+    Some(GenericComponent {
+        id: doc.id.clone(),
+        class_name: class_name.to_owned(),
+        enabled,
+        game_object_id,
+        fields,
+    })
+}
+
+/// Reads a serialized field, values that are not one of the known kinds are kept as they are
+pub fn field_from_yaml(value: &Yaml) -> Option<Field> {
+    match value {
+        Yaml::Hash(map) => {
+            let is_reference = map
+                .iter()
+                .all(|(key, _)| matches!(key.as_str(), Some("fileID" | "guid" | "type")));
+            if let Some(reference) = obj_to_reference(map).filter(|_| is_reference) {
+                Some(Field::Reference(reference))
+            } else if let Some(color) = obj_to_color(map).filter(|_| map.map.len() == 4) {
+                Some(Field::Color(color))
+            } else if let Some(vec4) = obj_to_vec4(map) {
+                Some(Field::Vector4(vec4))
+            } else if let Some(vec3) = obj_to_vec3(map) {
+                Some(Field::Vector3(vec3))
+            } else if let Some(vec2) = obj_to_vec2(map) {
+                Some(Field::Vector2(vec2))
+            } else {
+                Some(Field::Yaml(value.clone()))
+            }
+        }
+        Yaml::Real(f) => Some(Field::F64(f.parse().ok()?)),
+        Yaml::Integer(i) => Some(Field::I64(*i)),
+        Yaml::String(s) => Some(Field::Str(s.clone())),
+        Yaml::Boolean(b) => Some(Field::Bool(*b)),
+        Yaml::Array(items) => Some(Field::Array(
+            items.iter().map(field_from_yaml).collect::<Option<_>>()?,
+        )),
+        Yaml::BadValue => None,
+        _ => Some(Field::Yaml(value.clone())),
+    }
+}
+
 fn transform_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Transform> {
     match class_name {
         "Transform" => Some(Transform::Transform3D(transform_3d_from_yaml(
@@ -277,7 +933,11 @@ fn transform_3d_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<T
     let local_rotation = obj_to_vec4(map.get_from_str("m_LocalRotation")?.as_hash()?)?;
     let local_position = obj_to_vec3(map.get_from_str("m_LocalPosition")?.as_hash()?)?;
     let local_scale = obj_to_vec3(map.get_from_str("m_LocalScale")?.as_hash()?)?;
-    let root_order = map.get_from_str("m_RootOrder")?.as_i64()?;
+    // newer versions of Unity do not write it, see derive_root_orders
+    let root_order = map
+        .get_from_str("m_RootOrder")
+        .and_then(|order| order.as_i64())
+        .unwrap_or_default();
     let game_object_id = map
         .get_from_str("m_GameObject")?
         .as_hash()?
@@ -293,17 +953,9 @@ fn transform_3d_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<T
     let children_ids = map
         .get_from_str("m_Children")?
         .as_vec()?
-        .to_vec()
         .iter()
-        .map(|y| {
-            y.as_hash()
-                .unwrap()
-                .get_from_str("fileID")
-                .unwrap()
-                .as_file_id()
-                .unwrap()
-        })
-        .collect();
+        .map(|y| y.as_hash()?.get_from_str("fileID")?.as_file_id())
+        .collect::<Option<Vec<Id>>>()?;
 
     Some(Transform3D {
         id: doc.id.clone(),
@@ -328,7 +980,11 @@ fn rect_transform_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option
     let anchored_position = obj_to_vec2(map.get_from_str("m_AnchoredPosition")?.as_hash()?)?;
     let size_delta = obj_to_vec2(map.get_from_str("m_SizeDelta")?.as_hash()?)?;
     let pivot = obj_to_vec2(map.get_from_str("m_Pivot")?.as_hash()?)?;
-    let root_order = map.get_from_str("m_RootOrder")?.as_i64()?;
+    // newer versions of Unity do not write it, see derive_root_orders
+    let root_order = map
+        .get_from_str("m_RootOrder")
+        .and_then(|order| order.as_i64())
+        .unwrap_or_default();
     let game_object_id = map
         .get_from_str("m_GameObject")?
         .as_hash()?
@@ -344,17 +1000,9 @@ fn rect_transform_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option
     let children_ids = map
         .get_from_str("m_Children")?
         .as_vec()?
-        .to_vec()
         .iter()
-        .map(|y| {
-            y.as_hash()
-                .unwrap()
-                .get_from_str("fileID")
-                .unwrap()
-                .as_file_id()
-                .unwrap()
-        })
-        .collect();
+        .map(|y| y.as_hash()?.get_from_str("fileID")?.as_file_id())
+        .collect::<Option<Vec<Id>>>()?;
 
     Some(RectTransform {
         id: doc.id.clone(),
@@ -372,3 +1020,394 @@ fn rect_transform_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option
         game_object_id,
     })
 }
+
+fn camera_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Camera> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let enabled = map.get_from_str("m_Enabled")?.as_i64()? > 0;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let clear_flags = map.get_from_str("m_ClearFlags")?.as_i64()?;
+    let background_color = obj_to_color(map.get_from_str("m_BackGroundColor")?.as_hash()?)?;
+    let orthographic = map.get_from_str("orthographic")?.as_i64()? > 0;
+    let orthographic_size = map.get_from_str("orthographic size")?.as_f32()?;
+    let field_of_view = map.get_from_str("field of view")?.as_f32()?;
+    let near_clip_plane = map.get_from_str("near clip plane")?.as_f32()?;
+    let far_clip_plane = map.get_from_str("far clip plane")?.as_f32()?;
+    let depth = map.get_from_str("m_Depth")?.as_f32()?;
+    let culling_mask = map
+        .get_from_str("m_CullingMask")?
+        .as_hash()?
+        .get_from_str("m_Bits")?
+        .as_i64()?;
+    let target_display = map.get_from_str("m_TargetDisplay")?.as_i64()?;
+
+    Some(Camera {
+        id: doc.id.clone(),
+        enabled,
+        game_object_id,
+        clear_flags,
+        background_color,
+        orthographic,
+        orthographic_size,
+        field_of_view,
+        near_clip_plane,
+        far_clip_plane,
+        depth,
+        culling_mask,
+        target_display,
+    })
+}
+
+fn light_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Light> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let enabled = map.get_from_str("m_Enabled")?.as_i64()? > 0;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let light_type = map.get_from_str("m_Type")?.as_i64()?;
+    let color = obj_to_color(map.get_from_str("m_Color")?.as_hash()?)?;
+    let intensity = map.get_from_str("m_Intensity")?.as_f32()?;
+    let range = map.get_from_str("m_Range")?.as_f32()?;
+    let spot_angle = map.get_from_str("m_SpotAngle")?.as_f32()?;
+    let shadows_type = map
+        .get_from_str("m_Shadows")?
+        .as_hash()?
+        .get_from_str("m_Type")?
+        .as_i64()?;
+
+    Some(Light {
+        id: doc.id.clone(),
+        enabled,
+        game_object_id,
+        light_type,
+        color,
+        intensity,
+        range,
+        spot_angle,
+        shadows_type,
+    })
+}
+
+fn mesh_filter_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<MeshFilter> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let mesh = obj_to_reference(map.get_from_str("m_Mesh")?.as_hash()?)?;
+
+    Some(MeshFilter {
+        id: doc.id.clone(),
+        game_object_id,
+        mesh,
+    })
+}
+
+fn renderer_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Renderer> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let enabled = map.get_from_str("m_Enabled")?.as_i64()? > 0;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let materials = map
+        .get_from_str("m_Materials")?
+        .as_vec()?
+        .iter()
+        .map(|y| obj_to_reference(y.as_hash()?))
+        .collect::<Option<Vec<ObjectReference>>>()?;
+
+    match class_name {
+        "MeshRenderer" => Some(Renderer::Mesh(MeshRenderer {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            cast_shadows: map.get_from_str("m_CastShadows")?.as_i64()?,
+            receive_shadows: map.get_from_str("m_ReceiveShadows")?.as_i64()? > 0,
+            materials,
+        })),
+        "SkinnedMeshRenderer" => Some(Renderer::SkinnedMesh(SkinnedMeshRenderer {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            cast_shadows: map.get_from_str("m_CastShadows")?.as_i64()?,
+            receive_shadows: map.get_from_str("m_ReceiveShadows")?.as_i64()? > 0,
+            materials,
+            mesh: obj_to_reference(map.get_from_str("m_Mesh")?.as_hash()?)?,
+            root_bone: obj_to_reference(map.get_from_str("m_RootBone")?.as_hash()?)?,
+            update_when_offscreen: map.get_from_str("m_UpdateWhenOffscreen")?.as_i64()? > 0,
+        })),
+        "SpriteRenderer" => Some(Renderer::Sprite(SpriteRenderer {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            materials,
+            sprite: obj_to_reference(map.get_from_str("m_Sprite")?.as_hash()?)?,
+            color: obj_to_color(map.get_from_str("m_Color")?.as_hash()?)?,
+            flip_x: map.get_from_str("m_FlipX")?.as_i64()? > 0,
+            flip_y: map.get_from_str("m_FlipY")?.as_i64()? > 0,
+            sorting_order: map.get_from_str("m_SortingOrder")?.as_i64()?,
+        })),
+        _ => None,
+    }
+}
+
+fn collider_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Collider> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let enabled = map.get_from_str("m_Enabled")?.as_i64()? > 0;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let is_trigger = map.get_from_str("m_IsTrigger")?.as_i64()? > 0;
+    let material = obj_to_reference(map.get_from_str("m_Material")?.as_hash()?)?;
+
+    match class_name {
+        "BoxCollider" => Some(Collider::BoxCollider(BoxCollider {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            is_trigger,
+            material,
+            center: obj_to_vec3(map.get_from_str("m_Center")?.as_hash()?)?,
+            size: obj_to_vec3(map.get_from_str("m_Size")?.as_hash()?)?,
+        })),
+        "SphereCollider" => Some(Collider::SphereCollider(SphereCollider {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            is_trigger,
+            material,
+            center: obj_to_vec3(map.get_from_str("m_Center")?.as_hash()?)?,
+            radius: map.get_from_str("m_Radius")?.as_f32()?,
+        })),
+        "CapsuleCollider" => Some(Collider::CapsuleCollider(CapsuleCollider {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            is_trigger,
+            material,
+            center: obj_to_vec3(map.get_from_str("m_Center")?.as_hash()?)?,
+            radius: map.get_from_str("m_Radius")?.as_f32()?,
+            height: map.get_from_str("m_Height")?.as_f32()?,
+            direction: map.get_from_str("m_Direction")?.as_i64()?,
+        })),
+        "MeshCollider" => Some(Collider::MeshCollider(MeshCollider {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            is_trigger,
+            material,
+            convex: map.get_from_str("m_Convex")?.as_i64()? > 0,
+            mesh: obj_to_reference(map.get_from_str("m_Mesh")?.as_hash()?)?,
+        })),
+        "BoxCollider2D" => Some(Collider::BoxCollider2D(BoxCollider2D {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            is_trigger,
+            material,
+            offset: obj_to_vec2(map.get_from_str("m_Offset")?.as_hash()?)?,
+            size: obj_to_vec2(map.get_from_str("m_Size")?.as_hash()?)?,
+        })),
+        "CircleCollider2D" => Some(Collider::CircleCollider2D(CircleCollider2D {
+            id: doc.id.clone(),
+            enabled,
+            game_object_id,
+            is_trigger,
+            material,
+            offset: obj_to_vec2(map.get_from_str("m_Offset")?.as_hash()?)?,
+            radius: map.get_from_str("m_Radius")?.as_f32()?,
+        })),
+        _ => None,
+    }
+}
+
+fn rigidbody_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Rigidbody> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    // Unity 6 renamed drag to damping
+    let angular_drag = map
+        .get_from_str("m_AngularDrag")
+        .or_else(|| map.get_from_str("m_AngularDamping"))?
+        .as_f32()?;
+
+    match class_name {
+        "Rigidbody" => Some(Rigidbody::Rigidbody3D(Rigidbody3D {
+            id: doc.id.clone(),
+            game_object_id,
+            mass: map.get_from_str("m_Mass")?.as_f32()?,
+            drag: map
+                .get_from_str("m_Drag")
+                .or_else(|| map.get_from_str("m_LinearDamping"))?
+                .as_f32()?,
+            angular_drag,
+            use_gravity: map.get_from_str("m_UseGravity")?.as_i64()? > 0,
+            is_kinematic: map.get_from_str("m_IsKinematic")?.as_i64()? > 0,
+            interpolate: map.get_from_str("m_Interpolate")?.as_i64()?,
+            constraints: map.get_from_str("m_Constraints")?.as_i64()?,
+            collision_detection: map.get_from_str("m_CollisionDetection")?.as_i64()?,
+        })),
+        "Rigidbody2D" => Some(Rigidbody::Rigidbody2D(Rigidbody2D {
+            id: doc.id.clone(),
+            game_object_id,
+            body_type: map.get_from_str("m_BodyType")?.as_i64()?,
+            simulated: map.get_from_str("m_Simulated")?.as_i64()? > 0,
+            mass: map.get_from_str("m_Mass")?.as_f32()?,
+            linear_drag: map
+                .get_from_str("m_LinearDrag")
+                .or_else(|| map.get_from_str("m_LinearDamping"))?
+                .as_f32()?,
+            angular_drag,
+            gravity_scale: map.get_from_str("m_GravityScale")?.as_f32()?,
+            interpolate: map.get_from_str("m_Interpolate")?.as_i64()?,
+            constraints: map.get_from_str("m_Constraints")?.as_i64()?,
+            collision_detection: map.get_from_str("m_CollisionDetection")?.as_i64()?,
+        })),
+        _ => None,
+    }
+}
+
+fn canvas_from_yaml(doc: &YamlUnityDocument, class_name: &str) -> Option<Canvas> {
+    let map = doc.document.as_hash()?;
+    let map = map.get_from_str(class_name)?.as_hash()?;
+    let enabled = map.get_from_str("m_Enabled")?.as_i64()? > 0;
+    let game_object_id = map
+        .get_from_str("m_GameObject")?
+        .as_hash()?
+        .get_from_str("fileID")?
+        .as_file_id()?;
+    let render_mode = map.get_from_str("m_RenderMode")?.as_i64()?;
+    let camera = obj_to_reference(map.get_from_str("m_Camera")?.as_hash()?)?;
+    let plane_distance = map.get_from_str("m_PlaneDistance")?.as_f32()?;
+    let pixel_perfect = map.get_from_str("m_PixelPerfect")?.as_i64()? > 0;
+    let sorting_order = map.get_from_str("m_SortingOrder")?.as_i64()?;
+    let target_display = map.get_from_str("m_TargetDisplay")?.as_i64()?;
+
+    Some(Canvas {
+        id: doc.id.clone(),
+        enabled,
+        game_object_id,
+        render_mode,
+        camera,
+        plane_distance,
+        pixel_perfect,
+        sorting_order,
+        target_display,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unity::{
+        test_util::{read_repository, SCENE},
+        vector::Vector3,
+    };
+
+    #[test]
+    fn component_missing_typed_fields_is_read_as_generic() {
+        // a camera without the fields of the typed model, like one written by an older version
+        let scene = format!(
+            "{}--- !u!20 &122\nCamera:\n  m_GameObject: {{fileID: 120}}\n  m_Enabled: 1\n  m_ClearFlags: 1\n",
+            SCENE
+        );
+        let repo = read_repository(&scene);
+
+        match repo.get_component(&"122".to_owned()) {
+            Some(Component::Generic(generic)) => {
+                assert_eq!(generic.class_name, "Camera");
+                assert_eq!(generic.game_object_id, "120");
+                assert!(generic.fields.contains_key("m_ClearFlags"));
+            }
+            other => panic!("expected a generic camera, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn world_transform_goes_through_the_parents() {
+        // Player is moved, turned 90 degrees around y and scaled by 2, Weapon is turned the same
+        let scene = SCENE
+            .replacen(
+                "m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}
+  m_LocalPosition: {x: 0, y: 0, z: 0}
+  m_LocalScale: {x: 1, y: 1, z: 1}",
+                "m_LocalRotation: {x: 0, y: 0.70710677, z: 0, w: 0.70710677}
+  m_LocalPosition: {x: 1, y: 0, z: 0}
+  m_LocalScale: {x: 2, y: 2, z: 2}",
+                1,
+            )
+            .replacen(
+                "m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}
+  m_LocalPosition: {x: 0.5, y: 0, z: 0}",
+                "m_LocalRotation: {x: 0, y: 0.70710677, z: 0, w: 0.70710677}
+  m_LocalPosition: {x: 0.5, y: 0, z: 0}",
+                1,
+            );
+        let repo = read_repository(&scene);
+
+        let world = repo.get_world_transform(&"111".to_owned()).unwrap();
+        // the offset of 0.5 on x is scaled to 1, turned onto -z and moved by the parent
+        assert_eq!(
+            world.position.rounded(4),
+            Vector3 {
+                x: 1.0,
+                y: 0.0,
+                z: -1.0
+            }
+        );
+        // two quarter turns around y
+        let rotation = world.rotation;
+        assert!(rotation.x.abs() < 1e-5 && rotation.z.abs() < 1e-5 && rotation.w.abs() < 1e-5);
+        assert!((rotation.y.abs() - 1.0).abs() < 1e-5);
+        assert_eq!(
+            world.lossy_scale,
+            Vector3 {
+                x: 2.0,
+                y: 2.0,
+                z: 2.0
+            }
+        );
+    }
+
+    #[test]
+    fn guids_read_as_numbers_are_kept_as_written() {
+        let dir = std::env::temp_dir().join(format!("unity_tui_meta_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for guid in [
+            "12345678901234567890123456789012",
+            "12e45678901234567890123456789012",
+        ] {
+            let meta_path = dir.join("Player.prefab.meta");
+            std::fs::write(
+                &meta_path,
+                format!("fileFormatVersion: 2\nguid: {}\nPrefabImporter:\n", guid),
+            )
+            .unwrap();
+
+            let asset =
+                asset_from_meta_file(&meta_path, Path::new("Assets/Player.prefab")).unwrap();
+            assert_eq!(asset.guid, guid);
+            assert_eq!(asset.importer.as_deref(), Some("PrefabImporter"));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,526 @@
+use crate::unity::converters::helpers::f64_to_yaml;
+use std::collections::HashSet;
+use unity_yaml_rust::{yaml::Hash, Yaml};
+
+const MODIFIERS: [&str; 10] = [
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "new",
+    "volatile",
+    "unsafe",
+    "static",
+    "const",
+    "readonly",
+];
+/// Statements starting with these are not fields
+const NOT_FIELD_KEYWORDS: [&str; 12] = [
+    "class",
+    "struct",
+    "enum",
+    "interface",
+    "delegate",
+    "event",
+    "using",
+    "abstract",
+    "virtual",
+    "override",
+    "extern",
+    "operator",
+];
+const OBJECT_TYPES: [&str; 36] = [
+    "Object",
+    "GameObject",
+    "Component",
+    "Behaviour",
+    "MonoBehaviour",
+    "ScriptableObject",
+    "Transform",
+    "RectTransform",
+    "Rigidbody",
+    "Rigidbody2D",
+    "Collider",
+    "Collider2D",
+    "BoxCollider",
+    "SphereCollider",
+    "CapsuleCollider",
+    "MeshCollider",
+    "Camera",
+    "Light",
+    "Renderer",
+    "MeshRenderer",
+    "SkinnedMeshRenderer",
+    "SpriteRenderer",
+    "MeshFilter",
+    "Animator",
+    "AudioSource",
+    "AudioClip",
+    "Material",
+    "Mesh",
+    "Sprite",
+    "Texture",
+    "Texture2D",
+    "Shader",
+    "AnimationClip",
+    "RuntimeAnimatorController",
+    "TextAsset",
+    "ParticleSystem",
+];
+
+/// Returns the fields Unity serializes for the class declared in the script, with the values a new
+/// component gets. Fields of types that are not known are left out, Unity adds them when it saves
+/// the file again
+pub fn get_serialized_fields(source: &str, class_name: &str) -> Vec<(String, Yaml)> {
+    let source = strip_comments(source)
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let enums = get_declared_names(&source, "enum");
+    let Some(body) = find_class_body(&source, class_name) else { return vec![]; };
+    split_members(body)
+        .iter()
+        .filter_map(|member| parse_field(member, &enums))
+        .flatten()
+        .collect()
+}
+
+/// Replaces comments with spaces, string literals are kept as they are
+fn strip_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote = None;
+    while let Some(ch) = chars.next() {
+        if let Some(q) = quote {
+            result.push(ch);
+            if ch == '\\' {
+                result.extend(chars.next());
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match (ch, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|ch| *ch != '\n') {
+                    chars.next();
+                }
+                result.push(' ');
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for ch in chars.by_ref() {
+                    if last == '*' && ch == '/' {
+                        break;
+                    }
+                    last = ch;
+                }
+                result.push(' ');
+            }
+            ('"' | '\'', _) => {
+                quote = Some(ch);
+                result.push(ch);
+            }
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+/// Returns the names following the keyword, like the enums declared in the script
+fn get_declared_names(source: &str, keyword: &str) -> HashSet<String> {
+    let words = source
+        .split(|ch: char| !is_identifier_char(ch))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>();
+    words
+        .windows(2)
+        .filter(|pair| pair[0] == keyword)
+        .map(|pair| pair[1].to_owned())
+        .collect()
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Returns the text between the braces of the class declaration
+fn find_class_body<'a>(source: &'a str, class_name: &str) -> Option<&'a str> {
+    let mut search_from = 0;
+    let start = loop {
+        let idx = search_from + source[search_from..].find("class")?;
+        search_from = idx + "class".len();
+        let before_ok = source[..idx]
+            .chars()
+            .last()
+            .is_none_or(|ch| !is_identifier_char(ch));
+        let rest = &source[search_from..];
+        let name = rest.trim_start();
+        if before_ok
+            && rest.len() != name.len()
+            && name.starts_with(class_name)
+            && !name[class_name.len()..]
+                .chars()
+                .next()
+                .is_some_and(is_identifier_char)
+        {
+            break search_from + source[search_from..].find('{')? + 1;
+        }
+    };
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, ch) in source[start..].char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, ch) {
+            (Some(_), '\\') => escaped = true,
+            (Some(q), ch) if ch == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '{') => depth += 1,
+            (None, '}') if depth == 0 => return Some(&source[start..start + idx]),
+            (None, '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits the body of a class in the statements declaring its members. Members with a body, like
+/// methods, properties and nested types, are left out
+fn split_members(body: &str) -> Vec<String> {
+    let mut members = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    // the text before a block, only kept when the block is an initializer
+    let mut in_initializer = false;
+    for ch in body.chars() {
+        if let Some(q) = quote {
+            current.push(ch);
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' => {
+                quote = Some(ch);
+                current.push(ch);
+            }
+            '{' => {
+                if depth == 0 {
+                    in_initializer = has_initializer(&current);
+                }
+                depth += 1;
+                current.push(ch);
+            }
+            '}' => {
+                depth -= 1;
+                current.push(ch);
+                if depth == 0 && !in_initializer {
+                    current.clear();
+                }
+            }
+            ';' if depth == 0 => {
+                members.push(current.trim().to_owned());
+                current.clear();
+            }
+            ch => current.push(ch),
+        }
+    }
+    members
+}
+
+fn has_initializer(declaration: &str) -> bool {
+    find_assignment(declaration).is_some()
+}
+
+/// Returns the index of the `=` starting the initializer, `==`, `=>` and comparisons are skipped
+fn find_assignment(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    (0..bytes.len()).find(|idx| {
+        bytes[*idx] == b'='
+            && !matches!(bytes.get(idx + 1), Some(b'=' | b'>'))
+            && !matches!(
+                idx.checked_sub(1).map(|prev| bytes[prev]),
+                Some(b'=' | b'!' | b'<' | b'>')
+            )
+    })
+}
+
+/// Returns the serialized fields declared by the statement, None if it does not declare fields
+fn parse_field(member: &str, enums: &HashSet<String>) -> Option<Vec<(String, Yaml)>> {
+    let (attributes, declaration) = split_attributes(member);
+    if declaration.contains("=>") {
+        return None;
+    }
+    let mut serialize_field = false;
+    for attribute in attributes.iter() {
+        match attribute.as_str() {
+            "SerializeField" => serialize_field = true,
+            "NonSerialized" | "SerializeReference" => return None,
+            _ => {}
+        }
+    }
+
+    let mut rest = declaration.trim();
+    let mut modifiers = vec![];
+    loop {
+        let word = rest.split(|ch: char| !is_identifier_char(ch)).next()?;
+        if NOT_FIELD_KEYWORDS.contains(&word) {
+            return None;
+        }
+        if !MODIFIERS.contains(&word) {
+            break;
+        }
+        modifiers.push(word);
+        rest = rest[word.len()..].trim_start();
+    }
+    if modifiers
+        .iter()
+        .any(|modifier| ["static", "const", "readonly"].contains(modifier))
+        || !(modifiers.contains(&"public") || serialize_field)
+    {
+        return None;
+    }
+
+    let declarators = split_top_level(rest, ',');
+    let first = declarators.first()?;
+    let (first_declaration, first_initializer) = split_initializer(first);
+    if first_declaration.contains('(') {
+        return None;
+    }
+    let name_start = first_declaration
+        .rfind(|ch: char| !is_identifier_char(ch))
+        .map_or(0, |idx| idx + 1);
+    let field_type = first_declaration[..name_start]
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<String>();
+    if field_type.is_empty() {
+        return None;
+    }
+
+    let mut fields = vec![];
+    let first = (&first_declaration[name_start..], first_initializer);
+    let others = declarators[1..].iter().map(|d| split_initializer(d));
+    for (name, initializer) in std::iter::once(first).chain(others) {
+        let name = name.trim();
+        if name.is_empty() || !name.chars().all(is_identifier_char) {
+            return None;
+        }
+        if let Some(value) = default_value(&field_type, initializer, enums) {
+            fields.push((name.to_owned(), value));
+        }
+    }
+    Some(fields)
+}
+
+/// Splits the attributes in front of a declaration from it, the attributes are returned by name
+fn split_attributes(member: &str) -> (Vec<String>, &str) {
+    let mut attributes = vec![];
+    let mut rest = member.trim_start();
+    while rest.starts_with('[') {
+        let mut depth = 0;
+        let Some(end) = rest.find(|ch| {
+            match ch {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        }) else {
+            break;
+        };
+        for attribute in split_top_level(&rest[1..end], ',') {
+            let name = attribute.split('(').next().unwrap_or("").trim();
+            let name = name.rsplit('.').next().unwrap_or(name);
+            let name = name.strip_suffix("Attribute").unwrap_or(name);
+            attributes.push(name.to_owned());
+        }
+        rest = rest[end + 1..].trim_start();
+    }
+    (attributes, rest)
+}
+
+/// Splits the text at the separator, except inside brackets of any kind
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    let mut quote = None;
+    for (idx, ch) in text.char_indices() {
+        match (quote, ch) {
+            (Some(q), ch) if ch == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '(' | '[' | '{' | '<') => depth += 1,
+            (None, ')' | ']' | '}' | '>') => depth -= 1,
+            (None, ch) if ch == separator && depth == 0 => {
+                parts.push(&text[start..idx]);
+                start = idx + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn split_initializer(declarator: &str) -> (&str, Option<&str>) {
+    match find_assignment(declarator) {
+        Some(idx) => (declarator[..idx].trim(), Some(declarator[idx + 1..].trim())),
+        None => (declarator.trim(), None),
+    }
+}
+
+/// Returns the value Unity writes for a new field of the type, the initializer is used when it is
+/// a literal. None for types that are not known
+fn default_value(
+    field_type: &str,
+    initializer: Option<&str>,
+    enums: &HashSet<String>,
+) -> Option<Yaml> {
+    let field_type = field_type
+        .strip_prefix("UnityEngine.")
+        .unwrap_or(field_type);
+    if field_type.ends_with("[]") || field_type.starts_with("List<") {
+        return Some(Yaml::Array(vec![]));
+    }
+    let initializer = initializer.map(|init| init.trim());
+    let value = match field_type {
+        "int" | "uint" | "long" | "ulong" | "short" | "ushort" | "byte" | "sbyte" => {
+            Yaml::Integer(initializer.and_then(parse_integer).unwrap_or(0))
+        }
+        "float" | "double" => f64_to_yaml(initializer.and_then(parse_float).unwrap_or(0.0)),
+        "bool" => Yaml::Integer((initializer == Some("true")) as i64),
+        "string" => match initializer.and_then(parse_string) {
+            Some(s) if !s.is_empty() => Yaml::String(s),
+            // Unity writes nothing after the key for empty strings
+            _ => Yaml::Null,
+        },
+        "Vector2" | "Vector2Int" => struct_value(&["x", "y"], field_type, initializer),
+        "Vector3" | "Vector3Int" => struct_value(&["x", "y", "z"], field_type, initializer),
+        "Vector4" | "Quaternion" => struct_value(&["x", "y", "z", "w"], field_type, initializer),
+        "Color" | "Color32" => struct_value(&["r", "g", "b", "a"], field_type, initializer),
+        "LayerMask" => {
+            let mut hash = Hash::new(true);
+            hash.insert(
+                Yaml::String("serializedVersion".to_owned()),
+                Yaml::Integer(2),
+            );
+            hash.insert(Yaml::String("m_Bits".to_owned()), Yaml::Integer(0));
+            Yaml::Hash(hash)
+        }
+        field_type if OBJECT_TYPES.contains(&field_type) => {
+            let mut hash = Hash::new(false);
+            hash.insert(Yaml::String("fileID".to_owned()), Yaml::Integer(0));
+            Yaml::Hash(hash)
+        }
+        field_type if enums.contains(field_type) => Yaml::Integer(0),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn parse_integer(literal: &str) -> Option<i64> {
+    literal.trim_end_matches(['u', 'U', 'l', 'L']).parse().ok()
+}
+
+fn parse_float(literal: &str) -> Option<f64> {
+    literal
+        .trim_end_matches(['f', 'F', 'd', 'D', 'm', 'M'])
+        .parse()
+        .ok()
+}
+
+fn parse_string(literal: &str) -> Option<String> {
+    let content = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::new();
+    let mut chars = content.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            'n' => result.push('\n'),
+            't' => result.push('\t'),
+            'r' => result.push('\r'),
+            ch => result.push(ch),
+        }
+    }
+    Some(result)
+}
+
+/// Returns the value of a vector or color, from `new T(...)` with literal arguments or from one of
+/// its named constants
+fn struct_value(components: &[&str], field_type: &str, initializer: Option<&str>) -> Yaml {
+    let color = components[0] == "r";
+    let mut values = vec![0.0; components.len()];
+    if let Some(init) = initializer {
+        if let Some(args) = init
+            .strip_prefix("new")
+            .map(|rest| rest.trim_start())
+            .and_then(|rest| rest.strip_prefix(field_type))
+            .and_then(|rest| rest.trim().strip_prefix('('))
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let args = split_top_level(args, ',')
+                .into_iter()
+                .map(|arg| parse_float(arg.trim()))
+                .collect::<Option<Vec<f64>>>()
+                .unwrap_or_default();
+            for (value, arg) in values.iter_mut().zip(args.iter()) {
+                *value = *arg;
+            }
+            // colors made from three components are opaque
+            if color && args.len() == 3 {
+                values[3] = if field_type == "Color32" { 255.0 } else { 1.0 };
+            }
+        } else if let Some(constant) = init
+            .strip_prefix(field_type)
+            .and_then(|rest| rest.strip_prefix('.'))
+        {
+            values = named_constant(constant, components.len()).unwrap_or(values);
+        }
+    }
+    let mut hash = Hash::new(false);
+    for (component, value) in components.iter().zip(values) {
+        hash.insert(Yaml::String((*component).to_owned()), f64_to_yaml(value));
+    }
+    Yaml::Hash(hash)
+}
+
+fn named_constant(name: &str, len: usize) -> Option<Vec<f64>> {
+    let values = match name {
+        "zero" | "clear" => vec![0.0, 0.0, 0.0, 0.0],
+        "one" => vec![1.0, 1.0, 1.0, 1.0],
+        "identity" => vec![0.0, 0.0, 0.0, 1.0],
+        "up" => vec![0.0, 1.0, 0.0, 0.0],
+        "down" => vec![0.0, -1.0, 0.0, 0.0],
+        "right" => vec![1.0, 0.0, 0.0, 0.0],
+        "left" => vec![-1.0, 0.0, 0.0, 0.0],
+        "forward" => vec![0.0, 0.0, 1.0, 0.0],
+        "back" => vec![0.0, 0.0, -1.0, 0.0],
+        "white" => vec![1.0, 1.0, 1.0, 1.0],
+        "black" => vec![0.0, 0.0, 0.0, 1.0],
+        "red" => vec![1.0, 0.0, 0.0, 1.0],
+        "green" => vec![0.0, 1.0, 0.0, 1.0],
+        "blue" => vec![0.0, 0.0, 1.0, 1.0],
+        "yellow" => vec![1.0, 0.92156863, 0.015686275, 1.0],
+        _ => return None,
+    };
+    Some(values[..len].to_vec())
+}
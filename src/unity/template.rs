@@ -0,0 +1,382 @@
+use crate::class_id::ClassId;
+use crate::unity::{
+    prefab::body_mut,
+    yaml::{parse_str, YamlUnityDocument},
+    Guid, Id,
+};
+use unity_yaml_rust::Yaml;
+
+const MONO_BEHAVIOUR_CLASS_ID: ClassId = 114;
+
+/// The built-in components that can be added, with their class id and the values Unity gives to a
+/// new one, after m_GameObject
+const BUILT_IN_COMPONENTS: [(&str, ClassId, &[&str]); 16] = [
+    ("AudioListener", 81, &["m_Enabled: 1"]),
+    (
+        "BoxCollider",
+        65,
+        &[
+            "m_Material: {fileID: 0}",
+            "m_IsTrigger: 0",
+            "m_Enabled: 1",
+            "serializedVersion: 2",
+            "m_Size: {x: 1, y: 1, z: 1}",
+            "m_Center: {x: 0, y: 0, z: 0}",
+        ],
+    ),
+    (
+        "BoxCollider2D",
+        61,
+        &[
+            "m_Enabled: 1",
+            "m_Density: 1",
+            "m_Material: {fileID: 0}",
+            "m_IsTrigger: 0",
+            "m_UsedByEffector: 0",
+            "m_UsedByComposite: 0",
+            "m_Offset: {x: 0, y: 0}",
+            "serializedVersion: 2",
+            "m_Size: {x: 1, y: 1}",
+            "m_EdgeRadius: 0",
+        ],
+    ),
+    (
+        "Camera",
+        20,
+        &[
+            "m_Enabled: 1",
+            "serializedVersion: 2",
+            "m_ClearFlags: 1",
+            "m_BackGroundColor: {r: 0.19215687, g: 0.3019608, b: 0.4745098, a: 0}",
+            "m_projectionMatrixMode: 1",
+            "m_NormalizedViewPortRect:",
+            "  serializedVersion: 2",
+            "  x: 0",
+            "  y: 0",
+            "  width: 1",
+            "  height: 1",
+            "near clip plane: 0.3",
+            "far clip plane: 1000",
+            "field of view: 60",
+            "orthographic: 0",
+            "orthographic size: 5",
+            "m_Depth: 0",
+            "m_CullingMask:",
+            "  serializedVersion: 2",
+            "  m_Bits: 4294967295",
+            "m_RenderingPath: -1",
+            "m_TargetTexture: {fileID: 0}",
+            "m_TargetDisplay: 0",
+            "m_TargetEye: 3",
+            "m_HDR: 1",
+            "m_AllowMSAA: 1",
+            "m_AllowDynamicResolution: 0",
+            "m_ForceIntoRT: 0",
+            "m_OcclusionCulling: 1",
+            "m_StereoConvergence: 10",
+            "m_StereoSeparation: 0.022",
+        ],
+    ),
+    (
+        "Canvas",
+        223,
+        &[
+            "m_Enabled: 1",
+            "serializedVersion: 3",
+            "m_RenderMode: 0",
+            "m_Camera: {fileID: 0}",
+            "m_PlaneDistance: 100",
+            "m_PixelPerfect: 0",
+            "m_ReceivesEvents: 1",
+            "m_OverrideSorting: 0",
+            "m_OverridePixelPerfect: 0",
+            "m_SortingBucketNormalizedSize: 0",
+            "m_AdditionalShaderChannelsFlag: 0",
+            "m_SortingLayerID: 0",
+            "m_SortingOrder: 0",
+            "m_TargetDisplay: 0",
+        ],
+    ),
+    (
+        "CapsuleCollider",
+        136,
+        &[
+            "m_Material: {fileID: 0}",
+            "m_IsTrigger: 0",
+            "m_Enabled: 1",
+            "m_Radius: 0.5",
+            "m_Height: 2",
+            "m_Direction: 1",
+            "m_Center: {x: 0, y: 0, z: 0}",
+        ],
+    ),
+    (
+        "CircleCollider2D",
+        58,
+        &[
+            "m_Enabled: 1",
+            "m_Density: 1",
+            "m_Material: {fileID: 0}",
+            "m_IsTrigger: 0",
+            "m_UsedByEffector: 0",
+            "m_UsedByComposite: 0",
+            "m_Offset: {x: 0, y: 0}",
+            "serializedVersion: 2",
+            "m_Radius: 0.5",
+        ],
+    ),
+    (
+        "Light",
+        108,
+        &[
+            "m_Enabled: 1",
+            "serializedVersion: 10",
+            "m_Type: 2",
+            "m_Shape: 0",
+            "m_Color: {r: 1, g: 1, b: 1, a: 1}",
+            "m_Intensity: 1",
+            "m_Range: 10",
+            "m_SpotAngle: 30",
+            "m_InnerSpotAngle: 21.80208",
+            "m_CookieSize: 10",
+            "m_Shadows:",
+            "  m_Type: 0",
+            "  m_Resolution: -1",
+            "  m_CustomResolution: -1",
+            "  m_Strength: 1",
+            "  m_Bias: 0.05",
+            "  m_NormalBias: 0.4",
+            "  m_NearPlane: 0.2",
+            "m_Cookie: {fileID: 0}",
+            "m_DrawHalo: 0",
+            "m_Flare: {fileID: 0}",
+            "m_RenderMode: 0",
+            "m_CullingMask:",
+            "  serializedVersion: 2",
+            "  m_Bits: 4294967295",
+            "m_Lightmapping: 4",
+            "m_BounceIntensity: 1",
+        ],
+    ),
+    (
+        "MeshCollider",
+        64,
+        &[
+            "m_Material: {fileID: 0}",
+            "m_IsTrigger: 0",
+            "m_Enabled: 1",
+            "serializedVersion: 4",
+            "m_Convex: 0",
+            "m_CookingOptions: 30",
+            "m_Mesh: {fileID: 0}",
+        ],
+    ),
+    ("MeshFilter", 33, &["m_Mesh: {fileID: 0}"]),
+    (
+        "MeshRenderer",
+        23,
+        &[
+            "m_Enabled: 1",
+            "m_CastShadows: 1",
+            "m_ReceiveShadows: 1",
+            "m_DynamicOccludee: 1",
+            "m_MotionVectors: 1",
+            "m_LightProbeUsage: 1",
+            "m_ReflectionProbeUsage: 1",
+            "m_RenderingLayerMask: 1",
+            "m_RendererPriority: 0",
+            "m_Materials: []",
+            "m_StaticBatchInfo:",
+            "  firstSubMesh: 0",
+            "  subMeshCount: 0",
+            "m_StaticBatchRoot: {fileID: 0}",
+            "m_ProbeAnchor: {fileID: 0}",
+            "m_LightProbeVolumeOverride: {fileID: 0}",
+            "m_ScaleInLightmap: 1",
+            "m_ReceiveGI: 1",
+            "m_PreserveUVs: 0",
+            "m_IgnoreNormalsForChartDetection: 0",
+            "m_ImportantGI: 0",
+            "m_StitchLightmapSeams: 1",
+            "m_SelectedEditorRenderState: 3",
+            "m_MinimumChartSize: 4",
+            "m_AutoUVMaxDistance: 0.5",
+            "m_AutoUVMaxAngle: 89",
+            "m_LightmapParameters: {fileID: 0}",
+            "m_SortingLayerID: 0",
+            "m_SortingLayer: 0",
+            "m_SortingOrder: 0",
+        ],
+    ),
+    (
+        "Rigidbody",
+        54,
+        &[
+            "serializedVersion: 2",
+            "m_Mass: 1",
+            "m_Drag: 0",
+            "m_AngularDrag: 0.05",
+            "m_UseGravity: 1",
+            "m_IsKinematic: 0",
+            "m_Interpolate: 0",
+            "m_Constraints: 0",
+            "m_CollisionDetection: 0",
+        ],
+    ),
+    (
+        "Rigidbody2D",
+        50,
+        &[
+            "m_BodyType: 0",
+            "m_Simulated: 1",
+            "m_UseFullKinematicContacts: 0",
+            "m_UseAutoMass: 0",
+            "m_Mass: 1",
+            "m_LinearDrag: 0",
+            "m_AngularDrag: 0.05",
+            "m_GravityScale: 1",
+            "m_Material: {fileID: 0}",
+            "m_Interpolate: 0",
+            "m_SleepingMode: 1",
+            "m_CollisionDetection: 0",
+            "m_Constraints: 0",
+        ],
+    ),
+    (
+        "SphereCollider",
+        135,
+        &[
+            "m_Material: {fileID: 0}",
+            "m_IsTrigger: 0",
+            "m_Enabled: 1",
+            "serializedVersion: 2",
+            "m_Radius: 0.5",
+            "m_Center: {x: 0, y: 0, z: 0}",
+        ],
+    ),
+    (
+        "SpriteRenderer",
+        212,
+        &[
+            "m_Enabled: 1",
+            "m_CastShadows: 0",
+            "m_ReceiveShadows: 0",
+            "m_DynamicOccludee: 1",
+            "m_MotionVectors: 1",
+            "m_LightProbeUsage: 1",
+            "m_ReflectionProbeUsage: 1",
+            "m_RenderingLayerMask: 1",
+            "m_RendererPriority: 0",
+            "m_Materials:",
+            "- {fileID: 10754, guid: 0000000000000000f000000000000000, type: 0}",
+            "m_SortingLayerID: 0",
+            "m_SortingLayer: 0",
+            "m_SortingOrder: 0",
+            "m_Sprite: {fileID: 0}",
+            "m_Color: {r: 1, g: 1, b: 1, a: 1}",
+            "m_FlipX: 0",
+            "m_FlipY: 0",
+            "m_DrawMode: 0",
+            "m_Size: {x: 1, y: 1}",
+            "m_AdaptiveModeThreshold: 0.5",
+            "m_SpriteTileMode: 0",
+            "m_WasSpriteAssigned: 0",
+            "m_MaskInteraction: 0",
+            "m_SpriteSortPoint: 0",
+        ],
+    ),
+    (
+        "AudioSource",
+        82,
+        &[
+            "m_Enabled: 1",
+            "serializedVersion: 4",
+            "OutputAudioMixerGroup: {fileID: 0}",
+            "m_audioClip: {fileID: 0}",
+            "m_PlayOnAwake: 1",
+            "m_Volume: 1",
+            "m_Pitch: 1",
+            "Loop: 0",
+            "Mute: 0",
+            "Spatialize: 0",
+            "SpatializePostEffects: 0",
+            "Priority: 128",
+            "DopplerLevel: 1",
+            "MinDistance: 1",
+            "MaxDistance: 500",
+            "Pan2D: 0",
+            "rolloffMode: 0",
+            "BypassEffects: 0",
+            "BypassListenerEffects: 0",
+            "BypassReverbZones: 0",
+        ],
+    ),
+];
+
+/// Returns the names of the built-in components that can be added
+pub fn get_built_in_component_names() -> Vec<&'static str> {
+    let mut names = BUILT_IN_COMPONENTS
+        .iter()
+        .map(|(name, _, _)| *name)
+        .collect::<Vec<&str>>();
+    names.sort_unstable();
+    names
+}
+
+/// Returns a new built-in component of the game object with the values Unity gives it
+pub fn new_built_in_component(
+    class_name: &str,
+    id: &Id,
+    game_object_id: &Id,
+) -> Option<YamlUnityDocument> {
+    let (_, class_id, fields) = BUILT_IN_COMPONENTS
+        .iter()
+        .find(|(name, _, _)| *name == class_name)?;
+    let mut lines = component_header_lines(*class_id, class_name, id, game_object_id);
+    lines.extend(fields.iter().map(|field| format!("  {}", field)));
+    parse_str(&lines.join("\n")).ok()?.pop()
+}
+
+/// Returns a new MonoBehaviour running the script, the fields are added after the common ones
+pub fn new_mono_behaviour(
+    script_guid: &Guid,
+    fields: Vec<(String, Yaml)>,
+    id: &Id,
+    game_object_id: &Id,
+) -> Option<YamlUnityDocument> {
+    let mut lines =
+        component_header_lines(MONO_BEHAVIOUR_CLASS_ID, "MonoBehaviour", id, game_object_id);
+    lines.extend([
+        "  m_Enabled: 1".to_owned(),
+        "  m_EditorHideFlags: 0".to_owned(),
+        format!(
+            "  m_Script: {{fileID: 11500000, guid: {}, type: 3}}",
+            script_guid
+        ),
+        "  m_Name: ".to_owned(),
+        "  m_EditorClassIdentifier: ".to_owned(),
+    ]);
+    let mut doc = parse_str(&lines.join("\n")).ok()?.pop()?;
+    let body = body_mut(&mut doc.document)?;
+    for (name, value) in fields {
+        body.insert(Yaml::String(name), value);
+    }
+    Some(doc)
+}
+
+fn component_header_lines(
+    class_id: ClassId,
+    class_name: &str,
+    id: &Id,
+    game_object_id: &Id,
+) -> Vec<String> {
+    vec![
+        format!("--- !u!{} &{}", class_id, id),
+        format!("{}:", class_name),
+        "  m_ObjectHideFlags: 0".to_owned(),
+        "  m_CorrespondingSourceObject: {fileID: 0}".to_owned(),
+        "  m_PrefabInstance: {fileID: 0}".to_owned(),
+        "  m_PrefabAsset: {fileID: 0}".to_owned(),
+        format!("  m_GameObject: {{fileID: {}}}", game_object_id),
+    ]
+}
@@ -0,0 +1,179 @@
+use crate::{
+    fs::ProjectFiles,
+    unity::{
+        construct_repository,
+        edit::{apply_operations, Operation},
+        repository::{construct_meta_repository, MetaFilesRepository},
+        writer,
+        yaml::{parse_unity_str, YamlUnityFile},
+        Component, Repository,
+    },
+};
+use std::path::Path;
+
+/// Player with a MonoBehaviour and the child Weapon, then Camera. The objects are written with all
+/// the fields Unity writes so the edits can be compared with the text
+pub const SCENE: &str = "\
+%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &100
+GameObject:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  serializedVersion: 6
+  m_Component:
+  - component: {fileID: 101}
+  - component: {fileID: 102}
+  m_Layer: 0
+  m_Name: Player
+  m_TagString: Untagged
+  m_Icon: {fileID: 0}
+  m_NavMeshLayer: 0
+  m_StaticEditorFlags: 0
+  m_IsActive: 1
+--- !u!4 &101
+Transform:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  m_GameObject: {fileID: 100}
+  serializedVersion: 2
+  m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}
+  m_LocalPosition: {x: 0, y: 0, z: 0}
+  m_LocalScale: {x: 1, y: 1, z: 1}
+  m_ConstrainProportionsScale: 0
+  m_Children:
+  - {fileID: 111}
+  m_Father: {fileID: 0}
+  m_RootOrder: 0
+  m_LocalEulerAnglesHint: {x: 0, y: 0, z: 0}
+--- !u!114 &102
+MonoBehaviour:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  m_GameObject: {fileID: 100}
+  m_Enabled: 1
+  m_EditorHideFlags: 0
+  m_Script: {fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+  m_Name: 
+  m_EditorClassIdentifier: 
+  speed: 1.5
+--- !u!1 &110
+GameObject:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  serializedVersion: 6
+  m_Component:
+  - component: {fileID: 111}
+  m_Layer: 0
+  m_Name: Weapon
+  m_TagString: Untagged
+  m_Icon: {fileID: 0}
+  m_NavMeshLayer: 0
+  m_StaticEditorFlags: 0
+  m_IsActive: 1
+--- !u!4 &111
+Transform:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  m_GameObject: {fileID: 110}
+  serializedVersion: 2
+  m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}
+  m_LocalPosition: {x: 0.5, y: 0, z: 0}
+  m_LocalScale: {x: 1, y: 1, z: 1}
+  m_ConstrainProportionsScale: 0
+  m_Children: []
+  m_Father: {fileID: 101}
+  m_RootOrder: 0
+  m_LocalEulerAnglesHint: {x: 0, y: 0, z: 0}
+--- !u!1 &120
+GameObject:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  serializedVersion: 6
+  m_Component:
+  - component: {fileID: 121}
+  m_Layer: 0
+  m_Name: Camera
+  m_TagString: MainCamera
+  m_Icon: {fileID: 0}
+  m_NavMeshLayer: 0
+  m_StaticEditorFlags: 0
+  m_IsActive: 1
+--- !u!4 &121
+Transform:
+  m_ObjectHideFlags: 0
+  m_CorrespondingSourceObject: {fileID: 0}
+  m_PrefabInstance: {fileID: 0}
+  m_PrefabAsset: {fileID: 0}
+  m_GameObject: {fileID: 120}
+  serializedVersion: 2
+  m_LocalRotation: {x: 0, y: 0, z: 0, w: 1}
+  m_LocalPosition: {x: 0, y: 1, z: -10}
+  m_LocalScale: {x: 1, y: 1, z: 1}
+  m_ConstrainProportionsScale: 0
+  m_Children: []
+  m_Father: {fileID: 0}
+  m_RootOrder: 1
+  m_LocalEulerAnglesHint: {x: 0, y: 0, z: 0}
+";
+
+/// Reads the file the way it is read when it is opened, the documents must all be readable
+pub fn read_repository(content: &str) -> Repository {
+    let (repo, errors) = construct_repository(parse_unity_str(content).documents);
+    assert!(errors.is_empty(), "{:?}", errors);
+    repo
+}
+
+/// A file opened for editing the way the hierarchy view opens it
+pub struct Edited {
+    pub file: YamlUnityFile,
+    pub repo: Repository,
+    pub meta_files: MetaFilesRepository,
+}
+
+impl Edited {
+    pub fn open(content: &str) -> Self {
+        let file = parse_unity_str(content);
+        let repo = read_repository(content);
+        let (meta_files, _) = construct_meta_repository(&ProjectFiles::new(Path::new("")));
+        Self {
+            file,
+            repo,
+            meta_files,
+        }
+    }
+
+    pub fn apply(&mut self, operations: &[Operation]) {
+        apply_operations(operations, &mut self.file, &mut self.repo, &self.meta_files);
+    }
+
+    /// Undoes the operations the way the history does, the last one first
+    pub fn undo(&mut self, operations: &[Operation]) {
+        let reversed = operations
+            .iter()
+            .rev()
+            .map(Operation::reversed)
+            .collect::<Vec<Operation>>();
+        self.apply(&reversed);
+    }
+
+    pub fn text(&self) -> String {
+        writer::file_to_string(&self.file)
+    }
+
+    pub fn component(&self, id: &str) -> Component {
+        self.repo.get_component(&id.to_owned()).unwrap().clone()
+    }
+}
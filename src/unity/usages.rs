@@ -0,0 +1,330 @@
+use crate::{
+    fs::ProjectFiles,
+    unity::{
+        self,
+        error::ParseError,
+        references::{self, is_built_in_guid},
+        repository::MetaFilesRepository,
+        yaml, GameObject, Guid, Id, Object,
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// A reference to an asset from a document of a scene, a prefab or another asset
+#[derive(Debug, Clone)]
+pub struct Usage {
+    /// The file the reference is in, relative to the project
+    pub path: PathBuf,
+    /// The document the reference is in
+    pub file_id: Id,
+    pub class_name: String,
+    /// The line of the header of the document, counted from 1
+    pub line: Option<usize>,
+    /// The field the reference is in, like `m_Script`
+    pub property_path: String,
+    /// The names of the game objects from the root down to the one the document belongs to, like
+    /// `Canvas/Panel/Button`. Objects inside a prefab instance are named after the instance. None
+    /// for documents that are not in the hierarchy, like RenderSettings
+    pub hierarchy_path: Option<String>,
+}
+
+/// The references to the assets of the project by their guid, read from every scene, prefab and
+/// text asset. Only the files that changed since they were read are read again on a refresh
+#[derive(Debug, Default)]
+pub struct UsagesIndex {
+    usages: HashMap<Guid, Vec<Usage>>,
+    files: HashMap<PathBuf, IndexedFile>,
+}
+
+#[derive(Debug)]
+struct IndexedFile {
+    /// None if the time could not be read, the file is then read on every refresh
+    modified: Option<SystemTime>,
+    /// The references of a file that could not be read are not in the index
+    error: Option<ParseError>,
+}
+
+impl UsagesIndex {
+    /// Reads the files of the project that are new or were written since the last refresh
+    pub fn refresh(&mut self, project: &ProjectFiles, meta_files: &MetaFilesRepository) {
+        for path in project
+            .scenes
+            .iter()
+            .chain(project.prefabs.iter())
+            .chain(project.assets.iter())
+        {
+            let relative = path.strip_prefix(&project.base_path).unwrap_or(path);
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            if let Some(indexed) = self.files.get(relative) {
+                if modified.is_some() && indexed.modified == modified {
+                    continue;
+                }
+                for usages in self.usages.values_mut() {
+                    usages.retain(|usage| usage.path != relative);
+                }
+            }
+            // binary assets have no references that can be read
+            let error = if project.assets.contains(path) && yaml::is_binary_file(path) {
+                None
+            } else {
+                self.read_file(path, relative, meta_files).err()
+            };
+            self.files
+                .insert(relative.to_path_buf(), IndexedFile { modified, error });
+        }
+    }
+
+    /// Returns the usages of the asset, sorted by file and then by line
+    pub fn get_usages(&self, guid: &Guid) -> Vec<&Usage> {
+        let mut usages = self
+            .usages
+            .get(guid)
+            .map(|usages| usages.iter().collect::<Vec<&Usage>>())
+            .unwrap_or_default();
+        usages.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        usages
+    }
+
+    /// Returns the files that could not be read, sorted by path
+    pub fn get_errors(&self) -> Vec<ParseError> {
+        let mut errors = self
+            .files
+            .values()
+            .filter_map(|indexed| indexed.error.clone())
+            .collect::<Vec<ParseError>>();
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        errors
+    }
+
+    fn read_file(
+        &mut self,
+        path: &Path,
+        relative: &Path,
+        meta_files: &MetaFilesRepository,
+    ) -> Result<(), ParseError> {
+        let file = yaml::parse_unity_file(path).map_err(|e| e.with_path(relative))?;
+        // the prefab instances are not resolved, the paths only need the names of the objects
+        let (repo, _) = unity::construct_repository(file.documents.clone());
+        // stripped documents only link to the prefab instance, which is read itself
+        for doc in file
+            .documents
+            .iter()
+            .filter(|doc| !doc.is_unreadable() && !doc.stripped)
+        {
+            let Some((class_name, references)) = references::get_document_references(doc) else { continue; };
+            let mut hierarchy_path = None;
+            for (property_path, reference) in references {
+                // references to objects of the same file have no guid
+                let Some(guid) = reference.guid.filter(|guid| !is_built_in_guid(guid)) else { continue; };
+                let hierarchy_path = hierarchy_path
+                    .get_or_insert_with(|| get_hierarchy_path(&repo, meta_files, &doc.id))
+                    .clone();
+                self.usages.entry(guid).or_default().push(Usage {
+                    path: relative.to_path_buf(),
+                    file_id: doc.id.clone(),
+                    class_name: class_name.to_owned(),
+                    line: doc.line,
+                    property_path,
+                    hierarchy_path,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the names of the game objects from the root down to the one the object belongs to,
+/// joined with slashes
+fn get_hierarchy_path(
+    repo: &unity::Repository,
+    meta_files: &MetaFilesRepository,
+    id: &Id,
+) -> Option<String> {
+    let mut names = vec![];
+    // the id of the next transform up the hierarchy, "0" above the root
+    let mut parent_id = match repo.get(id)? {
+        Object::GameObject(go) => push_game_object(repo, go, &mut names),
+        Object::Component(component) => {
+            match repo.get_game_object(component.get_game_object_id()) {
+                Some(go) => push_game_object(repo, go, &mut names),
+                // components added to an object of a prefab instance are on a stripped game object
+                None => {
+                    let stripped = repo.get_stripped(component.get_game_object_id())?;
+                    let instance = repo.get_prefab_instance(&stripped.prefab_instance_id)?;
+                    names.push(instance.get_name(meta_files));
+                    instance.transform_parent_id.clone()
+                }
+            }
+        }
+        Object::PrefabInstance(instance) => {
+            names.push(instance.get_name(meta_files));
+            instance.transform_parent_id.clone()
+        }
+        Object::Stripped(_) | Object::SceneRoots(_) => return None,
+    };
+    let mut visited = HashSet::new();
+    while parent_id != "0" && visited.insert(parent_id.clone()) {
+        parent_id = match repo.get_transform(&parent_id) {
+            Some(transform) => {
+                let go = repo.get_game_object(transform.get_game_object_id())?;
+                names.push(go.name.clone());
+                transform.get_father_id().clone()
+            }
+            None => {
+                let stripped = repo.get_stripped(&parent_id)?;
+                let instance = repo.get_prefab_instance(&stripped.prefab_instance_id)?;
+                names.push(instance.get_name(meta_files));
+                instance.transform_parent_id.clone()
+            }
+        };
+    }
+    names.reverse();
+    Some(names.join("/"))
+}
+
+/// Adds the name of the game object and returns the id of the parent of its transform
+fn push_game_object(repo: &unity::Repository, go: &GameObject, names: &mut Vec<String>) -> Id {
+    names.push(go.name.clone());
+    go.component_ids
+        .iter()
+        .find_map(|id| repo.get_transform(id))
+        .map_or_else(
+            || "0".to_owned(),
+            |transform| transform.get_father_id().clone(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UsagesIndex;
+    use crate::{fs::ProjectFiles, unity::repository::construct_meta_repository};
+    use std::{
+        fs,
+        path::Path,
+        time::{Duration, SystemTime},
+    };
+
+    const SCRIPT_GUID: &str = "0123456789abcdef0123456789abcdef";
+    const PREFAB_GUID: &str = "fedcba9876543210fedcba9876543210";
+
+    fn game_object(id: u32, name: &str, component_ids: &[u32]) -> String {
+        let components = component_ids
+            .iter()
+            .map(|id| format!("  - component: {{fileID: {}}}\n", id))
+            .collect::<String>();
+        format!(
+            "--- !u!1 &{}\nGameObject:\n  m_ObjectHideFlags: 0\n  m_CorrespondingSourceObject: {{fileID: 0}}\n  m_PrefabInstance: {{fileID: 0}}\n  m_PrefabAsset: {{fileID: 0}}\n  serializedVersion: 6\n  m_Component:\n{}  m_Layer: 5\n  m_Name: {}\n  m_TagString: Untagged\n  m_Icon: {{fileID: 0}}\n  m_NavMeshLayer: 0\n  m_StaticEditorFlags: 0\n  m_IsActive: 1\n",
+            id, components, name
+        )
+    }
+
+    fn transform(id: u32, go_id: u32, father_id: u32, child_ids: &[u32]) -> String {
+        let children = match child_ids {
+            [] => " []\n".to_owned(),
+            ids => {
+                ids.iter()
+                    .map(|id| format!("\n  - {{fileID: {}}}", id))
+                    .collect::<String>()
+                    + "\n"
+            }
+        };
+        format!(
+            "--- !u!4 &{}\nTransform:\n  m_ObjectHideFlags: 0\n  m_CorrespondingSourceObject: {{fileID: 0}}\n  m_PrefabInstance: {{fileID: 0}}\n  m_PrefabAsset: {{fileID: 0}}\n  m_GameObject: {{fileID: {}}}\n  serializedVersion: 2\n  m_LocalRotation: {{x: 0, y: 0, z: 0, w: 1}}\n  m_LocalPosition: {{x: 0, y: 0, z: 0}}\n  m_LocalScale: {{x: 1, y: 1, z: 1}}\n  m_ConstrainProportionsScale: 0\n  m_Children:{}  m_Father: {{fileID: {}}}\n  m_RootOrder: 0\n  m_LocalEulerAnglesHint: {{x: 0, y: 0, z: 0}}\n",
+            id, go_id, children, father_id
+        )
+    }
+
+    fn script(id: u32, go_id: u32) -> String {
+        format!(
+            "--- !u!114 &{}\nMonoBehaviour:\n  m_ObjectHideFlags: 0\n  m_CorrespondingSourceObject: {{fileID: 0}}\n  m_PrefabInstance: {{fileID: 0}}\n  m_PrefabAsset: {{fileID: 0}}\n  m_GameObject: {{fileID: {}}}\n  m_Enabled: 1\n  m_EditorHideFlags: 0\n  m_Script: {{fileID: 11500000, guid: {}, type: 3}}\n  m_Name: \n  m_EditorClassIdentifier: \n",
+            id, go_id, SCRIPT_GUID
+        )
+    }
+
+    /// Canvas/Panel/Button with the script on Button, and an instance named Icon under Panel with
+    /// the script added to its root
+    fn menu(with_button_script: bool) -> String {
+        let button_components: &[u32] = if with_button_script { &[6, 7] } else { &[6] };
+        let mut content = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n".to_owned();
+        content += &game_object(1, "Canvas", &[2]);
+        content += &transform(2, 1, 0, &[4]);
+        content += &game_object(3, "Panel", &[4]);
+        content += &transform(4, 3, 2, &[6, 23]);
+        content += &game_object(5, "Button", button_components);
+        content += &transform(6, 5, 4, &[]);
+        if with_button_script {
+            content += &script(7, 5);
+        }
+        content += &format!(
+            "--- !u!1001 &20\nPrefabInstance:\n  m_ObjectHideFlags: 0\n  serializedVersion: 2\n  m_Modification:\n    serializedVersion: 3\n    m_TransformParent: {{fileID: 4}}\n    m_Modifications:\n    - target: {{fileID: 100, guid: {0}, type: 3}}\n      propertyPath: m_Name\n      value: Icon\n      objectReference: {{fileID: 0}}\n    m_RemovedComponents: []\n  m_SourcePrefab: {{fileID: 100100000, guid: {0}, type: 3}}\n--- !u!1 &21 stripped\nGameObject:\n  m_CorrespondingSourceObject: {{fileID: 100, guid: {0}, type: 3}}\n  m_PrefabInstance: {{fileID: 20}}\n  m_PrefabAsset: {{fileID: 0}}\n--- !u!4 &23 stripped\nTransform:\n  m_CorrespondingSourceObject: {{fileID: 400, guid: {0}, type: 3}}\n  m_PrefabInstance: {{fileID: 20}}\n  m_PrefabAsset: {{fileID: 0}}\n",
+            PREFAB_GUID
+        );
+        content += &script(22, 21);
+        content
+    }
+
+    /// Returns the hierarchy path and the field of every usage of the guid
+    fn usages(index: &UsagesIndex, guid: &str) -> Vec<(String, String)> {
+        index
+            .get_usages(&guid.to_owned())
+            .into_iter()
+            .map(|usage| {
+                (
+                    usage.hierarchy_path.clone().unwrap_or_default(),
+                    usage.property_path.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn usages_are_read_again_when_the_file_changes() {
+        let dir = std::env::temp_dir().join(format!("unity_tui_usages_{}", std::process::id()));
+        fs::create_dir_all(dir.join("Assets")).unwrap();
+        let scene = dir.join("Assets/Menu.unity");
+        fs::write(&scene, menu(true)).unwrap();
+        let mut project = ProjectFiles::new(&dir);
+        project.scenes.push(scene.clone());
+        let (meta_files, _) = construct_meta_repository(&ProjectFiles::new(Path::new("")));
+
+        let mut index = UsagesIndex::default();
+        index.refresh(&project, &meta_files);
+        assert!(index.get_errors().is_empty());
+        assert_eq!(
+            usages(&index, SCRIPT_GUID),
+            vec![
+                ("Canvas/Panel/Button".to_owned(), "m_Script".to_owned()),
+                ("Canvas/Panel/Icon".to_owned(), "m_Script".to_owned())
+            ]
+        );
+        assert_eq!(
+            usages(&index, PREFAB_GUID),
+            vec![("Canvas/Panel/Icon".to_owned(), "m_SourcePrefab".to_owned())]
+        );
+        let usage = index.get_usages(&PREFAB_GUID.to_owned())[0].clone();
+        assert_eq!(usage.path, Path::new("Assets/Menu.unity"));
+        assert_eq!(
+            (usage.file_id.as_str(), usage.class_name.as_str()),
+            ("20", "PrefabInstance")
+        );
+
+        // the time is moved on so the change is seen even on file systems that keep it in seconds
+        fs::write(&scene, menu(false)).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&scene)
+            .and_then(|file| file.set_modified(SystemTime::now() + Duration::from_secs(10)))
+            .unwrap();
+        index.refresh(&project, &meta_files);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            usages(&index, SCRIPT_GUID),
+            vec![("Canvas/Panel/Icon".to_owned(), "m_Script".to_owned())]
+        );
+        assert_eq!(usages(&index, PREFAB_GUID).len(), 1);
+    }
+}
@@ -1,5 +1,9 @@
 use std::fmt::Debug;
 
+/// The decimals kept of angles converted from a rotation, hides the float error of
+/// [`Quaternion::to_euler`]
+pub const EULER_DECIMALS: i32 = 4;
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
 pub struct Vector4 {
     pub x: f32,
@@ -30,3 +34,268 @@ where
 
 pub type Vector3 = Vec3<f32>;
 pub type Vector2 = Vec2<f32>;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Quaternion {
+    pub const IDENTITY: Quaternion = Quaternion {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    /// Returns the rotation by the angles in degrees, applied like Unity does: around z, then x,
+    /// then y
+    pub fn from_euler(angles: Vector3) -> Quaternion {
+        let half = |degrees: f32| (degrees.to_radians() / 2.0).sin_cos();
+        let (sx, cx) = half(angles.x);
+        let (sy, cy) = half(angles.y);
+        let (sz, cz) = half(angles.z);
+        let around_x = Quaternion {
+            x: sx,
+            y: 0.0,
+            z: 0.0,
+            w: cx,
+        };
+        let around_y = Quaternion {
+            x: 0.0,
+            y: sy,
+            z: 0.0,
+            w: cy,
+        };
+        let around_z = Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: sz,
+            w: cz,
+        };
+        around_y.mul(&around_x).mul(&around_z)
+    }
+
+    /// Returns the angles in degrees of the rotation, in the order of [`Quaternion::from_euler`].
+    /// The angles are between -180 and 180, at ±90 around x the angle around z is 0
+    pub fn to_euler(self) -> Vector3 {
+        let Quaternion { x, y, z, w } = self.normalized();
+        let sin_x = (2.0 * (w * x - y * z)).clamp(-1.0, 1.0);
+        let (angle_y, angle_z) = if sin_x.abs() < 0.999_999 {
+            (
+                (2.0 * (x * z + w * y)).atan2(1.0 - 2.0 * (x * x + y * y)),
+                (2.0 * (x * y + w * z)).atan2(1.0 - 2.0 * (x * x + z * z)),
+            )
+        } else {
+            (
+                (-2.0 * (x * z - w * y)).atan2(1.0 - 2.0 * (y * y + z * z)),
+                0.0,
+            )
+        };
+        Vector3 {
+            x: sin_x.asin().to_degrees(),
+            y: angle_y.to_degrees(),
+            z: angle_z.to_degrees(),
+        }
+    }
+
+    /// Returns the rotation of applying `other` first and then this one
+    pub fn mul(&self, other: &Quaternion) -> Quaternion {
+        Quaternion {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        }
+    }
+
+    /// Returns the vector turned by the rotation
+    pub fn rotate(&self, vector: Vector3) -> Vector3 {
+        let point = Quaternion {
+            x: vector.x,
+            y: vector.y,
+            z: vector.z,
+            w: 0.0,
+        };
+        let inverse = Quaternion {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w,
+        };
+        let turned = self.mul(&point).mul(&inverse);
+        Vector3 {
+            x: turned.x,
+            y: turned.y,
+            z: turned.z,
+        }
+    }
+
+    /// Returns the quaternion scaled to a length of 1, the identity if it has no length
+    pub fn normalized(&self) -> Quaternion {
+        let length = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        if length == 0.0 || !length.is_finite() {
+            return Quaternion::IDENTITY;
+        }
+        Quaternion {
+            x: self.x / length,
+            y: self.y / length,
+            z: self.z / length,
+            w: self.w / length,
+        }
+    }
+}
+
+impl Vector3 {
+    pub const ONE: Vector3 = Vector3 {
+        x: 1.0,
+        y: 1.0,
+        z: 1.0,
+    };
+
+    pub fn add(&self, other: &Vector3) -> Vector3 {
+        Vector3 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    /// Returns the vector with every component multiplied by the same one of `other`
+    pub fn scale(&self, other: &Vector3) -> Vector3 {
+        Vector3 {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z,
+        }
+    }
+
+    /// Returns the vector with every component rounded to the number of decimals, used to hide
+    /// the float error of conversions like [`Quaternion::to_euler`]
+    pub fn rounded(&self, decimals: i32) -> Vector3 {
+        let factor = 10f32.powi(decimals);
+        // adding 0.0 turns -0 into 0
+        let round = |value: f32| (value * factor).round() / factor + 0.0;
+        Vector3 {
+            x: round(self.x),
+            y: round(self.y),
+            z: round(self.z),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    fn assert_quaternion(actual: Quaternion, expected: Quaternion) {
+        assert_close(actual.x, expected.x);
+        assert_close(actual.y, expected.y);
+        assert_close(actual.z, expected.z);
+        assert_close(actual.w, expected.w);
+    }
+
+    fn angles(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+
+    #[test]
+    fn euler_angles_turn_around_z_then_x_then_y() {
+        let rotation = Quaternion::from_euler(angles(10.0, 20.0, 30.0));
+
+        // the value Unity gives for Quaternion.Euler(10, 20, 30)
+        assert_quaternion(
+            rotation,
+            Quaternion {
+                x: 0.1277,
+                y: 0.1449,
+                z: 0.2393,
+                w: 0.9515,
+            },
+        );
+    }
+
+    #[test]
+    fn euler_angles_are_read_back_from_their_rotation() {
+        for value in [
+            angles(10.0, 20.0, 30.0),
+            angles(-45.0, 170.0, -120.0),
+            angles(0.0, -90.0, 0.0),
+        ] {
+            let read = Quaternion::from_euler(value)
+                .to_euler()
+                .rounded(EULER_DECIMALS);
+
+            assert_eq!(read, value.rounded(EULER_DECIMALS));
+        }
+    }
+
+    #[test]
+    fn euler_angles_at_the_x_gimbal_lock_keep_the_rotation() {
+        for x in [90.0, -90.0] {
+            let rotation = Quaternion::from_euler(angles(x, 30.0, 40.0));
+            let read = rotation.to_euler();
+
+            assert_close(read.x, x);
+            assert_close(read.z, 0.0);
+            // the angle around z is folded into the one around y, the rotation stays the same
+            assert_quaternion(Quaternion::from_euler(read), rotation);
+        }
+    }
+
+    #[test]
+    fn identity_has_no_angles_and_turns_nothing() {
+        assert_eq!(Quaternion::IDENTITY.to_euler(), Vector3::default());
+        assert_quaternion(
+            Quaternion::from_euler(Vector3::default()),
+            Quaternion::IDENTITY,
+        );
+        let vector = angles(1.0, 2.0, 3.0);
+        assert_eq!(Quaternion::IDENTITY.rotate(vector), vector);
+    }
+
+    #[test]
+    fn normalized_quaternion_has_a_length_of_one() {
+        let quaternion = Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 3.0,
+            w: 4.0,
+        };
+
+        assert_quaternion(
+            quaternion.normalized(),
+            Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: 0.6,
+                w: 0.8,
+            },
+        );
+        assert_eq!(Quaternion::default().normalized(), Quaternion::IDENTITY);
+    }
+
+    #[test]
+    fn rotation_turns_vectors_and_combines_with_mul() {
+        let quarter_y = Quaternion::from_euler(angles(0.0, 90.0, 0.0));
+        let turned = quarter_y.rotate(angles(1.0, 0.0, 0.0));
+        assert_close(turned.x, 0.0);
+        assert_close(turned.y, 0.0);
+        assert_close(turned.z, -1.0);
+
+        let half_y = quarter_y.mul(&quarter_y);
+        assert_quaternion(half_y, Quaternion::from_euler(angles(0.0, 180.0, 0.0)));
+    }
+}
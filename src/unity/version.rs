@@ -0,0 +1,99 @@
+use std::{fmt, fs, path::Path};
+
+/// The file where Unity writes the version of the editor that last opened the project
+const PROJECT_VERSION_PATH: &str = "ProjectSettings/ProjectVersion.txt";
+
+/// A version of the Unity editor, like `2022.3.10f1`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnityVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// The release type and number after the patch, like `f1`
+    pub suffix: String,
+}
+
+impl UnityVersion {
+    pub fn parse(text: &str) -> Option<UnityVersion> {
+        let mut parts = text.trim().splitn(3, '.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let rest = parts.next().unwrap_or("0");
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let patch = rest[..digits].parse().ok()?;
+        Some(UnityVersion {
+            major,
+            minor,
+            patch,
+            suffix: rest[digits..].to_owned(),
+        })
+    }
+
+    /// Whether this version is the same as or newer than `major.minor`
+    pub fn is_at_least(&self, major: u32, minor: u32) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+
+    /// Whether Unity writes the field to the documents of the class, fields that are not listed in
+    /// [`VERSIONED_FIELDS`] are taken as written by every version
+    pub fn has_field(&self, class_name: &str, field: &str) -> bool {
+        VERSIONED_FIELDS
+            .iter()
+            .filter(|versioned| versioned.class_name == class_name && versioned.field == field)
+            .all(|versioned| {
+                versioned
+                    .since
+                    .is_none_or(|(major, minor)| self.is_at_least(major, minor))
+                    && versioned
+                        .removed_in
+                        .is_none_or(|(major, minor)| !self.is_at_least(major, minor))
+            })
+    }
+}
+
+impl fmt::Display for UnityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}{}",
+            self.major, self.minor, self.patch, self.suffix
+        )
+    }
+}
+
+/// A field that Unity only writes in some versions
+struct VersionedField {
+    class_name: &'static str,
+    field: &'static str,
+    /// The first `major.minor` version that writes the field, None if every older version does
+    since: Option<(u32, u32)>,
+    /// The first `major.minor` version that does not write the field anymore
+    removed_in: Option<(u32, u32)>,
+}
+
+const VERSIONED_FIELDS: [VersionedField; 2] = [
+    // the order of the children is only kept in the m_Children of the parent and in SceneRoots
+    VersionedField {
+        class_name: "Transform",
+        field: "m_RootOrder",
+        since: None,
+        removed_in: Some((2022, 2)),
+    },
+    VersionedField {
+        class_name: "RectTransform",
+        field: "m_RootOrder",
+        since: None,
+        removed_in: Some((2022, 2)),
+    },
+];
+
+/// Reads the editor version from `ProjectSettings/ProjectVersion.txt` of the project, None if the
+/// file is missing or cannot be read
+pub fn read_project_version(project_path: &Path) -> Option<UnityVersion> {
+    let text = fs::read_to_string(project_path.join(PROJECT_VERSION_PATH)).ok()?;
+    text.lines()
+        .find_map(|line| line.strip_prefix("m_EditorVersion:"))
+        .and_then(UnityVersion::parse)
+}
@@ -0,0 +1,819 @@
+use crate::unity::{
+    converters::{helpers::*, AsF32, GetFromStr},
+    object::{
+        Component, Field, GameObject, ObjectReference, PrefabInstance, PropertyModification,
+        Transform,
+    },
+    prefab::body_mut,
+    vector::EULER_DECIMALS,
+    yaml::{parse_str, YamlUnityDocument, YamlUnityFile},
+    Object,
+};
+use std::{fs, io::Error, path::Path};
+use unity_yaml_rust::{yaml::Hash, Yaml};
+
+/// Writes the file to disk, see [`file_to_string`]
+pub fn write_file(path: &Path, file: &YamlUnityFile) -> Result<(), Error> {
+    fs::write(path, file_to_string(file))
+}
+
+/// Serialises the file in Unity's text format.
+///
+/// Documents that were not changed since they were read are written with their original text, so
+/// writing an unchanged file gives back the same bytes. Changed documents keep the original text
+/// of the lines that did not change, the rest is written the way Unity writes it.
+pub fn file_to_string(file: &YamlUnityFile) -> String {
+    let mut out = file.preamble.clone();
+    for doc in file.documents.iter() {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push_str(file.line_ending);
+        }
+        match &doc.source {
+            Some(source) if doc.is_unreadable() => out.push_str(source),
+            Some(source) => write_changed_document(&mut out, doc, source, file.line_ending),
+            None => {
+                for line in document_to_lines(doc) {
+                    out.push_str(&line);
+                    out.push_str(file.line_ending);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Returns the header line of a document, like `--- !u!4 &1234 stripped`
+pub fn document_header(doc: &YamlUnityDocument) -> String {
+    format!(
+        "--- !u!{} &{}{}",
+        doc.class_id,
+        doc.id,
+        if doc.stripped { " stripped" } else { "" }
+    )
+}
+
+/// Writes the values of an edited object back into its document. Only values that changed are
+/// replaced, in place, so the rest of the document keeps its formatting and key order
+pub fn update_document(doc: &mut YamlUnityDocument, object: &Object) {
+    let Some(map) = body_mut(&mut doc.document) else { return; };
+    match object {
+        Object::GameObject(go) => update_game_object(map, go),
+        Object::Component(component) => update_component(map, component),
+        Object::PrefabInstance(instance) => update_prefab_instance(map, instance),
+        // stripped objects are edited through the modifications of their prefab instance
+        Object::Stripped(_) => {}
+        Object::SceneRoots(roots) => {
+            let roots = roots
+                .root_ids
+                .iter()
+                .map(|id| local_reference(id))
+                .collect();
+            set_if_changed(map, "m_Roots", Yaml::Array(roots));
+        }
+    }
+}
+
+/// Writes the parent, the modifications and the removed objects. Modifications that are kept get
+/// their item back, so the values that did not change are written the way they were read
+fn update_prefab_instance(map: &mut Hash, instance: &PrefabInstance) {
+    let Some(Yaml::Hash(modification)) = map.get_mut(&Yaml::String("m_Modification".to_owned())) else { return; };
+    set_if_changed(
+        modification,
+        "m_TransformParent",
+        local_reference(&instance.transform_parent_id),
+    );
+
+    let mut current = match modification.get_from_str("m_Modifications") {
+        Some(Yaml::Array(items)) => items.iter().map(Some).collect(),
+        _ => vec![],
+    };
+    let items = instance
+        .modifications
+        .iter()
+        .map(|m| modification_item(&mut current, m))
+        .collect();
+    set_if_changed(modification, "m_Modifications", Yaml::Array(items));
+
+    let references = |references: &[ObjectReference]| {
+        Yaml::Array(
+            references
+                .iter()
+                .map(|reference| Yaml::Hash(reference_to_obj(reference)))
+                .collect(),
+        )
+    };
+    set_if_changed(
+        modification,
+        "m_RemovedComponents",
+        references(&instance.removed_components),
+    );
+    if modification.get_from_str("m_RemovedGameObjects").is_some() {
+        set_if_changed(
+            modification,
+            "m_RemovedGameObjects",
+            references(&instance.removed_game_objects),
+        );
+    }
+}
+
+/// Returns the item of the modification, the first unused one of the current items with the same
+/// target and property if there is one
+fn modification_item(current: &mut [Option<&Yaml>], m: &PropertyModification) -> Yaml {
+    let is_same = |item: &Yaml| {
+        let Some(item) = item.as_hash() else { return false; };
+        item.get_from_str("propertyPath")
+            .and_then(|path| path.as_str())
+            == Some(m.property_path.as_str())
+            && item
+                .get_from_str("target")
+                .and_then(|target| obj_to_reference(target.as_hash()?))
+                .is_some_and(|target| {
+                    target.file_id == m.target.file_id && target.guid == m.target.guid
+                })
+    };
+    let mut item = match current.iter_mut().find(|item| item.is_some_and(is_same)) {
+        Some(item) => item.take().and_then(|item| item.as_hash()).cloned(),
+        None => None,
+    }
+    .unwrap_or_else(|| {
+        let mut item = Hash::new(true);
+        item.insert(
+            Yaml::String("target".to_owned()),
+            Yaml::Hash(reference_to_obj(&m.target)),
+        );
+        item.insert(
+            Yaml::String("propertyPath".to_owned()),
+            Yaml::String(m.property_path.clone()),
+        );
+        item
+    });
+    set_if_changed(&mut item, "value", m.value.clone());
+    set_if_changed(
+        &mut item,
+        "objectReference",
+        Yaml::Hash(reference_to_obj(&m.object_reference)),
+    );
+    Yaml::Hash(item)
+}
+
+fn update_game_object(map: &mut Hash, go: &GameObject) {
+    set_if_changed(map, "m_Name", Yaml::String(go.name.clone()));
+    set_if_changed(map, "m_IsActive", Yaml::Integer(go.active as i64));
+    set_if_changed(map, "m_Layer", Yaml::Integer(go.layer as i64));
+    set_if_changed(map, "m_TagString", Yaml::String(go.tag.clone()));
+    let components = go
+        .component_ids
+        .iter()
+        .map(|id| {
+            let mut item = Hash::new(true);
+            item.insert(Yaml::String("component".to_owned()), local_reference(id));
+            Yaml::Hash(item)
+        })
+        .collect();
+    set_if_changed(map, "m_Component", Yaml::Array(components));
+}
+
+fn update_component(map: &mut Hash, component: &Component) {
+    set_if_changed(
+        map,
+        "m_GameObject",
+        local_reference(component.get_game_object_id()),
+    );
+    // components that cannot be disabled do not have the key
+    if map.get_from_str("m_Enabled").is_some() {
+        set_if_changed(
+            map,
+            "m_Enabled",
+            Yaml::Integer(component.is_enabled() as i64),
+        );
+    }
+    match component {
+        Component::Transform(transform) => update_transform(map, transform),
+        Component::MonoBehaviour(m) => {
+            for (name, field) in m.fields.iter() {
+                set_if_changed(map, name, field_to_yaml(field));
+            }
+        }
+        Component::Generic(g) => {
+            for (name, field) in g.fields.iter() {
+                set_if_changed(map, name, field_to_yaml(field));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn update_transform(map: &mut Hash, transform: &Transform) {
+    let (rotation, position, scale, root_order) = match transform {
+        Transform::Transform3D(t) => (
+            &t.local_rotation,
+            &t.local_position,
+            &t.local_scale,
+            t.root_order,
+        ),
+        Transform::RectTransform(t) => (
+            &t.local_rotation,
+            &t.local_position,
+            &t.local_scale,
+            t.root_order,
+        ),
+    };
+    let rotation_changed = map
+        .get_from_str("m_LocalRotation")
+        .is_none_or(|current| !same_value(current, &Yaml::Hash(vec4_to_obj(rotation))));
+    set_if_changed(map, "m_LocalRotation", Yaml::Hash(vec4_to_obj(rotation)));
+    // The editor shows the hint instead of the rotation, so it would show the old angles
+    if rotation_changed && map.get_from_str("m_LocalEulerAnglesHint").is_some() {
+        let angles = rotation.to_euler().rounded(EULER_DECIMALS);
+        set_if_changed(
+            map,
+            "m_LocalEulerAnglesHint",
+            Yaml::Hash(vec3_to_obj(&angles)),
+        );
+    }
+    set_if_changed(map, "m_LocalPosition", Yaml::Hash(vec3_to_obj(position)));
+    set_if_changed(map, "m_LocalScale", Yaml::Hash(vec3_to_obj(scale)));
+    let children = transform
+        .get_children_ids()
+        .iter()
+        .map(|id| local_reference(id))
+        .collect();
+    set_if_changed(map, "m_Children", Yaml::Array(children));
+    set_if_changed(map, "m_Father", local_reference(transform.get_father_id()));
+    // newer versions keep the order of the roots in SceneRoots, it is changed with the transform
+    if map.get_from_str("m_RootOrder").is_some() {
+        set_if_changed(map, "m_RootOrder", Yaml::Integer(root_order));
+    }
+    if let Transform::RectTransform(t) = transform {
+        set_if_changed(map, "m_AnchorMin", Yaml::Hash(vec2_to_obj(&t.anchor_min)));
+        set_if_changed(map, "m_AnchorMax", Yaml::Hash(vec2_to_obj(&t.anchor_max)));
+        set_if_changed(
+            map,
+            "m_AnchoredPosition",
+            Yaml::Hash(vec2_to_obj(&t.anchored_position)),
+        );
+        set_if_changed(map, "m_SizeDelta", Yaml::Hash(vec2_to_obj(&t.size_delta)));
+        set_if_changed(map, "m_Pivot", Yaml::Hash(vec2_to_obj(&t.pivot)));
+    }
+}
+
+fn local_reference(id: &str) -> Yaml {
+    Yaml::Hash(reference_to_obj(&ObjectReference {
+        file_id: id.to_owned(),
+        ..Default::default()
+    }))
+}
+
+pub fn field_to_yaml(field: &Field) -> Yaml {
+    match field {
+        Field::Vector2(vec) => Yaml::Hash(vec2_to_obj(vec)),
+        Field::Vector3(vec) => Yaml::Hash(vec3_to_obj(vec)),
+        Field::Vector4(vec) => Yaml::Hash(vec4_to_obj(vec)),
+        Field::F64(f) => f64_to_yaml(*f),
+        Field::I64(i) => Yaml::Integer(*i),
+        Field::Str(s) => Yaml::String(s.clone()),
+        Field::Bool(b) => Yaml::Boolean(*b),
+        Field::Color(color) => Yaml::Hash(color_to_obj(color)),
+        Field::Array(items) => Yaml::Array(items.iter().map(field_to_yaml).collect()),
+        Field::Reference(reference) => Yaml::Hash(reference_to_obj(reference)),
+        Field::Yaml(yaml) => yaml.clone(),
+    }
+}
+
+/// Replaces the value of the key if it means something else than the current one, a missing key is
+/// added at the end
+fn set_if_changed(map: &mut Hash, key: &str, value: Yaml) {
+    match map.get_mut(&Yaml::String(key.to_owned())) {
+        Some(current) => {
+            if !same_value(current, &value) {
+                *current = value;
+            }
+        }
+        None => {
+            map.insert(Yaml::String(key.to_owned()), value);
+        }
+    }
+}
+
+/// Compares values by what they mean instead of how they are written, so `0` and `0.0` are the same
+fn same_value(a: &Yaml, b: &Yaml) -> bool {
+    match (a, b) {
+        (Yaml::Real(_) | Yaml::Integer(_), Yaml::Real(_) | Yaml::Integer(_)) => match (a, b) {
+            (Yaml::Integer(a), Yaml::Integer(b)) => a == b,
+            _ => a.as_f32().is_some() && a.as_f32() == b.as_f32(),
+        },
+        (Yaml::Hash(a), Yaml::Hash(b)) => {
+            a.map.len() == b.map.len()
+                && a.iter()
+                    .all(|(key, value)| b.get(key).is_some_and(|other| same_value(value, other)))
+        }
+        (Yaml::Array(a), Yaml::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| same_value(a, b))
+        }
+        (Yaml::Null, Yaml::String(s)) | (Yaml::String(s), Yaml::Null) => s.is_empty(),
+        _ => a == b,
+    }
+}
+
+fn document_to_lines(doc: &YamlUnityDocument) -> Vec<String> {
+    let mut lines = vec![document_header(doc)];
+    lines.extend(body_to_lines(&doc.document));
+    lines
+}
+
+fn body_to_lines(document: &Yaml) -> Vec<String> {
+    let mut lines = vec![];
+    match document {
+        Yaml::Hash(hash) => write_map(&mut lines, hash, 0),
+        Yaml::Null => {}
+        yaml => lines.push(scalar_to_string(yaml)),
+    }
+    lines
+}
+
+fn write_changed_document(
+    out: &mut String,
+    doc: &YamlUnityDocument,
+    source: &str,
+    line_ending: &str,
+) {
+    let Some(original) = parse_str(source).ok().and_then(|mut docs| docs.pop()) else {
+        for line in document_to_lines(doc) {
+            out.push_str(&line);
+            out.push_str(line_ending);
+        }
+        return;
+    };
+    let header_changed = original.class_id != doc.class_id
+        || original.id != doc.id
+        || original.stripped != doc.stripped;
+    if !header_changed && original.document == doc.document {
+        out.push_str(source);
+        return;
+    }
+
+    let line_ending = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut source_lines = source.lines();
+    let source_header = source_lines.next().unwrap_or_default();
+    let source_lines = source_lines.collect::<Vec<&str>>();
+    out.push_str(
+        if header_changed {
+            document_header(doc)
+        } else {
+            source_header.to_owned()
+        }
+        .as_str(),
+    );
+    out.push_str(line_ending);
+
+    for line in merge_lines(
+        &source_lines,
+        &body_to_lines(&original.document),
+        &body_to_lines(&doc.document),
+    ) {
+        out.push_str(line);
+        out.push_str(line_ending);
+    }
+}
+
+/// Takes the lines of the changed document and puts back the original text of the lines that did
+/// not change. The lines of the original document written from scratch tell which lines these are
+fn merge_lines<'a>(source: &[&'a str], original: &[String], changed: &'a [String]) -> Vec<&'a str> {
+    if source.len() != original.len() {
+        // the original text is formatted differently, a line cannot be matched to the source
+        return changed.iter().map(|line| line.as_str()).collect();
+    }
+    if original.len() == changed.len() {
+        return original
+            .iter()
+            .zip(changed.iter())
+            .zip(source.iter())
+            .map(|((original, changed), source)| {
+                if original == changed {
+                    *source
+                } else {
+                    changed.as_str()
+                }
+            })
+            .collect();
+    }
+
+    let prefix = original
+        .iter()
+        .zip(changed.iter())
+        .take_while(|(original, changed)| original == changed)
+        .count();
+    let max_suffix = original.len().min(changed.len()) - prefix;
+    let suffix = original
+        .iter()
+        .rev()
+        .zip(changed.iter().rev())
+        .take(max_suffix)
+        .take_while(|(original, changed)| original == changed)
+        .count();
+    source[..prefix]
+        .iter()
+        .copied()
+        .chain(
+            changed[prefix..changed.len() - suffix]
+                .iter()
+                .map(|l| l.as_str()),
+        )
+        .chain(source[source.len() - suffix..].iter().copied())
+        .collect()
+}
+
+fn write_map(lines: &mut Vec<String>, hash: &Hash, indent: usize) {
+    for (key, value) in hash.iter() {
+        let key = format!("{}{}:", " ".repeat(indent), scalar_to_string(key));
+        match value {
+            Yaml::Hash(map) if map.block && !map.is_empty() => {
+                lines.push(key);
+                write_map(lines, map, indent + 2);
+            }
+            // Unity writes the items of a list at the same indent as the key
+            Yaml::Array(items) if !items.is_empty() => {
+                lines.push(key);
+                write_list(lines, items, indent);
+            }
+            Yaml::Hash(_) | Yaml::Array(_) => {
+                lines.push(format!("{} {}", key, flow_to_string(value)))
+            }
+            _ => lines.push(format!("{} {}", key, scalar_to_string(value))),
+        }
+    }
+}
+
+fn write_list(lines: &mut Vec<String>, items: &[Yaml], indent: usize) {
+    let prefix = format!("{}- ", " ".repeat(indent));
+    for item in items {
+        let mut item_lines = vec![];
+        match item {
+            Yaml::Hash(map) if map.block && !map.is_empty() => {
+                write_map(&mut item_lines, map, indent + 2)
+            }
+            Yaml::Array(items) if !items.is_empty() => {
+                write_list(&mut item_lines, items, indent + 2)
+            }
+            Yaml::Hash(_) | Yaml::Array(_) => item_lines.push(format!(
+                "{}{}",
+                " ".repeat(indent + 2),
+                flow_to_string(item)
+            )),
+            _ => item_lines.push(format!(
+                "{}{}",
+                " ".repeat(indent + 2),
+                scalar_to_string(item)
+            )),
+        }
+        // the first line of the item goes after the dash
+        if let Some(first) = item_lines.first_mut() {
+            first.replace_range(..indent + 2, &prefix);
+        }
+        lines.append(&mut item_lines);
+    }
+}
+
+fn flow_to_string(yaml: &Yaml) -> String {
+    match yaml {
+        Yaml::Hash(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(k, v)| format!("{}: {}", scalar_to_string(k), flow_to_string(v)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Yaml::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(flow_to_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        _ => scalar_to_string(yaml),
+    }
+}
+
+fn scalar_to_string(yaml: &Yaml) -> String {
+    match yaml {
+        Yaml::Real(s) => s.clone(),
+        Yaml::Integer(i) => i.to_string(),
+        Yaml::String(s) => string_to_yaml(s),
+        Yaml::Boolean(b) => b.to_string(),
+        Yaml::Original(s) => s.clone(),
+        Yaml::Null | Yaml::BadValue | Yaml::Alias(_) => "".to_owned(),
+        Yaml::Hash(_) | Yaml::Array(_) => flow_to_string(yaml),
+    }
+}
+
+/// Writes strings plain when they read back as the same string, single quoted otherwise and double
+/// quoted when they contain characters that need escaping
+fn string_to_yaml(s: &str) -> String {
+    if s.chars().any(|ch| ch.is_control()) {
+        let escaped = s
+            .chars()
+            .map(|ch| match ch {
+                '\n' => "\\n".to_owned(),
+                '\r' => "\\r".to_owned(),
+                '\t' => "\\t".to_owned(),
+                '"' => "\\\"".to_owned(),
+                '\\' => "\\\\".to_owned(),
+                ch if ch.is_control() => format!("\\x{:02X}", ch as u32),
+                ch => ch.to_string(),
+            })
+            .collect::<String>();
+        return format!("\"{}\"", escaped);
+    }
+    if is_plain_safe(s) {
+        s.to_owned()
+    } else {
+        format!("'{}'", s.replace('\'', "''"))
+    }
+}
+
+/// Plain scalars that are read as null, a bool or a special float instead of a string
+const NON_STRING_WORDS: [&str; 22] = [
+    "~", "null", "Null", "NULL", "true", "True", "TRUE", "false", "False", "FALSE", ".inf", ".Inf",
+    ".INF", "-.inf", "-.Inf", "-.INF", "+.inf", "+.Inf", "+.INF", ".nan", ".NaN", ".NAN",
+];
+
+fn is_plain_safe(s: &str) -> bool {
+    if s.is_empty() || s.starts_with(' ') || s.ends_with(' ') {
+        return false;
+    }
+    if s.starts_with([
+        '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+    ]) {
+        return false;
+    }
+    // a dash alone or before a space starts a list item
+    if s == "-" || s.starts_with("- ") {
+        return false;
+    }
+    if s.contains(": ") || s.contains(" #") || s.ends_with(':') {
+        return false;
+    }
+    !NON_STRING_WORDS.contains(&s) && !reads_as_number(s)
+}
+
+/// Integers, hexadecimal and octal ones included, and floats are not read back as strings
+fn reads_as_number(s: &str) -> bool {
+    let unsigned = s.trim_start_matches(['-', '+']);
+    s.parse::<i64>().is_ok()
+        || s.parse::<f64>().is_ok()
+        || unsigned.starts_with("0x")
+        || unsigned.starts_with("0o")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unity::{
+        construct_repository, edit,
+        object::GetId,
+        overrides,
+        test_util::{read_repository, SCENE},
+        yaml::parse_unity_str,
+    };
+
+    const PREFAB_INSTANCE: &str = "\
+%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1001 &200
+PrefabInstance:
+  m_ObjectHideFlags: 0
+  serializedVersion: 2
+  m_Modification:
+    serializedVersion: 3
+    m_TransformParent: {fileID: 0}
+    m_Modifications:
+    - target: {fileID: 400, guid: 0123456789abcdef0123456789abcdef, type: 3}
+      propertyPath: m_Name
+      value: Enemy (1)
+      objectReference: {fileID: 0}
+    - target: {fileID: 500, guid: 0123456789abcdef0123456789abcdef, type: 3}
+      propertyPath: speed
+      value: 2.5
+      objectReference: {fileID: 0}
+    - target: {fileID: 500, guid: 0123456789abcdef0123456789abcdef, type: 3}
+      propertyPath: label
+      value: 
+      objectReference: {fileID: 0}
+    m_RemovedComponents:
+    - {fileID: 600, guid: 0123456789abcdef0123456789abcdef, type: 3}
+    m_RemovedGameObjects: []
+    m_AddedGameObjects: []
+    m_AddedComponents: []
+  m_SourcePrefab: {fileID: 100100000, guid: 0123456789abcdef0123456789abcdef, type: 3}
+--- !u!4 &300 stripped
+Transform:
+  m_CorrespondingSourceObject: {fileID: 400, guid: 0123456789abcdef0123456789abcdef, type: 3}
+  m_PrefabInstance: {fileID: 200}
+  m_PrefabAsset: {fileID: 0}
+";
+
+    fn round_trip(content: &str) -> String {
+        file_to_string(&parse_unity_str(content))
+    }
+
+    /// Writes the object back into its document the way an edit does
+    fn update(file: &mut YamlUnityFile, object: Object) {
+        let doc = file
+            .documents
+            .iter_mut()
+            .find(|doc| &doc.id == object.get_id() && !doc.stripped)
+            .unwrap();
+        update_document(doc, &object);
+    }
+
+    fn renamed(file: &YamlUnityFile, id: &str, name: &str) -> Object {
+        let repo = read_repository(&file_to_string(file));
+        let mut go = repo.get_game_object(&id.to_owned()).unwrap().clone();
+        go.name = name.to_owned();
+        Object::GameObject(go)
+    }
+
+    fn moved(file: &YamlUnityFile, id: &str, y: f32) -> Object {
+        let repo = read_repository(&file_to_string(file));
+        let mut transform = repo.get_transform(&id.to_owned()).unwrap().clone();
+        match &mut transform {
+            Transform::Transform3D(t) => t.local_position.y = y,
+            Transform::RectTransform(t) => t.local_position.y = y,
+        }
+        Object::Component(Component::Transform(transform))
+    }
+
+    #[test]
+    fn unchanged_files_are_written_as_they_were_read() {
+        let crlf = format!("\u{feff}{}", SCENE.replace('\n', "\r\n"));
+        for content in [SCENE, crlf.as_str(), SCENE.trim_end(), PREFAB_INSTANCE] {
+            assert_eq!(round_trip(content), content);
+        }
+    }
+
+    #[test]
+    fn changed_value_only_changes_its_line() {
+        let mut file = parse_unity_str(SCENE);
+        let object = renamed(&file, "100", "Enemy");
+        update(&mut file, object);
+
+        assert_eq!(
+            file_to_string(&file),
+            SCENE.replace("m_Name: Player", "m_Name: Enemy")
+        );
+    }
+
+    #[test]
+    fn byte_order_mark_and_crlf_are_kept() {
+        let content = format!("\u{feff}{}", SCENE.replace('\n', "\r\n"));
+        let mut file = parse_unity_str(&content);
+        let object = moved(&file, "121", 2.0);
+        update(&mut file, object);
+
+        assert_eq!(
+            file_to_string(&file),
+            content.replace(
+                "m_LocalPosition: {x: 0, y: 1, z: -10}",
+                "m_LocalPosition: {x: 0, y: 2, z: -10}"
+            )
+        );
+    }
+
+    #[test]
+    fn missing_trailing_newline_is_kept_for_unchanged_documents() {
+        let content = SCENE.trim_end();
+        let mut file = parse_unity_str(content);
+        let object = renamed(&file, "100", "Enemy");
+        update(&mut file, object);
+
+        assert_eq!(
+            file_to_string(&file),
+            content.replace("m_Name: Player", "m_Name: Enemy")
+        );
+    }
+
+    #[test]
+    fn reverted_modifications_remove_their_items() {
+        let mut file = parse_unity_str(PREFAB_INSTANCE);
+        let (repo, _) = construct_repository(file.documents.clone());
+        let instance = repo.get_prefab_instance(&"200".to_owned()).unwrap();
+        let edit::Operation::Set { after, .. } = overrides::revert_modifications(instance, &[1]) else { panic!("a revert sets the prefab instance"); };
+        update(&mut file, *after);
+
+        assert_eq!(
+            file_to_string(&file),
+            PREFAB_INSTANCE.replace(
+                "    - target: {fileID: 500, guid: 0123456789abcdef0123456789abcdef, type: 3}
+      propertyPath: speed
+      value: 2.5
+      objectReference: {fileID: 0}
+",
+                ""
+            )
+        );
+    }
+
+    #[test]
+    fn reverting_all_restores_removed_components() {
+        let mut file = parse_unity_str(PREFAB_INSTANCE);
+        let (repo, _) = construct_repository(file.documents.clone());
+        let instance = repo.get_prefab_instance(&"200".to_owned()).unwrap();
+        let edit::Operation::Set { after, .. } = overrides::revert_all(instance, &[0, 1, 2]) else { panic!("a revert sets the prefab instance"); };
+        update(&mut file, *after);
+
+        let (start, _) = PREFAB_INSTANCE.split_once("    m_Modifications:").unwrap();
+        let (_, end) = PREFAB_INSTANCE
+            .split_once("    m_RemovedGameObjects")
+            .unwrap();
+        assert_eq!(
+            file_to_string(&file),
+            format!(
+                "{}    m_Modifications: []\n    m_RemovedComponents: []\n    m_RemovedGameObjects{}",
+                start, end
+            )
+        );
+    }
+
+    /// Reads the written scalar back the way the file is read
+    fn read_back(written: &str) -> Option<Yaml> {
+        parse_str(&format!("--- !u!1 &1\nA: {}", written))
+            .ok()?
+            .pop()?
+            .document
+            .as_hash()?
+            .get_from_str("A")
+            .cloned()
+    }
+
+    #[test]
+    fn strings_read_as_something_else_are_quoted() {
+        let quoted = [
+            "",
+            " padded",
+            "padded ",
+            "true",
+            "False",
+            "null",
+            "~",
+            "12",
+            "-3.5",
+            "1e5",
+            "0x1F",
+            ".inf",
+            "-.INF",
+            ".nan",
+            "-",
+            "- item",
+            "a: b",
+            "a #b",
+            "key:",
+            "#tag",
+            "&anchor",
+            "*alias",
+            "!tag",
+            "'single'",
+            "\"double\"",
+            "[list]",
+            "{map}",
+            "%directive",
+            "@at",
+            "`tick",
+            "|",
+            ">",
+            "?",
+            ",",
+        ];
+        for s in quoted {
+            let written = string_to_yaml(s);
+            assert!(written.starts_with('\''), "{} is written as {}", s, written);
+            assert_eq!(read_back(&written), Some(Yaml::String(s.to_owned())));
+        }
+    }
+
+    #[test]
+    fn other_strings_are_written_plain() {
+        let plain = [
+            "Player",
+            "Main Camera",
+            "a-b",
+            "-x",
+            "v1.2.3",
+            "C#",
+            "50%",
+            "a:b",
+            "Enemy (1)",
+            "1.2.3",
+            "truely",
+        ];
+        for s in plain {
+            assert_eq!(string_to_yaml(s), s);
+            assert_eq!(read_back(s), Some(Yaml::String(s.to_owned())));
+        }
+        assert_eq!(string_to_yaml("tab\there"), "\"tab\\there\"");
+    }
+}
@@ -1,104 +1,373 @@
-use crate::class_id::ClassId;
-use crate::unity::Id;
-use crate::util::{ErrTo, PairWith};
-use std::io::Read;
-use std::{fs::File, io::Error, path::Path};
+use crate::class_id::{ClassId, CLASS_IDS};
+use crate::unity::{
+    error::{ParseError, ParseErrorKind},
+    Id,
+};
+use std::{fs, ops::Range, path::Path};
 use unity_yaml_rust::{Yaml, YamlLoader};
 
+const DOCUMENT_START: &str = "--- ";
+const CLASS_ID_PREFIX: &str = "!u!";
+const FILE_ID_PREFIX: char = '&';
+const STRIPPED_MARKER: &str = "stripped";
+const BYTE_ORDER_MARK: char = '\u{feff}';
+/// Text Unity files start with it, assets like lighting data are stored in a binary format
+const YAML_DIRECTIVE: &str = "%YAML";
+
+#[derive(Debug, Clone)]
 pub struct YamlUnityDocument {
     pub class_id: ClassId,
     pub id: Id,
+    /// Stripped documents only hold a link to the prefab instance they come from and the source object
+    pub stripped: bool,
     pub document: Yaml,
+    /// The text of the document as it was read, header line included. None for new documents
+    pub source: Option<String>,
+    /// The line of the header in the text it was read from, counted from 1
+    pub line: Option<usize>,
+    /// Where `source` is in the text it was read from, in bytes
+    pub span: Option<Range<usize>>,
+}
+
+impl YamlUnityDocument {
+    /// Documents that could not be read have no class id and no file id, they are kept so they can
+    /// be written back as they were
+    pub fn is_unreadable(&self) -> bool {
+        self.id.is_empty()
+    }
+}
+
+/// A whole Unity file, with everything needed to write it back as it was read
+pub struct YamlUnityFile {
+    /// Everything before the first document, usually the %YAML and %TAG directives and the byte
+    /// order mark of the file if it has one
+    pub preamble: String,
+    pub line_ending: &'static str,
+    pub documents: Vec<YamlUnityDocument>,
+    /// The documents that could not be read, see [`YamlUnityDocument::is_unreadable`]
+    pub errors: Vec<ParseError>,
+}
+
+/// The `--- !u!<class id> &<file id> [stripped]` line that starts every document of a Unity file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentHeader {
+    pub class_id: ClassId,
+    pub id: Id,
+    pub stripped: bool,
+}
+
+/// Reads a header line. File ids are signed 64-bit numbers, the ones of prefab instances are often
+/// negative or bigger than 32 bits
+pub fn parse_header(line: &str) -> Option<DocumentHeader> {
+    let mut parts = line
+        .trim_end_matches(['\r', '\n'])
+        .strip_prefix(DOCUMENT_START)?
+        .split_whitespace();
+    let class_id = parts
+        .next()?
+        .strip_prefix(CLASS_ID_PREFIX)?
+        .parse::<ClassId>()
+        .ok()?;
+    let id = parts.next()?.strip_prefix(FILE_ID_PREFIX)?;
+    id.parse::<i64>().ok()?;
+    let stripped = match parts.next() {
+        Some(STRIPPED_MARKER) => true,
+        Some(_) => return None,
+        None => false,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(DocumentHeader {
+        class_id,
+        id: id.to_owned(),
+        stripped,
+    })
 }
 
-fn bugfix_remove_negative_ids(text: String) -> (String, Vec<usize>) {
-    let mut indices = vec![];
-    let mut occurrence = 0;
-
-    text.lines()
-        .map(|line| {
-            if line.starts_with("--- !u!") {
-                if let Some(ch) = line.chars().skip_while(|ch| *ch != '&').nth(1) {
-                    occurrence += 1;
-                    if ch == '-' {
-                        indices.push(occurrence - 1);
-                        line.replace("&-", "&")
-                    } else {
-                        line.to_owned()
-                    }
-                } else {
-                    line.to_owned()
-                }
-            } else {
-                line.to_owned()
+/// A document of a Unity file as it is in the text, before its YAML is read
+struct DocumentText<'a> {
+    /// The header line without its line ending
+    header: &'a str,
+    /// Everything after the header line
+    body: &'a str,
+    /// The header line and the body together
+    span: Range<usize>,
+    line: usize,
+}
+
+/// Splits the text into the part before the first document and the documents. Documents start at
+/// every line beginning with `--- `, the line endings and a byte order mark are kept in the spans
+fn split_documents(content: &str) -> (&str, Vec<DocumentText<'_>>) {
+    let mut starts = vec![];
+    let mut offset = 0;
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        let text = if idx == 0 {
+            line.trim_start_matches(BYTE_ORDER_MARK)
+        } else {
+            line
+        };
+        if text.starts_with(DOCUMENT_START) {
+            starts.push((offset + line.len() - text.len(), idx + 1));
+        }
+        offset += line.len();
+    }
+    let preamble = &content[..starts.first().map_or(content.len(), |(start, _)| *start)];
+    let documents = starts
+        .iter()
+        .enumerate()
+        .map(|(idx, (start, line))| {
+            let end = starts.get(idx + 1).map_or(content.len(), |(end, _)| *end);
+            let text = &content[*start..end];
+            let header_end = text.find('\n').map_or(text.len(), |idx| idx + 1);
+            DocumentText {
+                header: text[..header_end].trim_end_matches(['\r', '\n']),
+                body: &text[header_end..],
+                span: *start..end,
+                line: *line,
             }
         })
-        .collect::<Vec<String>>()
-        .join("\r\n")
-        .pair_with(indices)
+        .collect();
+    (preamble, documents)
 }
 
-fn bugfix_restore_negative_ids(
-    mut docs: Vec<YamlUnityDocument>,
-    indices: Vec<usize>,
-) -> Vec<YamlUnityDocument> {
-    for idx in indices.iter() {
-        let doc = &mut docs[*idx];
-        doc.id = format!("-{}", doc.id);
-    }
-    docs
+pub fn parse_file(path: &Path) -> Result<Vec<YamlUnityDocument>, ParseError> {
+    Ok(parse_unity_file(path)?.documents)
 }
 
-pub fn parse_file(path: &Path) -> Result<Vec<YamlUnityDocument>, Error> {
-    let mut file = File::open(path)?;
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
+/// Tells binary assets apart from the ones in Unity's YAML format. A file that cannot be read is not
+/// taken as binary, so the error is reported when it is parsed
+pub fn is_binary_file(path: &Path) -> bool {
+    let Ok(content) = fs::read(path) else { return false; };
+    let mut bom = [0; 4];
+    let bom = BYTE_ORDER_MARK.encode_utf8(&mut bom).as_bytes();
+    let content = content.strip_prefix(bom).unwrap_or(&content);
+    !content.starts_with(YAML_DIRECTIVE.as_bytes())
+}
 
-    // BUG: document with negative Id cannot be parsed
-    let (content, indices) = bugfix_remove_negative_ids(content);
+pub fn parse_unity_file(path: &Path) -> Result<YamlUnityFile, ParseError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ParseError::new(ParseErrorKind::Io, e.to_string()).with_path(path))?;
+    let mut file = parse_unity_str(&content);
+    file.errors = file.errors.into_iter().map(|e| e.with_path(path)).collect();
+    Ok(file)
+}
 
-    let parsed = YamlLoader::load_from_str(&content).err_to_io_err()?;
+/// Reads the text of a Unity file, the documents keep their text like the ones read from disk
+pub fn parse_unity_str(content: &str) -> YamlUnityFile {
+    let (preamble, documents, errors) = parse_documents(content);
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    YamlUnityFile {
+        preamble: preamble.to_owned(),
+        line_ending,
+        documents,
+        errors,
+    }
+}
+
+/// Parses new documents, the ones that could not be read are left out. They are not from a file, so
+/// they are written like Unity writes them instead of like their text
+pub fn parse_str(content: &str) -> Result<Vec<YamlUnityDocument>, ParseError> {
+    let (_, mut docs, errors) = parse_documents(content);
+    docs.retain(|doc| !doc.is_unreadable());
+    for doc in docs.iter_mut() {
+        doc.source = None;
+        doc.line = None;
+        doc.span = None;
+    }
+    match errors.into_iter().next() {
+        Some(error) if docs.is_empty() => Err(error),
+        _ => Ok(docs),
+    }
+}
 
+/// Parses the documents of a Unity file one by one. A document with a header or a body that
+/// cannot be read is kept as unreadable with an error for it, the others are read anyway
+fn parse_documents(content: &str) -> (&str, Vec<YamlUnityDocument>, Vec<ParseError>) {
+    let (preamble, texts) = split_documents(content);
     let mut docs = vec![];
-    for doc in parsed {
-        match doc {
-            Yaml::Original(s) => {
-                if s.starts_with("%YAML") || s.starts_with("%TAG") {
-                    continue;
-                } else if s.starts_with("--- ") {
-                    let mut parts = s.split(' ');
-                    parts.next();
-                    let class_id = parts
-                        .next()
-                        .unwrap()
-                        .strip_prefix("!u!")
-                        .unwrap()
-                        .to_string()
-                        .parse::<ClassId>()
-                        .unwrap();
-                    let id = parts.next().unwrap().strip_prefix('&').unwrap().to_string();
-                    docs.push(YamlUnityDocument {
-                        class_id,
-                        id,
-                        document: Yaml::Null,
-                    })
-                } else if let Some(d) = docs.last_mut() {
-                    if d.document == Yaml::Null {
-                        d.document = Yaml::Original(s)
-                    }
-                }
-            }
-            doc => {
-                if let Some(d) = docs.last_mut() {
-                    if d.document == Yaml::Null {
-                        d.document = doc
-                    }
-                }
+    let mut errors = vec![];
+    for text in texts {
+        let mut doc = YamlUnityDocument {
+            class_id: 0,
+            id: Id::new(),
+            stripped: false,
+            document: Yaml::Null,
+            source: Some(content[text.span.clone()].to_owned()),
+            line: Some(text.line),
+            span: Some(text.span.clone()),
+        };
+        match read_document(&text) {
+            Ok((header, document)) => {
+                doc.class_id = header.class_id;
+                doc.id = header.id;
+                doc.stripped = header.stripped;
+                doc.document = document;
             }
+            Err(error) => errors.push(error),
+        }
+        docs.push(doc);
+    }
+    (preamble, docs, errors)
+}
+
+fn read_document(text: &DocumentText) -> Result<(DocumentHeader, Yaml), ParseError> {
+    let header = parse_header(text.header).ok_or_else(|| {
+        ParseError::new(
+            ParseErrorKind::Header,
+            format!("invalid document header \"{}\"", text.header),
+        )
+        .with_line(Some(text.line))
+    })?;
+    let document = YamlLoader::load_from_str(text.body)
+        .map_err(|e| {
+            let error = ParseError::from_scan_error(&e);
+            // the loader counts the lines of the body, the header line comes before them
+            let line = error.line.map(|line| text.line + line);
+            let class_name = CLASS_IDS.get(&header.class_id).copied().unwrap_or_default();
+            error.with_document(&header.id, class_name).with_line(line)
+        })?
+        .into_iter()
+        // the loader gives back the lines it does not read as YAML, like directives
+        .find(|document| !matches!(document, Yaml::Original(_) | Yaml::BadValue))
+        .unwrap_or(Yaml::Null);
+    Ok((header, document))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(line: &str) -> Option<(ClassId, String, bool)> {
+        parse_header(line).map(|header| (header.class_id, header.id, header.stripped))
+    }
+
+    #[test]
+    fn header_ids_are_signed_64_bit_numbers() {
+        assert_eq!(
+            header("--- !u!1001 &-8679921383154817045"),
+            Some((1001, "-8679921383154817045".to_owned(), false))
+        );
+        assert_eq!(
+            header("--- !u!4 &5898421930462181312\r\n"),
+            Some((4, "5898421930462181312".to_owned(), false))
+        );
+        assert_eq!(header("--- !u!4 &9223372036854775808"), None);
+    }
+
+    #[test]
+    fn stripped_headers_are_marked() {
+        assert_eq!(
+            header("--- !u!4 &300 stripped"),
+            Some((4, "300".to_owned(), true))
+        );
+    }
+
+    #[test]
+    fn unexpected_headers_are_not_read() {
+        for line in [
+            "--- !u!4 &300 stripped extra",
+            "--- !u!4 &300 unknown",
+            "--- !u!4 &abc",
+            "--- !u!4 &",
+            "--- !u!x &300",
+            "--- !4 &300",
+            "--- !u!4 300",
+            "--- !u!4",
+            "!u!4 &300",
+        ] {
+            assert_eq!(header(line), None, "{}", line);
         }
     }
 
-    docs = bugfix_restore_negative_ids(docs, indices);
+    #[test]
+    fn preamble_and_spans_keep_the_byte_order_mark_and_crlf() {
+        let content = "\u{feff}%YAML 1.1\r\n%TAG !u! tag:unity3d.com,2011:\r\n--- !u!1 &1\r\nGameObject:\r\n  m_Name: A\r\n--- !u!1 &2\r\nGameObject:\r\n  m_Name: B\r\n";
+        let file = parse_unity_str(content);
+
+        assert_eq!(
+            file.preamble,
+            "\u{feff}%YAML 1.1\r\n%TAG !u! tag:unity3d.com,2011:\r\n"
+        );
+        assert_eq!(file.line_ending, "\r\n");
+        assert!(file.errors.is_empty());
+        let sources = file
+            .documents
+            .iter()
+            .map(|doc| &content[doc.span.clone().unwrap()])
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            sources,
+            [
+                "--- !u!1 &1\r\nGameObject:\r\n  m_Name: A\r\n",
+                "--- !u!1 &2\r\nGameObject:\r\n  m_Name: B\r\n",
+            ]
+        );
+        for doc in &file.documents {
+            assert_eq!(
+                doc.source.as_deref(),
+                Some(&content[doc.span.clone().unwrap()])
+            );
+        }
+    }
+
+    #[test]
+    fn byte_order_mark_before_the_first_document_is_not_in_its_span() {
+        let content = "\u{feff}--- !u!1 &1\nGameObject:\n  m_Name: A\n";
+        let file = parse_unity_str(content);
+
+        assert_eq!(file.preamble, "\u{feff}");
+        assert_eq!(file.line_ending, "\n");
+        assert_eq!(file.documents[0].id, "1");
+        assert_eq!(
+            file.documents[0].source.as_deref(),
+            Some("--- !u!1 &1\nGameObject:\n  m_Name: A\n")
+        );
+    }
 
-    Ok(docs)
+    #[test]
+    fn unreadable_documents_are_kept_with_their_line() {
+        let content = "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  m_Name: A\n--- !u!1 &bad\nGameObject:\n  m_Name: B\n--- !u!1 &3\nGameObject:\n  m_Name: [C\n--- !u!4 &4\nTransform:\n  m_Father: {fileID: 0}\n";
+        let file = parse_unity_str(content);
+
+        let ids = file
+            .documents
+            .iter()
+            .map(|doc| doc.id.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, ["1", "", "", "4"]);
+        let lines = file
+            .documents
+            .iter()
+            .map(|doc| doc.line.unwrap())
+            .collect::<Vec<usize>>();
+        assert_eq!(lines, [2, 5, 8, 11]);
+        assert!(file.documents[1].is_unreadable() && file.documents[2].is_unreadable());
+        assert_eq!(
+            file.documents[1].source.as_deref(),
+            Some("--- !u!1 &bad\nGameObject:\n  m_Name: B\n")
+        );
+
+        let errors = file
+            .errors
+            .iter()
+            .map(|error| (error.kind, error.line))
+            .collect::<Vec<(ParseErrorKind, Option<usize>)>>();
+        assert_eq!(errors[0], (ParseErrorKind::Header, Some(5)));
+        assert_eq!(errors[1].0, ParseErrorKind::Syntax);
+        assert!(errors[1].1.is_some_and(|line| line > 8));
+    }
+
+    #[test]
+    fn new_documents_have_no_text() {
+        let docs = parse_str("--- !u!1 &1\nGameObject:\n  m_Name: A\n--- !u!1 &bad\n").unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert!(docs[0].source.is_none() && docs[0].line.is_none() && docs[0].span.is_none());
+        assert!(parse_str("--- !u!1 &bad\nGameObject:\n").is_err());
+    }
 }
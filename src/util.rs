@@ -1,5 +1,3 @@
-use std::io::{Error, ErrorKind};
-
 macro_rules! hash_map {
     () => {
         std::collections::HashMap::new()
@@ -42,7 +40,6 @@ pub trait ErrTo<T, U> {
     /// assert_eq!(Err("hey".to_owned()), x.err_to_str());
     /// ```
     fn err_to_str(self) -> Result<T, String>;
-    fn err_to_io_err(self) -> Result<T, Error>;
 }
 
 impl<T, U> ErrTo<T, U> for Result<T, U>
@@ -52,27 +49,4 @@ where
     fn err_to_str(self) -> Result<T, String> {
         self.map_err(|e| e.to_string())
     }
-
-    fn err_to_io_err(self) -> Result<T, Error> {
-        self.map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
-    }
-}
-
-pub trait PairWith<T, U> {
-    /// Creates a tuple (T, U) by moving the given arguments.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// let (a, b) = 1.pair_with(2);
-    /// assert_eq!(a, 1);
-    /// assert_eq!(b, 2);
-    /// ```
-    fn pair_with(self, other: U) -> (T, U);
-}
-
-impl<T, U> PairWith<T, U> for T {
-    fn pair_with(self, other: U) -> (T, U) {
-        (self, other)
-    }
 }